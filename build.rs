@@ -1,6 +1,8 @@
 //! The build.rs file is necessary to generate rules.zip.
 //! rules.zip are needed so there is a way to get the rules dir into the build since your can't get from the crate.
 //! The expectation is that most builds (with the exception of WASM builds) will need a build.rs file to extract the rules.
+//!
+//! With the "c-header" feature, it also generates the C header (mathcat.h) for the C API in src/c_interface.rs.
 #![allow(clippy::needless_return)]
 
 use std::fs::{read_dir, DirBuilder, File, remove_file, remove_dir};
@@ -148,11 +150,39 @@ fn zip_entry<W: Write + Seek>(
     return Ok(n_files_in_zip);
 }
 
+/// Generate mathcat.h from src/c_interface.rs. It is written to OUT_DIR (the only place a build script should write).
+#[cfg(feature = "c-header")]
+fn generate_c_header(crate_dir: &Path, out_dir: &Path) {
+    let source = crate_dir.join("src").join("c_interface.rs");
+    println!("cargo::rerun-if-changed={}", source.display());
+    println!("cargo::rerun-if-changed=build.rs");
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("MATHCAT_H".to_string()),
+        cpp_compat: true,
+        usize_is_size_t: true,
+        header: Some(format!("/* MathCAT {} C API -- generated by build.rs from src/c_interface.rs. Do not edit. */",
+                             std::env::var("CARGO_PKG_VERSION").unwrap())),
+        documentation_style: cbindgen::DocumentationStyle::C99,
+        ..Default::default()
+    };
+    let header_path = out_dir.join("mathcat.h");
+    match cbindgen::Builder::new().with_config(config).with_src(&source).generate() {
+        Ok(bindings) => {
+            bindings.write_to_file(&header_path);
+        },
+        Err(e) => println!("cargo::warning=couldn't generate mathcat.h: {e}"),
+    }
+}
+
 /// Zip up each language and braille dir
 /// Note: regional variations (including zh-cn and zh-tw) are zipped together into one zip file
 fn main() {
     // This doesn't work because the build claims OUT_DIR is not defined(?)
     // let archive = PathBuf::from(concat!(env!("OUT_DIR"),"/rules.zip"));
+    #[cfg(feature = "c-header")]
+    generate_c_header(&PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()),
+                      &PathBuf::from(std::env::var_os("OUT_DIR").unwrap()));
     if std::env::var("CARGO_FEATURE_INCLUDE_ZIP").is_ok() {
         let out_dir = std::env::var_os("OUT_DIR").unwrap();
         let out_dir = PathBuf::from(&out_dir);
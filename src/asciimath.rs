@@ -0,0 +1,900 @@
+#![allow(clippy::needless_return)]
+//! Convert ASCIIMath (see <https://asciimath.org>) into MathML.
+//!
+//! This follows the grammar and symbol table of the reference implementation (ASCIIMathML.js):
+//! ```text
+//!   v  ::= [A-Za-z] | greek letters | numbers | other constant symbols
+//!   u  ::= sqrt | text | bb | other unary symbols for font commands
+//!   b  ::= frac | root | stackrel | other binary symbols
+//!   l  ::= ( | [ | { | (: | {: | other left brackets
+//!   r  ::= ) | ] | } | :) | :} | other right brackets
+//!   S  ::= v | l E r | u S | b S S             Simple expression
+//!   I  ::= S_S | S^S | S_S^S | S               Intermediate expression
+//!   E  ::= IE | I/I                            Expression
+//! ```
+//! Symbols are matched greedily (the longest match wins). Like the reference implementation, brackets around
+//! the arguments of fractions, scripts, and unary/binary symbols are dropped, and `((a,b),(c,d))` is a matrix.
+//! Like ASCIIMath, this never fails on odd input -- a missing argument becomes an empty `mrow`.
+
+use anyhow::{Result, bail};
+use phf::{phf_map, phf_set};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use sxd_document::dom::Element;
+use crate::canonicalize::{as_element, as_text, name};
+use crate::xpath_functions::is_leaf;
+
+/// How a symbol is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	Identifier,
+	Operator,
+	UnderOver,		// large operators and functions such as 'lim' whose scripts go under/over
+	Function,
+	LeftBracket,
+	RightBracket,
+	Space,
+	Text,			// 'text(...)' and 'mbox(...)'
+	Sqrt,
+	Fence,			// 'abs(x)', 'floor(x)', ... -- the output is the left and right fence chars
+	Accent,			// over accent
+	UnderAccent,
+	Font,			// the output is the mathvariant
+	Cancel,
+	Frac,
+	Root,
+	Over,			// 'stackrel' and 'overset'
+	Under,
+	Color,
+}
+
+/// The ASCIIMath symbol table: input -> (output, kind)
+static SYMBOLS: phf::Map<&str, (&str, Kind)> = phf_map! {
+	// Greek letters
+	"alpha" => ("α", Kind::Identifier), "beta" => ("β", Kind::Identifier), "chi" => ("χ", Kind::Identifier),
+	"delta" => ("δ", Kind::Identifier), "Delta" => ("Δ", Kind::Identifier), "epsi" => ("ε", Kind::Identifier),
+	"epsilon" => ("ε", Kind::Identifier), "varepsilon" => ("ɛ", Kind::Identifier), "eta" => ("η", Kind::Identifier),
+	"gamma" => ("γ", Kind::Identifier), "Gamma" => ("Γ", Kind::Identifier), "iota" => ("ι", Kind::Identifier),
+	"kappa" => ("κ", Kind::Identifier), "lambda" => ("λ", Kind::Identifier), "Lambda" => ("Λ", Kind::Identifier),
+	"lamda" => ("λ", Kind::Identifier), "Lamda" => ("Λ", Kind::Identifier), "mu" => ("μ", Kind::Identifier),
+	"nu" => ("ν", Kind::Identifier), "omega" => ("ω", Kind::Identifier), "Omega" => ("Ω", Kind::Identifier),
+	"phi" => ("ϕ", Kind::Identifier), "varphi" => ("φ", Kind::Identifier), "Phi" => ("Φ", Kind::Identifier),
+	"pi" => ("π", Kind::Identifier), "Pi" => ("Π", Kind::Identifier), "psi" => ("ψ", Kind::Identifier),
+	"Psi" => ("Ψ", Kind::Identifier), "rho" => ("ρ", Kind::Identifier), "sigma" => ("σ", Kind::Identifier),
+	"Sigma" => ("Σ", Kind::Identifier), "tau" => ("τ", Kind::Identifier), "theta" => ("θ", Kind::Identifier),
+	"vartheta" => ("ϑ", Kind::Identifier), "Theta" => ("Θ", Kind::Identifier), "upsilon" => ("υ", Kind::Identifier),
+	"xi" => ("ξ", Kind::Identifier), "Xi" => ("Ξ", Kind::Identifier), "zeta" => ("ζ", Kind::Identifier),
+
+	// binary operation symbols
+	"+" => ("+", Kind::Operator), "-" => ("−", Kind::Operator), "*" => ("⋅", Kind::Operator), "cdot" => ("⋅", Kind::Operator),
+	"**" => ("∗", Kind::Operator), "ast" => ("∗", Kind::Operator), "***" => ("⋆", Kind::Operator), "star" => ("⋆", Kind::Operator),
+	"//" => ("/", Kind::Operator), "\\\\" => ("\\", Kind::Operator), "backslash" => ("\\", Kind::Operator),
+	"setminus" => ("\\", Kind::Operator), "xx" => ("×", Kind::Operator), "times" => ("×", Kind::Operator),
+	"|><" => ("⋉", Kind::Operator), "ltimes" => ("⋉", Kind::Operator), "><|" => ("⋊", Kind::Operator), "rtimes" => ("⋊", Kind::Operator),
+	"|><|" => ("⋈", Kind::Operator), "bowtie" => ("⋈", Kind::Operator), "-:" => ("÷", Kind::Operator), "div" => ("÷", Kind::Operator),
+	"divide" => ("÷", Kind::Operator), "@" => ("∘", Kind::Operator), "circ" => ("∘", Kind::Operator),
+	"o+" => ("⊕", Kind::Operator), "oplus" => ("⊕", Kind::Operator), "ox" => ("⊗", Kind::Operator), "otimes" => ("⊗", Kind::Operator),
+	"o." => ("⊙", Kind::Operator), "odot" => ("⊙", Kind::Operator),
+	"sum" => ("∑", Kind::UnderOver), "prod" => ("∏", Kind::UnderOver),
+	"^^" => ("∧", Kind::Operator), "wedge" => ("∧", Kind::Operator), "^^^" => ("⋀", Kind::UnderOver), "bigwedge" => ("⋀", Kind::UnderOver),
+	"vv" => ("∨", Kind::Operator), "vee" => ("∨", Kind::Operator), "vvv" => ("⋁", Kind::UnderOver), "bigvee" => ("⋁", Kind::UnderOver),
+	"nn" => ("∩", Kind::Operator), "cap" => ("∩", Kind::Operator), "nnn" => ("⋂", Kind::UnderOver), "bigcap" => ("⋂", Kind::UnderOver),
+	"uu" => ("∪", Kind::Operator), "cup" => ("∪", Kind::Operator), "uuu" => ("⋃", Kind::UnderOver), "bigcup" => ("⋃", Kind::UnderOver),
+
+	// binary relation symbols
+	"!=" => ("≠", Kind::Operator), "ne" => ("≠", Kind::Operator), ":=" => (":=", Kind::Operator),
+	"lt" => ("<", Kind::Operator), "<=" => ("≤", Kind::Operator), "le" => ("≤", Kind::Operator),
+	"lt=" => ("≤", Kind::Operator), "leq" => ("≤", Kind::Operator), "gt" => (">", Kind::Operator), "mlt" => ("≪", Kind::Operator),
+	"ll" => ("≪", Kind::Operator), ">=" => ("≥", Kind::Operator), "ge" => ("≥", Kind::Operator), "gt=" => ("≥", Kind::Operator),
+	"geq" => ("≥", Kind::Operator), "mgt" => ("≫", Kind::Operator), "gg" => ("≫", Kind::Operator),
+	"-<" => ("≺", Kind::Operator), "prec" => ("≺", Kind::Operator), "-lt" => ("≺", Kind::Operator),
+	">-" => ("≻", Kind::Operator), "succ" => ("≻", Kind::Operator), "-<=" => ("⪯", Kind::Operator), "preceq" => ("⪯", Kind::Operator),
+	">-=" => ("⪰", Kind::Operator), "succeq" => ("⪰", Kind::Operator),
+	"in" => ("∈", Kind::Operator), "!in" => ("∉", Kind::Operator), "notin" => ("∉", Kind::Operator),
+	"sub" => ("⊂", Kind::Operator), "subset" => ("⊂", Kind::Operator), "sup" => ("⊃", Kind::Operator), "supset" => ("⊃", Kind::Operator),
+	"sube" => ("⊆", Kind::Operator), "subseteq" => ("⊆", Kind::Operator), "supe" => ("⊇", Kind::Operator), "supseteq" => ("⊇", Kind::Operator),
+	"-=" => ("≡", Kind::Operator), "equiv" => ("≡", Kind::Operator), "~=" => ("≅", Kind::Operator), "cong" => ("≅", Kind::Operator),
+	"~~" => ("≈", Kind::Operator), "approx" => ("≈", Kind::Operator), "~" => ("∼", Kind::Operator), "sim" => ("∼", Kind::Operator),
+	"prop" => ("∝", Kind::Operator), "propto" => ("∝", Kind::Operator),
+
+	// logical symbols
+	"and" => ("and", Kind::Space), "or" => ("or", Kind::Space), "not" => ("¬", Kind::Operator), "neg" => ("¬", Kind::Operator),
+	"=>" => ("⇒", Kind::Operator), "implies" => ("⇒", Kind::Operator), "if" => ("if", Kind::Space),
+	"<=>" => ("⇔", Kind::Operator), "iff" => ("⇔", Kind::Operator), "AA" => ("∀", Kind::Operator), "forall" => ("∀", Kind::Operator),
+	"EE" => ("∃", Kind::Operator), "exists" => ("∃", Kind::Operator), "_|_" => ("⊥", Kind::Operator), "bot" => ("⊥", Kind::Operator),
+	"TT" => ("⊤", Kind::Operator), "top" => ("⊤", Kind::Operator), "|--" => ("⊢", Kind::Operator), "vdash" => ("⊢", Kind::Operator),
+	"|==" => ("⊨", Kind::Operator), "models" => ("⊨", Kind::Operator),
+
+	// grouping brackets
+	"(" => ("(", Kind::LeftBracket), ")" => (")", Kind::RightBracket), "[" => ("[", Kind::LeftBracket), "]" => ("]", Kind::RightBracket),
+	"{" => ("{", Kind::LeftBracket), "}" => ("}", Kind::RightBracket),
+	"(:" => ("⟨", Kind::LeftBracket), ":)" => ("⟩", Kind::RightBracket), "<<" => ("⟨", Kind::LeftBracket), ">>" => ("⟩", Kind::RightBracket),
+	"langle" => ("⟨", Kind::LeftBracket), "rangle" => ("⟩", Kind::RightBracket),
+	"{:" => ("", Kind::LeftBracket), ":}" => ("", Kind::RightBracket),
+	"|:" => ("|", Kind::LeftBracket), ":|" => ("|", Kind::RightBracket), ":|:" => ("|", Kind::Operator),
+
+	// miscellaneous symbols
+	"int" => ("∫", Kind::Operator), "oint" => ("∮", Kind::Operator),
+	"del" => ("∂", Kind::Operator), "partial" => ("∂", Kind::Operator), "grad" => ("∇", Kind::Operator), "nabla" => ("∇", Kind::Operator),
+	"+-" => ("±", Kind::Operator), "pm" => ("±", Kind::Operator), "-+" => ("∓", Kind::Operator), "mp" => ("∓", Kind::Operator),
+	"O/" => ("∅", Kind::Identifier), "emptyset" => ("∅", Kind::Identifier), "oo" => ("∞", Kind::Identifier), "infty" => ("∞", Kind::Identifier),
+	"aleph" => ("ℵ", Kind::Identifier), "..." => ("…", Kind::Operator), "ldots" => ("…", Kind::Operator),
+	":." => ("∴", Kind::Operator), "therefore" => ("∴", Kind::Operator), ":'" => ("∵", Kind::Operator), "because" => ("∵", Kind::Operator),
+	"/_" => ("∠", Kind::Operator), "angle" => ("∠", Kind::Operator), "/_\\" => ("△", Kind::Operator), "triangle" => ("△", Kind::Operator),
+	"'" => ("′", Kind::Operator), "prime" => ("′", Kind::Operator),
+	"\\ " => ("\u{00A0}", Kind::Operator), "frown" => ("⌢", Kind::Operator),
+	"quad" => ("\u{00A0}\u{00A0}", Kind::Operator), "qquad" => ("\u{00A0}\u{00A0}\u{00A0}\u{00A0}", Kind::Operator),
+	"cdots" => ("⋯", Kind::Operator), "vdots" => ("⋮", Kind::Operator), "ddots" => ("⋱", Kind::Operator),
+	"diamond" => ("⋄", Kind::Operator), "square" => ("□", Kind::Operator),
+	"|__" => ("⌊", Kind::Operator), "lfloor" => ("⌊", Kind::Operator), "__|" => ("⌋", Kind::Operator), "rfloor" => ("⌋", Kind::Operator),
+	"|~" => ("⌈", Kind::Operator), "lceiling" => ("⌈", Kind::Operator), "~|" => ("⌉", Kind::Operator), "rceiling" => ("⌉", Kind::Operator),
+	"CC" => ("ℂ", Kind::Identifier), "NN" => ("ℕ", Kind::Identifier), "QQ" => ("ℚ", Kind::Identifier),
+	"RR" => ("ℝ", Kind::Identifier), "ZZ" => ("ℤ", Kind::Identifier),
+
+	// standard functions
+	"lim" => ("lim", Kind::UnderOver), "Lim" => ("Lim", Kind::UnderOver),
+	"sin" => ("sin", Kind::Function), "cos" => ("cos", Kind::Function), "tan" => ("tan", Kind::Function),
+	"sinh" => ("sinh", Kind::Function), "cosh" => ("cosh", Kind::Function), "tanh" => ("tanh", Kind::Function),
+	"cot" => ("cot", Kind::Function), "sec" => ("sec", Kind::Function), "csc" => ("csc", Kind::Function),
+	"arcsin" => ("arcsin", Kind::Function), "arccos" => ("arccos", Kind::Function), "arctan" => ("arctan", Kind::Function),
+	"coth" => ("coth", Kind::Function), "sech" => ("sech", Kind::Function), "csch" => ("csch", Kind::Function),
+	"exp" => ("exp", Kind::Function), "log" => ("log", Kind::Function), "ln" => ("ln", Kind::Function),
+	"det" => ("det", Kind::Function), "dim" => ("dim", Kind::Function), "mod" => ("mod", Kind::Function),
+	"gcd" => ("gcd", Kind::Function), "lcm" => ("lcm", Kind::Function), "lub" => ("lub", Kind::Function),
+	"glb" => ("glb", Kind::Function), "min" => ("min", Kind::UnderOver), "max" => ("max", Kind::UnderOver),
+	"Sin" => ("Sin", Kind::Function), "Cos" => ("Cos", Kind::Function), "Tan" => ("Tan", Kind::Function),
+	"Log" => ("Log", Kind::Function), "Ln" => ("Ln", Kind::Function),
+
+	// arrows
+	"uarr" => ("↑", Kind::Operator), "uparrow" => ("↑", Kind::Operator), "darr" => ("↓", Kind::Operator), "downarrow" => ("↓", Kind::Operator),
+	"rarr" => ("→", Kind::Operator), "rightarrow" => ("→", Kind::Operator), "->" => ("→", Kind::Operator), "to" => ("→", Kind::Operator),
+	">->" => ("↣", Kind::Operator), "rightarrowtail" => ("↣", Kind::Operator), "->>" => ("↠", Kind::Operator),
+	"twoheadrightarrow" => ("↠", Kind::Operator), ">->>" => ("⤖", Kind::Operator), "twoheadrightarrowtail" => ("⤖", Kind::Operator),
+	"|->" => ("↦", Kind::Operator), "mapsto" => ("↦", Kind::Operator), "larr" => ("←", Kind::Operator), "leftarrow" => ("←", Kind::Operator),
+	"harr" => ("↔", Kind::Operator), "leftrightarrow" => ("↔", Kind::Operator), "rArr" => ("⇒", Kind::Operator),
+	"Rightarrow" => ("⇒", Kind::Operator), "lArr" => ("⇐", Kind::Operator), "Leftarrow" => ("⇐", Kind::Operator),
+	"hArr" => ("⇔", Kind::Operator), "Leftrightarrow" => ("⇔", Kind::Operator),
+
+	// commands with arguments
+	"sqrt" => ("", Kind::Sqrt), "root" => ("", Kind::Root), "frac" => ("", Kind::Frac), "/" => ("/", Kind::Operator),
+	"stackrel" => ("", Kind::Over), "overset" => ("", Kind::Over), "underset" => ("", Kind::Under),
+	"_" => ("_", Kind::Operator), "^" => ("^", Kind::Operator),
+	"abs" => ("||", Kind::Fence), "floor" => ("⌊⌋", Kind::Fence), "ceil" => ("⌈⌉", Kind::Fence), "norm" => ("‖‖", Kind::Fence),
+	"hat" => ("^", Kind::Accent), "bar" => ("¯", Kind::Accent), "overline" => ("¯", Kind::Accent),
+	"vec" => ("→", Kind::Accent), "tilde" => ("~", Kind::Accent), "dot" => (".", Kind::Accent), "ddot" => ("..", Kind::Accent),
+	"overarc" => ("⏜", Kind::Accent), "overparen" => ("⏜", Kind::Accent), "obrace" => ("⏞", Kind::Accent), "overbrace" => ("⏞", Kind::Accent),
+	"ul" => ("̲", Kind::UnderAccent), "underline" => ("̲", Kind::UnderAccent), "ubrace" => ("⏟", Kind::UnderAccent),
+	"underbrace" => ("⏟", Kind::UnderAccent),
+	"cancel" => ("", Kind::Cancel), "color" => ("", Kind::Color),
+	"text" => ("", Kind::Text), "mbox" => ("", Kind::Text),
+	"bb" => ("bold", Kind::Font), "mathbf" => ("bold", Kind::Font), "sf" => ("sans-serif", Kind::Font), "mathsf" => ("sans-serif", Kind::Font),
+	"bbb" => ("double-struck", Kind::Font), "mathbb" => ("double-struck", Kind::Font), "cc" => ("script", Kind::Font),
+	"mathcal" => ("script", Kind::Font), "tt" => ("monospace", Kind::Font), "mathtt" => ("monospace", Kind::Font),
+	"fr" => ("fraktur", Kind::Font), "mathfrak" => ("fraktur", Kind::Font),
+};
+
+/// Length (in bytes) of the longest symbol -- used to limit the search for the longest match
+const MAX_SYMBOL_LEN: usize = 21;
+
+/// Convert 'asciimath' to a MathML string.
+pub fn asciimath_to_mathml(asciimath: &str) -> String {
+	let mut parser = ASCIIMathParser { rest: asciimath };
+	let items = parser.parse_expr(false);
+	return format!("<math>{}</math>", mrow(items.into_iter().map(|node| node.mathml).collect()));
+}
+
+/// A token from the input
+#[derive(Debug, Clone)]
+struct Token<'s> {
+	input: &'s str,
+	output: String,
+	kind: Kind,
+	is_number: bool,
+}
+
+/// A parsed piece of the output
+#[derive(Debug, Clone)]
+struct Node {
+	mathml: String,
+	inner: Option<String>,			// contents without the brackets (for bracketed groups)
+	cells: Option<Vec<String>>,		// the comma separated parts of a '(...)' or '[...]' group (a potential matrix row)
+	is_comma: bool,
+}
+
+impl Node {
+	fn new(mathml: String) -> Node {
+		return Node { mathml, inner: None, cells: None, is_comma: false };
+	}
+
+	/// The contents without the outer brackets (if it is bracketed)
+	fn without_brackets(self) -> String {
+		return self.inner.unwrap_or(self.mathml);
+	}
+}
+
+struct ASCIIMathParser<'s> {
+	rest: &'s str,
+}
+
+impl<'s> ASCIIMathParser<'s> {
+	/// Return the next token without consuming it
+	fn peek(&self) -> Option<Token<'s>> {
+		let rest = self.rest.trim_start();
+		let first = rest.chars().next()?;
+		if first == '"' {
+			let len = rest[1..].find('"').map_or(rest.len(), |i| i + 2);
+			let text = rest[1..len].trim_end_matches('"');
+			return Some( Token { input: &rest[..len], output: text.to_string(), kind: Kind::Text, is_number: false } );
+		}
+		if first.is_ascii_digit() || (first == '.' && rest[1..].starts_with(|ch: char| ch.is_ascii_digit())) {
+			let mut len = rest.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len());
+			if rest[len..].starts_with('.') && rest[len+1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+				len += 1 + rest[len+1..].find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len() - len - 1);
+			}
+			return Some( Token { input: &rest[..len], output: rest[..len].to_string(), kind: Kind::Identifier, is_number: true } );
+		}
+		// the longest match in the symbol table
+		let mut end = rest.len().min(MAX_SYMBOL_LEN);
+		while end > 0 {
+			if rest.is_char_boundary(end) && let Some(&(output, kind)) = SYMBOLS.get(&rest[..end]) {
+				return Some( Token { input: &rest[..end], output: output.to_string(), kind, is_number: false } );
+			}
+			end -= 1;
+		}
+		let len = first.len_utf8();
+		let kind = if first.is_alphabetic() {Kind::Identifier} else {Kind::Operator};
+		return Some( Token { input: &rest[..len], output: first.to_string(), kind, is_number: false } );
+	}
+
+	fn next(&mut self) -> Option<Token<'s>> {
+		let token = self.peek()?;
+		let rest = self.rest.trim_start();
+		self.rest = &rest[token.input.len()..];
+		return Some(token);
+	}
+
+	fn peek_is(&self, input: &str) -> bool {
+		return self.peek().is_some_and(|token| token.input == input);
+	}
+
+	/// E ::= IE | I/I -- parse until the end or a right bracket (which is not consumed)
+	fn parse_expr(&mut self, in_brackets: bool) -> Vec<Node> {
+		let mut nodes = vec![];
+		while let Some(token) = self.peek() {
+			if token.kind == Kind::RightBracket {
+				if in_brackets {
+					break;
+				}
+				// unmatched right bracket -- just an operator
+				self.next();
+				nodes.push( Node::new(format!("<mo>{}</mo>", escape(&token.output))) );
+				continue;
+			}
+			let node = self.parse_intermediate();
+			if self.peek_is("/") {
+				self.next();
+				let denominator = self.parse_intermediate();
+				nodes.push( Node::new(format!("<mfrac>{}{}</mfrac>", node.without_brackets(), denominator.without_brackets())) );
+			} else {
+				nodes.push(node);
+			}
+		}
+		return nodes;
+	}
+
+	/// I ::= S_S | S^S | S_S^S | S
+	fn parse_intermediate(&mut self) -> Node {
+		let (base, limits) = self.parse_simple();
+		let mut sub = None;
+		let mut sup = None;
+		if self.peek_is("_") {
+			self.next();
+			sub = Some(self.parse_script());
+		}
+		if self.peek_is("^") {
+			self.next();
+			sup = Some(self.parse_script());
+		}
+		let (both, only_sub, only_sup) = if limits {("munderover", "munder", "mover")} else {("msubsup", "msub", "msup")};
+		return match (sub, sup) {
+			(Some(sub), Some(sup)) => Node::new(format!("<{both}>{}{sub}{sup}</{both}>", base.mathml)),
+			(Some(sub), None) => Node::new(format!("<{only_sub}>{}{sub}</{only_sub}>", base.mathml)),
+			(None, Some(sup)) => Node::new(format!("<{only_sup}>{}{sup}</{only_sup}>", base.mathml)),
+			(None, None) => base,
+		};
+	}
+
+	/// S ::= v | l E r | u S | b S S
+	/// Returns the node and whether scripts on it are written as limits
+	fn parse_simple(&mut self) -> (Node, bool) {
+		let Some(token) = self.next() else {
+			return (Node::new("<mrow></mrow>".to_string()), false);
+		};
+		let output = escape(&token.output);
+		let node = match token.kind {
+			Kind::Identifier if token.is_number => Node::new(format!("<mn>{output}</mn>")),
+			Kind::Identifier => Node::new(format!("<mi>{output}</mi>")),
+			Kind::Operator => {
+				let mut node = Node::new(format!("<mo>{output}</mo>"));
+				node.is_comma = token.input == ",";
+				node
+			},
+			Kind::UnderOver => {
+				let element = if token.output.chars().count() > 1 {"mi"} else {"mo"};
+				return (Node::new(format!("<{element}>{output}</{element}>")), true);
+			},
+			Kind::Function => Node::new(format!("<mi>{output}</mi>")),
+			Kind::Space => Node::new(format!("<mrow><mspace width='1ex'></mspace><mtext>{output}</mtext><mspace width='1ex'></mspace></mrow>")),
+			Kind::Text => {
+				// "text" or text(...)
+				let text = if token.input.starts_with('"') {
+					token.output
+				} else {
+					self.read_raw_group()
+				};
+				Node::new(format!("<mtext>{}</mtext>", escape(&text)))
+			},
+			Kind::LeftBracket => self.parse_bracketed(&token),
+			Kind::RightBracket => Node::new(format!("<mo>{output}</mo>")),
+			Kind::Sqrt => Node::new(format!("<msqrt>{}</msqrt>", self.parse_argument())),
+			Kind::Fence => {
+				let mut fences = token.output.chars();
+				let (open, close) = (fences.next().unwrap(), fences.next().unwrap());
+				Node::new(format!("<mrow><mo>{open}</mo>{}<mo>{close}</mo></mrow>", self.parse_argument()))
+			},
+			Kind::Accent => Node::new(format!("<mover accent='true'>{}<mo>{output}</mo></mover>", self.parse_argument())),
+			Kind::UnderAccent => Node::new(format!("<munder accentunder='true'>{}<mo>{output}</mo></munder>", self.parse_argument())),
+			Kind::Font => {
+				let argument = self.parse_argument();
+				Node::new(add_mathvariant(&argument, &output))
+			},
+			Kind::Cancel => Node::new(format!("<menclose notation='updiagonalstrike'>{}</menclose>", self.parse_argument())),
+			Kind::Frac => {
+				let numerator = self.parse_argument();
+				let denominator = self.parse_argument();
+				Node::new(format!("<mfrac>{numerator}{denominator}</mfrac>"))
+			},
+			Kind::Root => {
+				let index = self.parse_argument();
+				let radicand = self.parse_argument();
+				Node::new(format!("<mroot>{radicand}{index}</mroot>"))
+			},
+			Kind::Over | Kind::Under => {
+				let script = self.parse_argument();
+				let base = self.parse_argument();
+				let element = if token.kind == Kind::Over {"mover"} else {"munder"};
+				Node::new(format!("<{element}>{base}{script}</{element}>"))
+			},
+			Kind::Color => {
+				self.read_raw_group();		// the color is ignored
+				Node::new(self.parse_argument())
+			},
+		};
+		return (node, false);
+	}
+
+	/// A sub/superscript (brackets are dropped). As in ASCIIMath, a '-' right after the '_' or '^' is part of the script.
+	fn parse_script(&mut self) -> String {
+		if self.peek_is("-") {
+			self.next();
+			return format!("<mrow><mo>−</mo>{}</mrow>", self.parse_simple().0.without_brackets());
+		}
+		return self.parse_simple().0.without_brackets();
+	}
+
+	/// An argument of a unary or binary symbol (brackets are dropped)
+	fn parse_argument(&mut self) -> String {
+		return self.parse_simple().0.without_brackets();
+	}
+
+	/// The text inside of the brackets that follow (e.g., for 'text(...)')
+	fn read_raw_group(&mut self) -> String {
+		let rest = self.rest.trim_start();
+		let Some(open) = rest.chars().next().filter(|ch| "([{".contains(*ch)) else {
+			return String::new();
+		};
+		let close = match open {'(' => ')', '[' => ']', _ => '}'};
+		let end = rest.find(close).unwrap_or(rest.len());
+		let text = rest[1..end].to_string();
+		self.rest = &rest[(end + 1).min(rest.len())..];
+		return text;
+	}
+
+	/// l E r -- also recognizes matrices such as `[(a,b),(c,d)]`
+	fn parse_bracketed(&mut self, open: &Token) -> Node {
+		let contents = self.parse_expr(true);
+		let close = self.next().map(|token| token.output).unwrap_or_default();
+		let open_mo = if open.output.is_empty() {String::new()} else {format!("<mo>{}</mo>", escape(&open.output))};
+		let close_mo = if close.is_empty() {String::new()} else {format!("<mo>{}</mo>", escape(&close))};
+
+		if let Some(table) = as_matrix(&contents) {
+			let mathml = format!("<mrow>{open_mo}{table}{close_mo}</mrow>");
+			return Node { mathml, inner: Some(table), cells: None, is_comma: false };
+		}
+
+		// split at the commas -- used by the parent (if any) for matrix detection
+		let mut cells = vec![vec![]];
+		for node in &contents {
+			if node.is_comma {
+				cells.push(vec![]);
+			} else {
+				cells.last_mut().unwrap().push(node.mathml.clone());
+			}
+		}
+		let cells: Vec<String> = cells.into_iter().map(mrow).collect();
+		let inner = mrow(contents.into_iter().map(|node| node.mathml).collect());
+		let mathml = format!("<mrow>{open_mo}{inner}{close_mo}</mrow>");
+		let is_matrix_row = matches!(open.input, "(" | "[") && cells.len() > 1;
+		return Node { mathml, inner: Some(inner), cells: if is_matrix_row {Some(cells)} else {None}, is_comma: false };
+	}
+}
+
+/// If 'contents' is a list of at least two bracketed rows with the same number of entries, return an mtable
+fn as_matrix(contents: &[Node]) -> Option<String> {
+	let mut rows = vec![];
+	for (i, node) in contents.iter().enumerate() {
+		if i % 2 == 1 {
+			if !node.is_comma {
+				return None;
+			}
+		} else {
+			rows.push(node.cells.clone()?);
+		}
+	}
+	if rows.len() < 2 || contents.len().is_multiple_of(2) || rows.iter().any(|row| row.len() != rows[0].len()) {
+		return None;
+	}
+	let rows: String = rows.iter()
+		.map(|row| format!("<mtr>{}</mtr>", row.iter().map(|cell| format!("<mtd>{cell}</mtd>")).collect::<String>()))
+		.collect();
+	return Some( format!("<mtable>{rows}</mtable>") );
+}
+
+/// Add mathvariant to all the mi's and mn's in 'mathml'
+fn add_mathvariant(mathml: &str, mathvariant: &str) -> String {
+	return mathml.replace("<mi>", &format!("<mi mathvariant='{mathvariant}'>"))
+				 .replace("<mn>", &format!("<mn mathvariant='{mathvariant}'>"));
+}
+
+/******** MathML to ASCIIMath ********/
+
+/// The ASCIIMath name of a character (the inverse of the symbol table).
+/// If a character has more than one name, a name without letters is preferred (e.g., "<=" over "le"), then the shortest one.
+static ASCIIMATH_NAMES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+	let mut names: HashMap<&str, &str> = HashMap::new();
+	for (&input, &(output, kind)) in SYMBOLS.entries() {
+		if output.is_ascii() || !matches!(kind, Kind::Identifier | Kind::Operator | Kind::UnderOver | Kind::LeftBracket | Kind::RightBracket) {
+			continue;
+		}
+		let rank = |name: &str| (name.chars().any(|ch| ch.is_ascii_alphabetic()), name.len(), name.to_string());
+		match names.get(output) {
+			Some(&old) if rank(old) <= rank(input) => (),
+			_ => {names.insert(output, input);},
+		}
+	}
+	names.insert("−", "-");
+	return names;
+});
+
+/// Relations are written with a space on each side
+static RELATIONS: phf::Set<&str> = phf_set! {
+	"=", "<", ">", "≤", "≥", "≠", "≈", "≡", "∼", "≅", "∝", "≪", "≫", "∈", "∉", "⊂", "⊃", "⊆", "⊇",
+	"→", "←", "↔", "⇒", "⇐", "⇔", "↦", ":=",
+};
+
+/// Convert (canonicalized) MathML into ASCIIMath.
+/// This is used to copy math as text, so the ASCIIMath is written the way a person would write it (e.g., `(x+1)/2`).
+pub fn mathml_to_asciimath(mathml: Element) -> Result<String> {
+	let mut writer = ASCIIMathWriter::default();
+	writer.write(mathml)?;
+	return Ok( writer.asciimath.trim().to_string() );
+}
+
+#[derive(Default)]
+struct ASCIIMathWriter {
+	asciimath: String,
+	is_script: bool,			// relations aren't spaced out in scripts (e.g., "sum_(i=1)^n")
+	after_script: bool,			// a script is separated from a number or letter that follows it (e.g., "x^2 y")
+}
+
+impl ASCIIMathWriter {
+	/// Add `text`, separating it from a word that it would otherwise run into (e.g., "sin x" and "alpha beta")
+	fn push(&mut self, text: &str) {
+		if text.starts_with([')', ']', '}', ',', ':']) && self.asciimath.ends_with(' ') {
+			self.asciimath.pop();
+		}
+		if std::mem::take(&mut self.after_script) && text.starts_with(|ch: char| ch.is_ascii_alphanumeric()) {
+			self.space();
+		}
+		let last_word = &self.asciimath[self.asciimath.trim_end_matches(|ch: char| ch.is_ascii_alphabetic()).len()..];
+		let first_word = text.split(|ch: char| !ch.is_ascii_alphabetic()).next().unwrap_or_default();
+		if !last_word.is_empty() && !first_word.is_empty() && (last_word.len() > 1 || first_word.len() > 1) {
+			self.asciimath.push(' ');
+		}
+		self.asciimath.push_str(text);
+	}
+
+	fn space(&mut self) {
+		if !self.asciimath.is_empty() && !self.asciimath.ends_with(' ') {
+			self.asciimath.push(' ');
+		}
+	}
+
+	/// The ASCIIMath for `mathml` (without adding it)
+	fn to_asciimath(mathml: Element, is_script: bool) -> Result<String> {
+		let mut writer = ASCIIMathWriter { is_script, ..Default::default() };
+		writer.write(mathml)?;
+		return Ok( writer.asciimath.trim().to_string() );
+	}
+
+	/// Write `mathml` as an argument -- parens are added unless it is a single number, letter, or symbol
+	fn write_arg(&mut self, mathml: Element) -> Result<()> {
+		let arg = ASCIIMathWriter::to_asciimath(mathml, self.is_script)?;
+		let is_single = arg.chars().count() == 1 || arg.chars().all(|ch| ch.is_ascii_digit() || ch == '.') ||
+						SYMBOLS.get(arg.as_str()).is_some_and(|(_, kind)| matches!(kind, Kind::Identifier));
+		if is_single {
+			self.push(&arg);
+		} else {
+			self.push(&format!("({arg})"));
+		}
+		return Ok( () );
+	}
+
+	fn write_script(&mut self, mathml: Element) -> Result<()> {
+		let is_script = std::mem::replace(&mut self.is_script, true);
+		let result = self.write_arg(mathml);
+		self.is_script = is_script;
+		self.after_script = true;
+		return result;
+	}
+
+	fn write_children(&mut self, mathml: Element) -> Result<()> {
+		let children = mathml.children();
+		for (i, &child) in children.iter().enumerate() {
+			let child = as_element(child);
+			if name(child) == "mo" && as_text(child) == "\u{2062}" &&
+			   children.get(i+1).is_some_and(|&next| crate::latex::is_differential(as_element(next))) {
+				self.space();		// "x dx"
+			}
+			self.write(child)?;
+		}
+		return Ok( () );
+	}
+
+	fn write(&mut self, mathml: Element) -> Result<()> {
+		let element_name = name(mathml);
+		match element_name {
+			"math" | "mrow" | "mstyle" | "mpadded" | "merror" | "mtd" => {
+				if element_name != "mrow" || !self.write_fenced_table(mathml)? {
+					self.write_children(mathml)?;
+				}
+			},
+			"semantics" => {
+				if let Some(&child) = mathml.children().first() {
+					self.write(as_element(child))?;
+				}
+			},
+			"mi" | "mn" => {
+				let text = as_text(mathml);
+				match ASCIIMATH_NAMES.get(text) {
+					Some(name) => self.push(name),
+					None => self.push(&crate::latex::plain_text(text)),
+				}
+			},
+			"mo" => {
+				let text = as_text(mathml);
+				if matches!(text, "\u{2061}" | "\u{2062}" | "\u{2063}" | "\u{2064}") {
+					return Ok( () );
+				}
+				let op = ASCIIMATH_NAMES.get(text).copied().unwrap_or(text);
+				if RELATIONS.contains(text) && !self.is_script {
+					self.space();
+					self.push(op);
+					self.space();
+				} else {
+					self.push(op);
+				}
+			},
+			"mtext" | "ms" => {
+				let text = as_text(mathml).replace('\u{00A0}', " ");
+				let text = if element_name == "ms" {format!("\"{text}\"")} else {text};
+				self.space();
+				// ASCIIMath text can't contain the quote char that delimits it
+				self.push(&if text.contains('"') {format!("text({text})")} else {format!("\"{text}\"")});
+				self.space();
+			},
+			"mspace" => self.space(),
+			"mfrac" => {
+				let children = mathml.children();
+				self.write_arg(as_element(children[0]))?;
+				self.push("/");
+				self.write_arg(as_element(children[1]))?;
+			},
+			"msqrt" => {
+				self.push("sqrt(");
+				self.write_children(mathml)?;
+				self.push(")");
+			},
+			"mroot" => {
+				let children = mathml.children();
+				self.push("root(");
+				self.write(as_element(children[1]))?;
+				self.push(")(");
+				self.write(as_element(children[0]))?;
+				self.push(")");
+			},
+			"msub" | "msup" | "msubsup" => {
+				let children = mathml.children();
+				self.write(as_element(children[0]))?;
+				let (sub, sup) = match element_name {
+					"msub" => (Some(children[1]), None),
+					"msup" => (None, Some(children[1])),
+					_ => (Some(children[1]), Some(children[2])),
+				};
+				if let Some(sub) = sub {
+					self.push("_");
+					self.write_script(as_element(sub))?;
+				}
+				if let Some(sup) = sup {
+					let sup = as_element(sup);
+					if name(sup) == "mo" && !as_text(sup).is_empty() && as_text(sup).chars().all(|ch| ch == '′') {
+						self.push(&"'".repeat(as_text(sup).chars().count()));
+					} else {
+						self.push("^");
+						self.write_script(sup)?;
+					}
+				}
+			},
+			"munder" | "mover" | "munderover" => self.write_under_over(mathml)?,
+			"mmultiscripts" => {
+				let children = mathml.children();
+				let mut scripts: Vec<Element> = children[1..].iter().map(|&child| as_element(child)).collect();
+				let prescripts = match scripts.iter().position(|&child| name(child) == "mprescripts") {
+					Some(i) => scripts.split_off(i)[1..].to_vec(),
+					None => vec![],
+				};
+				if !prescripts.is_empty() {
+					self.push("{::}");
+					self.write_script_pairs(&prescripts)?;
+				}
+				self.write(as_element(children[0]))?;
+				self.write_script_pairs(&scripts)?;
+			},
+			"none" | "mprescripts" | "mphantom" => (),
+			"mtable" => self.write_table(mathml, "{:", ":}")?,
+			"menclose" => {
+				let notation = mathml.attribute_value("notation").unwrap_or("longdiv");
+				let is_cancel = notation.split_whitespace().any(|notation| notation.ends_with("strike"));
+				if is_cancel {
+					self.push("cancel(");
+				}
+				self.write_children(mathml)?;
+				if is_cancel {
+					self.push(")");
+				}
+			},
+			"mglyph" => self.push(mathml.attribute_value("alt").unwrap_or_default()),
+			_ => bail!("ASCIIMath can't be generated for the MathML element '{}'", element_name),
+		};
+		return Ok( () );
+	}
+
+	fn write_script_pairs(&mut self, scripts: &[Element]) -> Result<()> {
+		for pair in scripts.chunks(2) {
+			if name(pair[0]) != "none" {
+				self.push("_");
+				self.write_script(pair[0])?;
+			}
+			if let Some(&sup) = pair.get(1) && name(sup) != "none" {
+				self.push("^");
+				self.write_script(sup)?;
+			}
+		}
+		return Ok( () );
+	}
+
+	fn write_under_over(&mut self, mathml: Element) -> Result<()> {
+		let element_name = name(mathml);
+		let children = mathml.children();
+		let base = as_element(children[0]);
+		let script = as_element(children[1]);
+		if children.len() == 2 && name(script) == "mo" {
+			let accent = match as_text(script) {
+				"^" | "ˆ" | "\u{0302}" => "hat", "¯" | "‾" | "\u{0304}" | "\u{0305}" => "bar",
+				"→" | "\u{20D7}" => "vec", "˙" | "\u{0307}" => "dot", "¨" | "\u{0308}" => "ddot",
+				"~" | "˜" | "\u{0303}" => "tilde", "⏞" => "obrace",
+				"_" | "\u{0332}" => "ul", "⏟" => "ubrace",
+				_ => "",
+			};
+			let is_under_accent = matches!(accent, "ul" | "ubrace");
+			if !accent.is_empty() && is_under_accent == (element_name == "munder") {
+				self.push(&format!("{accent}("));
+				self.write(base)?;
+				self.push(")");
+				return Ok( () );
+			}
+		}
+
+		let base_text = if is_leaf(base) {as_text(base)} else {""};
+		let has_limits = SYMBOLS.values().any(|&(output, kind)| kind == Kind::UnderOver && output == base_text);
+		if has_limits {
+			self.write(base)?;
+			let (under, over) = match element_name {
+				"munder" => (Some(script), None),
+				"mover" => (None, Some(script)),
+				_ => (Some(script), Some(as_element(children[2]))),
+			};
+			if let Some(under) = under {
+				self.push("_");
+				self.write_script(under)?;
+			}
+			if let Some(over) = over {
+				self.push("^");
+				self.write_script(over)?;
+			}
+			return Ok( () );
+		}
+
+		if element_name != "mover" {
+			self.push("underset(");
+			self.write(script)?;
+			self.push(")(");
+		}
+		if element_name != "munder" {
+			let over = as_element(children[children.len() - 1]);
+			self.push("overset(");
+			self.write(over)?;
+			self.push(")(");
+		}
+		self.write(base)?;
+		self.push(if element_name == "munderover" {"))"} else {")"});
+		return Ok( () );
+	}
+
+	/// If `mrow` is a table inside of fences (e.g., a matrix in parens), write it using the fences as the outer brackets.
+	/// Returns true if it was written.
+	fn write_fenced_table(&mut self, mrow: Element) -> Result<bool> {
+		let children = mrow.children();
+		if !(2..=3).contains(&children.len()) {
+			return Ok(false);
+		}
+		let open = as_element(children[0]);
+		let table = as_element(children[1]);
+		let close = if children.len() == 3 {as_element(children[2])} else {open};
+		if name(open) != "mo" || name(table) != "mtable" || name(close) != "mo" {
+			return Ok(false);
+		}
+		let (open, close) = match (as_text(open), if children.len() == 3 {as_text(close)} else {""}) {
+			("|", "|") => ("|:", ":|"),
+			("{", "") => ("{", ":}"),
+			(open @ ("(" | "[" | "{"), close @ (")" | "]" | "}")) => (open, close),
+			_ => return Ok(false),
+		};
+		self.write_table(table, open, close)?;
+		return Ok(true);
+	}
+
+	/// Write the table as rows of comma separated cells (e.g., `[(1,2),(3,4)]`)
+	fn write_table(&mut self, table: Element, open: &str, close: &str) -> Result<()> {
+		self.push(open);
+		for (i, &row) in table.children().iter().enumerate() {
+			if i > 0 {
+				self.push(",");
+			}
+			let row = as_element(row);
+			let cells = row.children();
+			let cells = if name(row) == "mlabeledtr" {&cells[1..]} else {&cells[..]};
+			self.push("(");
+			for (j, &cell) in cells.iter().enumerate() {
+				if j > 0 {
+					self.push(",");
+				}
+				self.write(as_element(cell))?;
+			}
+			self.push(")");
+		}
+		self.push(close);
+		return Ok( () );
+	}
+}
+
+/// Wrap the items in an mrow unless there is exactly one
+fn mrow(items: Vec<String>) -> String {
+	if items.len() == 1 {
+		return items.into_iter().next().unwrap();
+	}
+	return format!("<mrow>{}</mrow>", items.join(""));
+}
+
+fn escape(text: &str) -> String {
+	return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('\'', "&apos;").replace('"', "&quot;");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::interface::{get_element, trim_element, is_same_element};
+	use sxd_document::parser;
+	use anyhow::bail;
+
+	fn is_converted_to(asciimath: &str, target: &str) -> Result<()> {
+		let mathml = asciimath_to_mathml(asciimath);
+		let test_package = parser::parse(&mathml).expect("Failed to parse generated MathML");
+		let test_mathml = get_element(&test_package);
+		let target_package = parser::parse(target).expect("Failed to parse target input");
+		let target_mathml = get_element(&target_package);
+		trim_element(target_mathml, false);
+		if let Err(e) = is_same_element(test_mathml, target_mathml, &[]) {
+			bail!("{}\nResult:\n{}", e, mathml);
+		}
+		return Ok( () );
+	}
+
+	#[test]
+	fn sum_with_limits() -> Result<()> {
+		return is_converted_to("sum_(i=1)^n i^2",
+			"<math>
+				<mrow>
+					<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>
+					<msup><mi>i</mi><mn>2</mn></msup>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn fractions_drop_brackets() -> Result<()> {
+		return is_converted_to("(a+b)/c^2 = 10^-3",
+			"<math>
+				<mrow>
+					<mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><msup><mi>c</mi><mn>2</mn></msup></mfrac>
+					<mo>=</mo>
+					<msup><mn>10</mn><mrow><mo>−</mo><mn>3</mn></mrow></msup>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn unary_and_binary() -> Result<()> {
+		return is_converted_to(r#"root(3)(x) + abs(bbb R) "if" sin x"#,
+			"<math>
+				<mrow>
+					<mroot><mi>x</mi><mn>3</mn></mroot>
+					<mo>+</mo>
+					<mrow><mo>|</mo><mi mathvariant='double-struck'>R</mi><mo>|</mo></mrow>
+					<mtext>if</mtext>
+					<mi>sin</mi>
+					<mi>x</mi>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn matrix() -> Result<()> {
+		return is_converted_to("[(1,2),(x,y)] (a,b)",
+			"<math>
+				<mrow>
+					<mrow>
+						<mo>[</mo>
+						<mtable>
+							<mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+							<mtr><mtd><mi>x</mi></mtd><mtd><mi>y</mi></mtd></mtr>
+						</mtable>
+						<mo>]</mo>
+					</mrow>
+					<mrow><mo>(</mo><mrow><mi>a</mi><mo>,</mo><mi>b</mi></mrow><mo>)</mo></mrow>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn unbalanced() -> Result<()> {
+		return is_converted_to("(x) ) sqrt",
+			"<math>
+				<mrow>
+					<mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>
+					<mo>)</mo>
+					<msqrt><mrow></mrow></msqrt>
+				</mrow>
+			</math>");
+	}
+}
@@ -0,0 +1,706 @@
+#![allow(clippy::needless_return)]
+//! Back-translation of Nemeth and UEB technical braille into MathML.
+//!
+//! The braille is first broken into tokens (numbers, letters, operators, and the indicators that give the structure
+//! such as fraction and script indicators), then the tokens are parsed into MathML.
+//! The chars and operators come from the code's `unicode.yaml` file, so they stay in sync with the MathML -> braille direction.
+//! The structures that are understood are the common ones: fractions, superscripts and subscripts, roots, and grouping.
+//! Function names (e.g., "sin") are recognized, including UEB's contracted forms of them (e.g., ⠎⠔).
+//! The result is meant to be passed to `set_mathml` (which does the rest of the cleanup).
+
+use crate::errors::*;
+use crate::prefs::PreferenceManager;
+use crate::shim_filesystem::read_to_string_shim;
+use crate::definitions::SPEECH_DEFINITIONS;
+use crate::braille::contract_ueb_letters;
+use std::collections::HashMap;
+use std::path::Path;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// A braille char (dots 7 and 8 removed)
+type Cell = char;
+
+const NEMETH_DIGITS: [Cell; 10] = ['⠴', '⠂', '⠆', '⠒', '⠲', '⠢', '⠖', '⠶', '⠦', '⠔'];
+const UEB_DIGITS: [Cell; 10] = ['⠚', '⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊'];
+const LETTERS: [Cell; 26] = ['⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊', '⠚', '⠅', '⠇', '⠍',
+                             '⠝', '⠕', '⠏', '⠟', '⠗', '⠎', '⠞', '⠥', '⠧', '⠺', '⠭', '⠽', '⠵'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+enum Code {
+    Nemeth,
+    UEB,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Sub,
+    Sup,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String, bool),       // the digits and whether there was a numeric indicator
+    Identifier(String),
+    Operator(String),
+    Space,
+    FractionStart,
+    FractionLine,
+    FractionEnd,
+    NumericFraction(String, String),    // UEB's ⠼⠁⠌⠃
+    RootIndex,                  // Nemeth puts the index first
+    RootStart,
+    RootEnd,
+    GroupStart,
+    GroupEnd,
+    Level(Vec<Script>),         // Nemeth level indicators (an empty vector is the baseline)
+    Script(Script),             // UEB level indicators (they apply to the next item)
+}
+
+/// Convert Unicode 'braille' in the braille code 'code' ("Nemeth" or "UEB") to a MathML string.
+pub fn braille_to_mathml(braille: &str, code: &str) -> Result<String> {
+    let code = match code {
+        "Nemeth" => Code::Nemeth,
+        "UEB" => Code::UEB,
+        _ => bail!("Back-translation is only supported for Nemeth and UEB, not '{}'", code),
+    };
+    let symbols = read_symbols(code)?;
+    // dots 7 & 8 (e.g., highlighting) don't matter and line breaks are just space
+    let cells = braille.chars()
+        .filter(|ch| *ch != '\r')
+        .map(|ch| match ch {
+            '\n' | ' ' | '\u{00A0}' => Ok('⠀'),
+            '\u{2800}'..='\u{28FF}' => Ok(char::from_u32(0x2800 + ((ch as u32 - 0x2800) & 0x3F)).unwrap()),
+            _ => bail!("'{}' is not a braille char", ch),
+        })
+        .collect::<Result<Vec<Cell>>>()?;
+    let tokens = match code {
+        Code::Nemeth => nemeth_tokens(&cells, &symbols)?,
+        Code::UEB => ueb_tokens(&cells, &symbols)?,
+    };
+    let mut parser = Parser{ tokens, i: 0, code };
+    let children = parser.parse_row(&[], &|_| false)?;
+    if parser.i < parser.tokens.len() {
+        bail!("Unexpected {:?} in the braille", parser.tokens[parser.i]);
+    }
+    let mut mathml = "<math>".to_string();
+    if children.len() == 1 {
+        children[0].write(&mut mathml);
+    } else {
+        MathNode::Tree("mrow", children).write(&mut mathml);
+    }
+    mathml.push_str("</math>");
+    return Ok(mathml);
+}
+
+/// Read the braille code's unicode.yaml file and return a map from braille to the char it represents.
+/// When more than one char has the same braille, ASCII chars are preferred (e.g., '=' over 'κ' for Nemeth's ⠨⠅).
+/// Digits are not included because numbers depend upon the code's numeric indicator rules.
+fn read_symbols(code: Code) -> Result<HashMap<Vec<Cell>, char>> {
+    let rules_dir = PreferenceManager::get().borrow().get_rules_dir();
+    let code_name = if code == Code::Nemeth {"Nemeth"} else {"UEB"};
+    let file = rules_dir.join("Braille").join(code_name).join("unicode.yaml");
+    let contents = read_to_string_shim(Path::new(&file))?;
+    let docs = YamlLoader::load_from_str(&contents)
+        .with_context(|| format!("Error parsing {}", file.display()))?;
+    let mut symbols = HashMap::new();
+    for entry in docs.iter().filter_map(|doc| doc.as_vec()).flatten() {
+        let Some(hash) = entry.as_hash() else { continue };
+        for (key, value) in hash {
+            let mut key_chars = key.as_str().unwrap_or_default().chars();
+            let (Some(ch), None) = (key_chars.next(), key_chars.next()) else { continue };
+            if ch.is_ascii_digit() {
+                continue;
+            }
+            let Some(text) = find_braille(value) else { continue };
+            // letters keep the capital (C) and Greek (G) indicators; other chars just keep the braille
+            let braille = text.chars()
+                .filter_map(|braille_ch| match braille_ch {
+                    '\u{2800}'..='\u{28FF}' => Some(braille_ch),
+                    'C' if ch.is_alphabetic() => Some('⠠'),
+                    'G' if ch.is_alphabetic() => Some('⠨'),
+                    _ => None,
+                })
+                .collect::<Vec<Cell>>();
+            if braille.is_empty() {
+                continue;
+            }
+            let keep_old = symbols.get(&braille).is_some_and(|old: &char| old.is_ascii() || !ch.is_ascii());
+            if !keep_old {
+                symbols.insert(braille, ch);
+            }
+        }
+    }
+    return Ok(symbols);
+
+    /// Returns the first `t:` string (if there is a test, the 'else' case is used because that is the usual case)
+    fn find_braille(value: &Yaml) -> Option<&str> {
+        return match value {
+            Yaml::Array(values) => values.iter().find_map(find_braille),
+            Yaml::Hash(hash) => {
+                if let Some(text) = hash.get(&Yaml::String("t".to_string())) {
+                    text.as_str()
+                } else {
+                    ["test", "else", "else_test", "then"].iter()
+                        .find_map(|key| hash.get(&Yaml::String(key.to_string())).and_then(find_braille))
+                }
+            },
+            _ => None,
+        };
+    }
+}
+
+/// Returns the char and length of the longest entry in 'symbols' that matches the start of 'cells' (at least 'min_len' long)
+fn match_symbol(cells: &[Cell], symbols: &HashMap<Vec<Cell>, char>, min_len: usize) -> Option<(char, usize)> {
+    const MAX_SYMBOL_LEN: usize = 8;
+    return (min_len..=std::cmp::min(cells.len(), MAX_SYMBOL_LEN)).rev()
+        .find_map(|len| symbols.get(&cells[..len]).map(|&ch| (ch, len)));
+}
+
+fn symbol_token(ch: char) -> Token {
+    return if ch.is_alphabetic() {Token::Identifier(ch.to_string())} else {Token::Operator(ch.to_string())};
+}
+
+fn nemeth_tokens(cells: &[Cell], symbols: &HashMap<Vec<Cell>, char>) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut i = 0;
+    let mut fraction_depth = 0;
+    while i < cells.len() {
+        let cell = cells[i];
+        let next = cells.get(i + 1).copied().unwrap_or('⠀');
+        let at_start = matches!(tokens.last(), None | Some(Token::Space));
+        if cell == '⠀' {
+            if !at_start {
+                tokens.push(Token::Space);
+            }
+            i += 1;
+        } else if cell == '⠼' && fraction_depth > 0 {
+            tokens.push(Token::FractionEnd);
+            fraction_depth -= 1;
+            i += 1;
+        } else if NEMETH_DIGITS.contains(&cell) || (cell == '⠼' && (NEMETH_DIGITS.contains(&next) || next == '⠨')) ||
+                  (cell == '⠨' && NEMETH_DIGITS.contains(&next) && at_start) {
+            let has_indicator = cell == '⠼';
+            if has_indicator {
+                i += 1;
+            }
+            let mut number = String::new();
+            while i < cells.len() {
+                let following = cells.get(i + 1).copied().unwrap_or('⠀');
+                if let Some(digit) = NEMETH_DIGITS.iter().position(|&d| d == cells[i]) {
+                    number.push(char::from_digit(digit as u32, 10).unwrap());
+                } else if cells[i] == '⠨' && NEMETH_DIGITS.contains(&following) {
+                    number.push('.');
+                } else if cells[i] == '⠠' && NEMETH_DIGITS.contains(&following) && !number.is_empty() {
+                    number.push(',');
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            tokens.push(Token::Number(number, has_indicator));
+        } else if let Some((ch, len)) = match_symbol(&cells[i..], symbols, 2) {
+            tokens.push(symbol_token(ch));
+            i += len;
+        } else if cell == '⠰' && at_start && LETTERS.contains(&next) {
+            i += 1;     // English letter indicator
+        } else if matches!(cell, '⠘' | '⠰' | '⠐') {
+            let start = i;
+            let mut levels = vec![];
+            while i < cells.len() && matches!(cells[i], '⠘' | '⠰') {
+                levels.push(if cells[i] == '⠘' {Script::Sup} else {Script::Sub});
+                i += 1;
+            }
+            if levels.is_empty() {
+                i += 1;     // baseline indicator
+            }
+            if cells.get(i).is_none_or(|&following| following == '⠀') {
+                bail!("The level indicator '{}' at position {} is not followed by anything", cells[start..i].iter().collect::<String>(), start);
+            }
+            tokens.push(Token::Level(levels));
+        } else {
+            let token = match cell {
+                '⠹' => {fraction_depth += 1; Some(Token::FractionStart)},
+                '⠌' if fraction_depth > 0 => Some(Token::FractionLine),
+                '⠣' => Some(Token::RootIndex),
+                '⠜' => Some(Token::RootStart),
+                '⠻' => Some(Token::RootEnd),
+                _ => None,
+            };
+            if let Some(token) = token {
+                tokens.push(token);
+                i += 1;
+            } else if let Some((ch, len)) = match_symbol(&cells[i..], symbols, 1) {
+                tokens.push(symbol_token(ch));
+                i += len;
+            } else if cell == '⠠' {
+                i += 1;     // e.g., enlarged grouping symbols
+            } else {
+                bail!("Unknown Nemeth braille '{}' at position {}", cells[i..].iter().take(3).collect::<String>(), i);
+            }
+        }
+    }
+    return Ok(tokens);
+}
+
+fn ueb_tokens(cells: &[Cell], symbols: &HashMap<Vec<Cell>, char>) -> Result<Vec<Token>> {
+    let function_names = contracted_function_names();
+    let mut tokens = vec![];
+    let mut i = 0;
+    let mut capitalize_word = false;
+    while i < cells.len() {
+        let cell = cells[i];
+        let next = cells.get(i + 1).copied().unwrap_or('⠀');
+        if !LETTERS.contains(&cell) && cell != '⠠' {
+            capitalize_word = false;
+        }
+        let at_word_start = i == 0 || (cells[i - 1] != '⠰' &&
+                            matches!(tokens.last(), None | Some(Token::Space | Token::Operator(_) | Token::GroupStart |
+                                                                 Token::FractionStart | Token::FractionLine | Token::RootStart)));
+        if cell == '⠀' {
+            if !matches!(tokens.last(), None | Some(Token::Space)) {
+                tokens.push(Token::Space);
+            }
+            i += 1;
+        } else if let Some((braille, name)) = function_names.iter()
+                        .filter(|(braille, _)| at_word_start && cells[i..].starts_with(braille))
+                        .max_by_key(|(braille, _)| braille.len()) {
+            // a contracted function name (e.g., ⠎⠔ for "sin") -- its letters are joined back together by the parser
+            tokens.extend(name.chars().map(|letter| Token::Identifier(letter.to_string())));
+            i += braille.len();
+        } else if cell == '⠼' && (UEB_DIGITS.contains(&next) || next == '⠲') {
+            i += 1;
+            let (number, len) = ueb_number(&cells[i..]);
+            i += len;
+            if cells.get(i) == Some(&'⠌') && cells.get(i + 1).is_some_and(|ch| UEB_DIGITS.contains(ch)) {
+                let (denominator, len) = ueb_number(&cells[i + 1..]);
+                tokens.push(Token::NumericFraction(number, denominator));
+                i += 1 + len;
+            } else {
+                tokens.push(Token::Number(number, true));
+            }
+        } else if (cell == '⠰' || cell == '⠠') && next == '⠄' {
+            i += 2;     // grade 1 or capitals terminator
+        } else if cell == '⠠' && next == '⠠' {
+            // capitalized word (⠠⠠) or passage (⠠⠠⠠)
+            let start = i;
+            while i < cells.len() && i - start < 3 && cells[i] == '⠠' {
+                i += 1;
+            }
+            if !cells.get(i).is_some_and(|following| LETTERS.contains(following)) {
+                bail!("The capital indicator '{}' at position {} is not followed by a letter", cells[start..i].iter().collect::<String>(), start);
+            }
+            capitalize_word = true;
+        } else if cell == '⠨' && next == '⠌' {
+            tokens.push(Token::FractionLine);
+            i += 2;
+        } else if let Some((ch, len)) = match_symbol(&cells[i..], symbols, 2) {
+            tokens.push(symbol_token(ch));
+            i += len;
+        } else {
+            let token = match cell {
+                '⠰' if next == '⠀' => bail!("The grade 1 indicator at position {} is not followed by anything", i),
+                '⠰' => None,    // grade 1 indicator
+                '⠷' => Some(Token::FractionStart),
+                '⠾' => Some(Token::FractionEnd),
+                '⠩' => Some(Token::RootStart),
+                '⠬' => Some(Token::RootEnd),
+                '⠣' => Some(Token::GroupStart),
+                '⠜' => Some(Token::GroupEnd),
+                '⠔' => Some(Token::Script(Script::Sup)),
+                '⠢' => Some(Token::Script(Script::Sub)),
+                _ => match match_symbol(&cells[i..], symbols, 1) {
+                    Some((ch, _)) if capitalize_word => Some(symbol_token(ch.to_uppercase().next().unwrap_or(ch))),
+                    Some((ch, _)) => Some(symbol_token(ch)),
+                    None => bail!("Unknown UEB braille '{}' at position {}", cells[i..].iter().take(3).collect::<String>(), i),
+                },
+            };
+            if let Some(token) = token {
+                tokens.push(token);
+            }
+            i += 1;
+        }
+    }
+    return Ok(tokens);
+
+    /// Returns the function names that UEB contracts (e.g., "sin" is ⠎⠔) along with their contracted braille
+    fn contracted_function_names() -> Vec<(Vec<Cell>, String)> {
+        let names = SPEECH_DEFINITIONS.with(|defs| defs.borrow().get_hashset("FunctionNames").cloned().unwrap_or_default());
+        return names.into_iter()
+            .filter_map(|name| {
+                let letters = name.chars()
+                    .map(|ch| if ch.is_ascii_lowercase() {Some(LETTERS[(ch as u8 - b'a') as usize])} else {None})
+                    .collect::<Option<Vec<Cell>>>()?;
+                let braille = contract_ueb_letters(&letters).chars().collect::<Vec<Cell>>();
+                return if braille == letters {None} else {Some((braille, name))};
+            })
+            .collect();
+    }
+
+    /// Returns the number (in print) that starts at 'cells' (after the numeric indicator) and the number of cells used
+    fn ueb_number(cells: &[Cell]) -> (String, usize) {
+        let mut number = String::new();
+        let mut i = 0;
+        while i < cells.len() {
+            let following = cells.get(i + 1).copied().unwrap_or('⠀');
+            if let Some(digit) = UEB_DIGITS.iter().position(|&d| d == cells[i]) {
+                number.push(char::from_digit(digit as u32, 10).unwrap());
+            } else if cells[i] == '⠲' && UEB_DIGITS.contains(&following) {
+                number.push('.');
+            } else if cells[i] == '⠂' && UEB_DIGITS.contains(&following) && !number.is_empty() {
+                number.push(',');
+            } else if !(cells[i] == '⠐' && UEB_DIGITS.contains(&following) && !number.is_empty()) {   // numeric space
+                break;
+            }
+            i += 1;
+        }
+        return (number, i);
+    }
+}
+
+/// A simple tree that is turned into a MathML string
+#[derive(Debug, Clone)]
+enum MathNode {
+    Leaf(&'static str, String),
+    Tree(&'static str, Vec<MathNode>),
+}
+
+impl MathNode {
+    fn mrow(mut children: Vec<MathNode>) -> MathNode {
+        return if children.len() == 1 {children.pop().unwrap()} else {MathNode::Tree("mrow", children)};
+    }
+
+    fn write(&self, result: &mut String) {
+        match self {
+            MathNode::Leaf(name, text) => {
+                let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                result.push_str(&format!("<{name}>{text}</{name}>"));
+            },
+            MathNode::Tree(name, children) => {
+                result.push_str(&format!("<{name}>"));
+                for child in children {
+                    child.write(result);
+                }
+                result.push_str(&format!("</{name}>"));
+            },
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    i: usize,
+    code: Code,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        return self.tokens.get(self.i);
+    }
+
+    fn expect(&mut self, token: Token) -> Result<()> {
+        match self.peek() {
+            Some(t) if *t == token => {
+                self.i += 1;
+                return Ok(());
+            },
+            Some(t) => bail!("Expected {:?} but found {:?} in the braille", token, t),
+            None => bail!("Missing {:?} at the end of the braille", token),
+        }
+    }
+
+    /// Parse the items at the (Nemeth) script 'level' until a token that satisfies 'stop' (not consumed) or the end
+    fn parse_row(&mut self, level: &[Script], stop: &dyn Fn(&Token) -> bool) -> Result<Vec<MathNode>> {
+        let mut children: Vec<MathNode> = vec![];
+        while let Some(token) = self.peek() {
+            if stop(token) {
+                break;
+            }
+            match token.clone() {
+                Token::Space => {
+                    if !level.is_empty() {
+                        break;      // a space returns to the baseline
+                    }
+                    self.i += 1;
+                },
+                Token::Level(levels) => {
+                    if levels.len() == level.len() + 1 && levels.starts_with(level) {
+                        self.i += 1;
+                        let base = children.pop().unwrap_or(MathNode::Tree("mrow", vec![]));
+                        let script = MathNode::mrow(self.parse_row(&levels, stop)?);
+                        let mut other_levels = levels.clone();
+                        *other_levels.last_mut().unwrap() = if levels[level.len()] == Script::Sub {Script::Sup} else {Script::Sub};
+                        if self.peek() == Some(&Token::Level(other_levels.clone())) {
+                            self.i += 1;
+                            let other_script = MathNode::mrow(self.parse_row(&other_levels, stop)?);
+                            children.push(scripted(base, levels[level.len()], script, Some(other_script)));
+                        } else {
+                            children.push(scripted(base, levels[level.len()], script, None));
+                        }
+                    } else if levels == level {
+                        self.i += 1;
+                    } else {
+                        break;      // back to an enclosing level
+                    }
+                },
+                Token::Script(script) => {
+                    self.i += 1;
+                    let base = children.pop().unwrap_or(MathNode::Tree("mrow", vec![]));
+                    let script_node = self.parse_item()?;
+                    let other = if script == Script::Sub {Script::Sup} else {Script::Sub};
+                    if self.peek() == Some(&Token::Script(other)) {
+                        self.i += 1;
+                        let other_node = self.parse_item()?;
+                        children.push(scripted(base, script, script_node, Some(other_node)));
+                    } else {
+                        children.push(scripted(base, script, script_node, None));
+                    }
+                },
+                Token::Number(number, false) if self.code == Code::Nemeth &&
+                                                 self.i > 0 && matches!(self.tokens[self.i - 1], Token::Identifier(_)) => {
+                    // Nemeth writes numeric subscripts (e.g., x₂) without a subscript indicator
+                    self.i += 1;
+                    let base = children.pop().unwrap();
+                    children.push(MathNode::Tree("msub", vec![base, MathNode::Leaf("mn", number)]));
+                },
+                Token::Identifier(_) => {
+                    let node = self.parse_identifiers();
+                    children.push(node);
+                },
+                Token::Operator(op) => {
+                    self.i += 1;
+                    children.push(MathNode::Leaf("mo", op));
+                },
+                _ => {
+                    let item = self.parse_item()?;
+                    children.push(item);
+                },
+            }
+        }
+        add_function_application(&mut children);
+        return Ok(children);
+
+        /// Add an invisible function application after a function name (e.g., "sin") that is followed by its argument
+        fn add_function_application(children: &mut Vec<MathNode>) {
+            let mut i = 0;
+            while i + 1 < children.len() {
+                let is_function = match &children[i] {
+                    MathNode::Tree("msub" | "msup" | "msubsup", scripted) => matches!(&scripted[0], MathNode::Leaf("mi", name) if is_function_name(name)),
+                    MathNode::Leaf("mi", name) => is_function_name(name),
+                    _ => false,
+                };
+                let is_argument = match &children[i + 1] {
+                    MathNode::Leaf("mo", op) => matches!(op.as_str(), "(" | "[" | "{"),
+                    _ => true,
+                };
+                if is_function && is_argument {
+                    children.insert(i + 1, MathNode::Leaf("mo", "\u{2061}".to_string()));
+                    i += 1;
+                }
+                i += 1;
+            }
+        }
+
+        fn scripted(base: MathNode, script: Script, script_node: MathNode, other_node: Option<MathNode>) -> MathNode {
+            return match (script, other_node) {
+                (Script::Sub, None) => MathNode::Tree("msub", vec![base, script_node]),
+                (Script::Sup, None) => MathNode::Tree("msup", vec![base, script_node]),
+                (Script::Sub, Some(sup)) => MathNode::Tree("msubsup", vec![base, script_node, sup]),
+                (Script::Sup, Some(sub)) => MathNode::Tree("msubsup", vec![base, sub, script_node]),
+            };
+        }
+    }
+
+    /// Letters are separate identifiers unless they spell a function name such as "sin"
+    fn parse_identifiers(&mut self) -> MathNode {
+        let mut letters = String::new();
+        let mut i = self.i;
+        while let Some(Token::Identifier(letter)) = self.tokens.get(i) {
+            letters.push_str(letter);
+            i += 1;
+        }
+        let n_letters = letters.chars().count();
+        for len in (2..=n_letters).rev() {
+            let word = letters.chars().take(len).collect::<String>();
+            if is_function_name(&word) {
+                self.i += len;
+                return MathNode::Leaf("mi", word);
+            }
+        }
+        self.i += 1;
+        return MathNode::Leaf("mi", letters.chars().next().unwrap().to_string());
+    }
+
+    /// Parse a single item (e.g., a number or a fraction)
+    fn parse_item(&mut self) -> Result<MathNode> {
+        let Some(token) = self.peek().cloned() else {
+            bail!("The braille ended unexpectedly");
+        };
+        self.i += 1;
+        return match token {
+            Token::Number(number, _) => Ok(MathNode::Leaf("mn", number)),
+            Token::Identifier(_) => {
+                self.i -= 1;
+                Ok(self.parse_identifiers())
+            },
+            Token::Operator(op) => {
+                // a sign in a UEB script (e.g., x⁻¹) goes with the item that follows it
+                if self.code == Code::UEB && (op == "-" || op == "+" || op == "−") &&
+                   matches!(self.peek(), Some(Token::Number(..) | Token::Identifier(_))) {
+                    let item = self.parse_item()?;
+                    Ok(MathNode::Tree("mrow", vec![MathNode::Leaf("mo", op), item]))
+                } else {
+                    Ok(MathNode::Leaf("mo", op))
+                }
+            },
+            Token::NumericFraction(numerator, denominator) => {
+                Ok(MathNode::Tree("mfrac", vec![MathNode::Leaf("mn", numerator), MathNode::Leaf("mn", denominator)]))
+            },
+            Token::FractionStart => {
+                let numerator = self.parse_row(&[], &|t| *t == Token::FractionLine)?;
+                self.expect(Token::FractionLine)?;
+                let denominator = self.parse_row(&[], &|t| *t == Token::FractionEnd)?;
+                self.expect(Token::FractionEnd)?;
+                Ok(MathNode::Tree("mfrac", vec![MathNode::mrow(numerator), MathNode::mrow(denominator)]))
+            },
+            Token::RootIndex => {
+                let index = self.parse_row(&[], &|t| *t == Token::RootStart)?;
+                self.expect(Token::RootStart)?;
+                let radicand = self.parse_row(&[], &|t| *t == Token::RootEnd)?;
+                self.expect(Token::RootEnd)?;
+                Ok(MathNode::Tree("mroot", vec![MathNode::mrow(radicand), MathNode::mrow(index)]))
+            },
+            Token::RootStart => {
+                let index = if self.peek() == Some(&Token::Script(Script::Sup)) {
+                    self.i += 1;
+                    Some(self.parse_item()?)
+                } else {
+                    None
+                };
+                let radicand = self.parse_row(&[], &|t| *t == Token::RootEnd)?;
+                self.expect(Token::RootEnd)?;
+                Ok(match index {
+                    None => MathNode::Tree("msqrt", radicand),
+                    Some(index) => MathNode::Tree("mroot", vec![MathNode::mrow(radicand), index]),
+                })
+            },
+            Token::GroupStart => {
+                let children = self.parse_row(&[], &|t| *t == Token::GroupEnd)?;
+                self.expect(Token::GroupEnd)?;
+                Ok(MathNode::Tree("mrow", children))
+            },
+            _ => bail!("Unexpected {:?} in the braille", token),
+        };
+    }
+}
+
+fn is_function_name(word: &str) -> bool {
+    return word.chars().count() > 1 &&
+           SPEECH_DEFINITIONS.with(|defs| defs.borrow().get_hashset("FunctionNames").is_some_and(|names| names.contains(word)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::{set_rules_dir, set_preference, set_mathml, get_braille};
+
+    fn init(code: &str) {
+        set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_preference("Language", "en").unwrap();
+        set_preference("BrailleCode", code).unwrap();
+        set_preference("UseSpacesAroundAllOperators", "false").unwrap();
+        crate::speech::SPEECH_RULES.with(|rules| rules.borrow_mut().read_files()).unwrap();
+    }
+
+    #[test]
+    fn nemeth() -> Result<()> {
+        init("Nemeth");
+        assert_eq!(braille_to_mathml("⠭⠘⠆⠐⠬⠹⠂⠌⠆⠼⠀⠨⠅⠀⠼⠂⠨⠢", "Nemeth")?,
+                   "<math><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>=</mo><mn>1.5</mn></mrow></math>");
+        assert_eq!(braille_to_mathml("⠭⠆⠘⠝⠀⠨⠅⠀⠣⠒⠜⠽⠻", "Nemeth")?,
+                   "<math><mrow><msup><msub><mi>x</mi><mn>2</mn></msub><mi>n</mi></msup><mo>=</mo><mroot><mi>y</mi><mn>3</mn></mroot></mrow></math>");
+        assert_eq!(braille_to_mathml("⠎⠊⠝⠀⠭", "Nemeth")?, "<math><mrow><mi>sin</mi><mo>\u{2061}</mo><mi>x</mi></mrow></math>");
+        return Ok(());
+    }
+
+    #[test]
+    fn nemeth_function_names() -> Result<()> {
+        init("Nemeth");
+        assert_eq!(braille_to_mathml("⠉⠕⠎⠀⠨⠹", "Nemeth")?, "<math><mrow><mi>cos</mi><mo>\u{2061}</mo><mi>θ</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠎⠊⠝⠀⠷⠆⠭⠾", "Nemeth")?,
+                   "<math><mrow><mi>sin</mi><mo>\u{2061}</mo><mo>(</mo><mn>2</mn><mi>x</mi><mo>)</mo></mrow></math>");
+        assert_eq!(braille_to_mathml("⠎⠊⠝⠘⠆⠀⠭", "Nemeth")?,
+                   "<math><mrow><msup><mi>sin</mi><mn>2</mn></msup><mo>\u{2061}</mo><mi>x</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠁⠗⠉⠎⠊⠝⠀⠭⠬⠇⠕⠛⠀⠽", "Nemeth")?,
+                   "<math><mrow><mi>arcsin</mi><mo>\u{2061}</mo><mi>x</mi><mo>+</mo><mi>log</mi><mo>\u{2061}</mo><mi>y</mi></mrow></math>");
+        return Ok(());
+    }
+
+    #[test]
+    fn nemeth_errors() {
+        init("Nemeth");
+        assert!(braille_to_mathml("⠭⠘", "Nemeth").is_err());         // superscript indicator with nothing after it
+        assert!(braille_to_mathml("⠭⠘⠀⠨⠅⠀⠽", "Nemeth").is_err());    // ... or a space after it
+        assert!(braille_to_mathml("⠭⠘⠆⠐", "Nemeth").is_err());       // dangling baseline indicator
+        assert!(braille_to_mathml("⠹⠂⠌⠆", "Nemeth").is_err());       // missing the end of the fraction
+    }
+
+    #[test]
+    fn ueb() -> Result<()> {
+        init("UEB");
+        assert_eq!(braille_to_mathml("⠭⠔⠼⠃⠐⠖⠼⠁⠌⠃⠀⠐⠶⠀⠼⠁⠲⠑", "UEB")?,
+                   "<math><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>=</mo><mn>1.5</mn></mrow></math>");
+        assert_eq!(braille_to_mathml("⠷⠁⠐⠖⠃⠨⠌⠉⠾⠀⠐⠶⠀⠩⠽⠔⠣⠝⠐⠤⠼⠁⠜⠬", "UEB")?,
+                   "<math><mrow><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac><mo>=</mo>\
+                    <msqrt><msup><mi>y</mi><mrow><mi>n</mi><mo>−</mo><mn>1</mn></mrow></msup></msqrt></mrow></math>");
+        return Ok(());
+    }
+
+    #[test]
+    fn ueb_function_names() -> Result<()> {
+        init("UEB");
+        assert_eq!(braille_to_mathml("⠎⠔⠀⠰⠭", "UEB")?, "<math><mrow><mi>sin</mi><mo>\u{2061}</mo><mi>x</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠉⠕⠎⠨⠹", "UEB")?, "<math><mrow><mi>cos</mi><mo>\u{2061}</mo><mi>θ</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠎⠔⠐⠣⠼⠃⠭⠐⠜", "UEB")?,
+                   "<math><mrow><mi>sin</mi><mo>\u{2061}</mo><mo>(</mo><mn>2</mn><mi>x</mi><mo>)</mo></mrow></math>");
+        assert_eq!(braille_to_mathml("⠎⠔⠰⠔⠼⠃⠭", "UEB")?,
+                   "<math><mrow><msup><mi>sin</mi><mn>2</mn></msup><mo>\u{2061}</mo><mi>x</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠜⠉⠎⠔⠀⠰⠭⠐⠖⠎⠔⠓⠀⠰⠽", "UEB")?,
+                   "<math><mrow><mi>arcsin</mi><mo>\u{2061}</mo><mi>x</mi><mo>+</mo><mi>sinh</mi><mo>\u{2061}</mo><mi>y</mi></mrow></math>");
+        assert_eq!(braille_to_mathml("⠇⠕⠛⠀⠰⠭", "UEB")?, "<math><mrow><mi>log</mi><mo>\u{2061}</mo><mi>x</mi></mrow></math>");
+        // ⠔ after a letter is still a superscript when it isn't part of a function name
+        assert_eq!(braille_to_mathml("⠭⠔⠝", "UEB")?, "<math><msup><mi>x</mi><mi>n</mi></msup></math>");
+        return Ok(());
+    }
+
+    #[test]
+    fn ueb_errors() {
+        init("UEB");
+        assert!(braille_to_mathml("⠠⠠⠠⠠", "UEB").is_err());          // capital indicators without letters
+        assert!(braille_to_mathml("⠠⠠⠀⠭", "UEB").is_err());
+        assert!(braille_to_mathml("⠭⠐⠖⠰", "UEB").is_err());          // dangling grade 1 indicator
+        assert!(braille_to_mathml("⠭⠔", "UEB").is_err());            // superscript indicator with nothing after it
+        assert!(braille_to_mathml("⠷⠁⠨⠌⠃", "UEB").is_err());         // missing the end of the fraction
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let exprs = [
+            "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mn>2</mn></mfrac><mo>=</mo><msqrt><msub><mi>x</mi><mi>i</mi></msub></msqrt></math>",
+            "<math><msup><mi>e</mi><mrow><mo>-</mo><msup><mi>x</mi><mn>2</mn></msup></mrow></msup><mo>&lt;</mo><mn>3.14</mn></math>",
+            "<math><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mn>2</mn><mi>x</mi><mo>-</mo><mn>7</mn></math>",
+            "<math><mi>sin</mi><mo>&#x2061;</mo><mi>x</mi><mo>+</mo><mi>cos</mi><mo>&#x2061;</mo><mi>θ</mi></math>",
+            "<math><msup><mi>sin</mi><mn>2</mn></msup><mo>&#x2061;</mo><mi>x</mi><mo>=</mo><mi>arcsin</mi><mo>&#x2061;</mo><mi>y</mi></math>",
+        ];
+        for code in ["Nemeth", "UEB"] {
+            init(code);
+            for expr in exprs {
+                set_mathml(expr)?;
+                let braille = get_braille("")?;
+                set_mathml(braille_to_mathml(&braille, code)?)?;
+                assert_eq!(get_braille("")?, braille, "{code} round trip of {expr}");
+            }
+        }
+        return Ok(());
+    }
+}
@@ -29,8 +29,31 @@ fn braille_at(braille: &str, index: usize) -> char {
 
 /// braille the MathML
 /// If 'nav_node_id' is not an empty string, then the element with that id will have dots 7 & 8 turned on as per the pref
+/// If the `BrailleLineLength` pref is not 0, the braille is broken into lines (separated by '\n')
 /// Returns the braille string (highlighted) along with the *character* start/end of the highlight (whole string if no highlight)
 pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<(String, usize, usize)> {
+    let (braille, start, end) = braille_mathml_line(mathml, nav_node_id)?;
+    let line_length = PreferenceManager::get().borrow().pref_to_string("BrailleLineLength").parse::<f64>().unwrap_or(0.0).max(0.0) as usize;
+    let (braille, start, end) = if line_length == 0 {
+        (braille, start, end)
+    } else {
+        let n_chars = braille.chars().count();
+        let (braille, new_position) = break_into_lines(mathml, braille, line_length)?;
+        if start == 0 && end == n_chars {
+            let end = new_position[n_chars];
+            (braille, 0, end)     // nothing was highlighted
+        } else {
+            let new_end = if end > start {new_position[end - 1] + 1} else {new_position[end]};
+            (braille, new_position[start], std::cmp::min(new_end, new_position[n_chars]))
+        }
+    };
+    // LINE_SEPARATOR is used until now because, like braille chars, it is 3 bytes long
+    return Ok( (braille.replace(LINE_SEPARATOR, "\n"), start, end) );
+}
+
+/// braille the MathML without breaking it into lines (elementary math is laid out on multiple lines separated by LINE_SEPARATOR)
+/// See braille_mathml() for the meaning of the args and the return value
+fn braille_mathml_line(mathml: Element, nav_node_id: &str) -> Result<(String, usize, usize)> {
     return BRAILLE_RULES.with(|rules| {
         rules.borrow_mut().read_files()?;
         let rules = rules.borrow();
@@ -50,11 +73,13 @@ pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<(String, usi
             "CMU" => cmu_cleanup(pref_manager, braille_string), 
             "Finnish" => finnish_cleanup(pref_manager, braille_string),
             "Swedish" => swedish_cleanup(pref_manager, braille_string),
+            "Marburg" => marburg_cleanup(pref_manager, braille_string),
             "LaTeX" => LaTeX_cleanup(pref_manager, braille_string),
             "ASCIIMath" => ASCIIMath_cleanup(pref_manager, braille_string),
             "ASCIIMath-fi" => ASCIIMath_cleanup(pref_manager, braille_string),
             _ => braille_string.trim_matches('⠀').to_string(),    // probably needs cleanup if someone has another code, but this will have to get added by hand
         };
+        let braille = layout_elementary_math(braille);
 
         return Ok(
             if highlight_style != "Off" {
@@ -248,6 +273,283 @@ pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<(String, usi
     }
 }
 
+// Markers output by the braille rules for elementary math (mstack and mlongdiv) -- see layout_elementary_math()
+const ELEMENTARY_ROW: char = '\u{F8E0}';
+const ELEMENTARY_CELL: char = '\u{F8E1}';
+const ELEMENTARY_END: char = '\u{F8E2}';
+const ELEMENTARY_FILL: char = '\u{F8E3}';
+const ELEMENTARY_OVERHANG: char = '\u{F8E4}';
+/// Separates the lines of braille (turned into '\n' after highlighting)
+const LINE_SEPARATOR: char = '\u{2028}';
+
+/// Lay out elementary math (mstack and mlongdiv) spatially: each row goes on its own line and the columns are right aligned.
+/// The braille rules mark the start of the rows (ELEMENTARY_ROW) and cells (ELEMENTARY_CELL) along with the end of the layout (ELEMENTARY_END).
+/// Anything in a row before the first cell (e.g., the divisor of a long division) is right aligned to the left of the first column.
+/// A cell that starts with ELEMENTARY_FILL is filled with the char that follows it (used for lines).
+/// The part of a cell before ELEMENTARY_OVERHANG (e.g., a numeric indicator) hangs into the column(s) to the left.
+/// Braille before and after the layout is put on its own line.
+fn layout_elementary_math(braille: String) -> String {
+    if !braille.contains(ELEMENTARY_ROW) {
+        return braille;
+    }
+
+    let mut result = String::with_capacity(2 * braille.len());
+    let mut rest = braille.as_str();
+    while let Some(i_start) = rest.find(ELEMENTARY_ROW) {
+        let before = rest[..i_start].trim_end_matches('⠀');
+        if !before.is_empty() {
+            result.push_str(before);
+            result.push(LINE_SEPARATOR);
+        }
+        let i_end = rest[i_start..].find(ELEMENTARY_END).map_or(rest.len(), |i| i_start + i);
+        layout_grid(&rest[i_start..i_end], &mut result);
+        rest = rest[i_end..].trim_start_matches(ELEMENTARY_END).trim_start_matches('⠀');
+        if !rest.is_empty() {
+            result.push(LINE_SEPARATOR);
+        }
+    }
+    result.push_str(rest);
+    return result;
+
+    fn layout_grid(grid: &str, result: &mut String) {
+        // split into rows and the rows into (prefix, cells)
+        let rows = grid.split(ELEMENTARY_ROW)
+                    .skip(1)        // the grid starts with ELEMENTARY_ROW
+                    .map(|row| {
+                        let mut cells = row.split(ELEMENTARY_CELL);
+                        let prefix = cells.next().unwrap_or_default();
+                        (prefix, cells.collect::<Vec<&str>>())
+                    })
+                    .collect::<Vec<(&str, Vec<&str>)>>();
+        let prefix_width = rows.iter().map(|(prefix, _)| prefix.chars().count()).max().unwrap_or(0);
+        let n_columns = rows.iter().map(|(_, cells)| cells.len()).max().unwrap_or(0);
+        // the rows are right aligned, so the widths are indexed from the right
+        let mut widths = vec![1; n_columns];
+        for (_, cells) in &rows {
+            for (width, cell) in widths.iter_mut().zip(cells.iter().rev()) {
+                if !cell.starts_with(ELEMENTARY_FILL) {
+                    let cell = cell.split_once(ELEMENTARY_OVERHANG).map_or(*cell, |(_, cell)| cell);
+                    *width = std::cmp::max(*width, cell.chars().count());
+                }
+            }
+        }
+
+        let mut lines = Vec::with_capacity(rows.len());
+        for (prefix, cells) in rows {
+            let mut line = "⠀".repeat(prefix_width - prefix.chars().count());
+            line.push_str(prefix);
+            for (i, cell) in cells.iter().enumerate() {
+                let width = widths[cells.len() - 1 - i];
+                if let Some(fill) = cell.strip_prefix(ELEMENTARY_FILL) {
+                    line.push_str(&fill.repeat(width));
+                    continue;
+                }
+                let (overhang, cell) = cell.split_once(ELEMENTARY_OVERHANG).unwrap_or(("", cell));
+                let padding = "⠀".repeat(width - cell.chars().count());
+                // A cell that is only an overhang (an operator) goes to the left of its column (which is otherwise blank)
+                //   so that both Nemeth and UEB put the operation sign to the left of the digits of the longest number
+                let is_only_overhang = cell.is_empty() && !overhang.is_empty();
+                if !is_only_overhang {
+                    line.push_str(&padding);
+                }
+                // the overhang uses up blanks to the left -- if there aren't enough, the row gets longer (to the left)
+                for _ in 0..overhang.chars().count() {
+                    if line.ends_with('⠀') {
+                        line.pop();
+                    }
+                }
+                line.push_str(overhang);
+                if is_only_overhang {
+                    line.push_str(&padding);
+                }
+                line.push_str(cell);
+            }
+            lines.push(line);
+        }
+
+        // an overhang might have made a row longer, so right align the rows (a line is extended)
+        let max_len = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let lines = lines.iter()
+                .map(|line| {
+                    let pad_len = max_len - line.chars().count();
+                    let padding = match line.chars().next() {
+                        Some(ch) if ch != '⠀' && line.chars().all(|line_ch| line_ch == ch) => ch.to_string().repeat(pad_len),
+                        _ => "⠀".repeat(pad_len),
+                    };
+                    return padding + line.trim_end_matches('⠀');
+                })
+                .collect::<Vec<String>>();
+        result.push_str(&lines.join(&LINE_SEPARATOR.to_string()));
+    }
+}
+
+/// Places where the braille can be broken into lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineBreak {
+    Forced,         // the start of an mtable row
+    Comparison,     // before a comparison operator
+    Operator,       // before any other operator
+}
+
+/// Number of cells runover lines are indented
+const RUNOVER_INDENT: usize = 2;
+
+/// Break 'braille' into lines of at most 'line_length' cells (separated by LINE_SEPARATOR).
+/// mtable rows start on a new line. Otherwise, lines are broken before the last operator that fits on the line.
+/// Nemeth ends a line that is broken before an operator with a continuation indicator; UEB only uses one if there isn't an operator to break at.
+/// Runover lines are indented.
+/// Returns the new braille along with the new (char) position of each char in 'braille' (plus the end of the string).
+/// Chars that are dropped (e.g., trailing spaces) have the position of the next char.
+fn break_into_lines(mathml: Element, braille: String, line_length: usize) -> Result<(String, Vec<usize>)> {
+    const CONTINUATION_INDICATOR: char = '⠐';
+    const NEMETH_DIGITS: [char; 10] = ['⠴', '⠂', '⠆', '⠒', '⠲', '⠢', '⠖', '⠶', '⠦', '⠔'];
+    let chars = braille.chars().collect::<Vec<char>>();
+    let line_breaks = get_line_breaks(mathml, &chars)?;
+    if chars.len() <= line_length && !line_breaks.iter().any(|&(_, kind)| kind == LineBreak::Forced) && !chars.contains(&LINE_SEPARATOR) {
+        return Ok( (braille, (0..=chars.len()).collect()) );
+    }
+    let is_nemeth = PreferenceManager::get().borrow().pref_to_string("BrailleCode") == "Nemeth";
+
+    let mut result = String::with_capacity(braille.len() + braille.len()/line_length * 3 * (RUNOVER_INDENT + 2));
+    let mut n_result_chars = 0;
+    let mut new_position = vec![0; chars.len() + 1];    // index in 'chars' -> index in 'result'
+    let mut i_start = 0;
+    let mut indent = 0;
+    let mut starts_row = false;
+    while i_start < chars.len() {
+        let i_forced = line_breaks.iter()
+                .filter(|&&(i, kind)| kind == LineBreak::Forced && i > i_start)
+                .map(|&(i, _)| i)
+                .chain( chars[i_start..].iter().position(|&ch| ch == LINE_SEPARATOR).map(|i| i_start + i) )
+                .min()
+                .unwrap_or(chars.len());
+        // always leave room for at least a char and the continuation indicator
+        let available = std::cmp::max(line_length.saturating_sub(indent), 2);
+        let (i_end, continuation) = if i_forced - i_start <= available {
+            (i_forced, None)
+        } else {
+            let i_last = i_start + available - 1;      // last char that fits on the line along with the continuation indicator
+            // prefer breaking before a comparison operator as long as that doesn't leave the line mostly empty
+            let candidates = line_breaks.iter()
+                    .filter(|&&(i, kind)| kind != LineBreak::Forced && i_start < i && i <= i_last)
+                    .collect::<Vec<_>>();
+            let i_operator = candidates.iter()
+                    .rfind(|&&&(i, kind)| kind == LineBreak::Comparison && i > i_start + available/2)
+                    .or(candidates.last())
+                    .map(|&&(i, _)| i);
+            match i_operator {
+                Some(i) => (i, if is_nemeth {Some(CONTINUATION_INDICATOR)} else {None}),
+                None => (i_last, Some(CONTINUATION_INDICATOR)),
+            }
+        };
+
+        result.extend(std::iter::repeat_n('⠀', indent));
+        n_result_chars += indent;
+        // Nemeth requires a numeric indicator at the start of a line (elementary math layouts are spatial and don't use them)
+        if is_nemeth && starts_row && NEMETH_DIGITS.contains(&unhighlight(chars[i_start])) {
+            result.push('⠼');
+            n_result_chars += 1;
+        }
+        // trailing blanks are dropped
+        let i_text_end = i_start + chars[i_start..i_end].iter().rposition(|&ch| unhighlight(ch) != '⠀').map_or(0, |i| i + 1);
+        for i in i_start..i_end {
+            new_position[i] = n_result_chars;
+            if i < i_text_end {
+                result.push(chars[i]);
+                n_result_chars += 1;
+            }
+        }
+        if let Some(ch) = continuation {
+            result.push(ch);
+            n_result_chars += 1;
+        }
+
+        // leading blanks are dropped, as is the separator Nemeth puts between mtable rows when they are on the same line
+        let is_forced = continuation.is_none() && i_end == i_forced;
+        let mut i_next = i_end;
+        while i_next < chars.len() &&
+              (chars[i_next] == LINE_SEPARATOR || unhighlight(chars[i_next]) == '⠀' || (is_forced && chars[i_next] == '⣍')) {
+            new_position[i_next] = n_result_chars;
+            i_next += 1;
+        }
+        if i_next < chars.len() {
+            result.push(LINE_SEPARATOR);
+            n_result_chars += 1;
+        }
+        indent = if is_forced {0} else {RUNOVER_INDENT};
+        starts_row = is_forced && chars.get(i_end) != Some(&LINE_SEPARATOR);
+        i_start = i_next;
+    }
+    new_position[chars.len()] = n_result_chars;
+    return Ok( (result, new_position) );
+}
+
+thread_local!{
+    /// The line breaks are the same no matter what node is highlighted, so the last ones computed are cached (keyed by the unhighlighted braille)
+    static LINE_BREAKS: RefCell<(String, Vec<(usize, LineBreak)>)> = const { RefCell::new( (String::new(), Vec::new()) ) };
+}
+
+/// Returns the (char) positions in 'braille' (the braille for 'mathml') where a line can be broken
+fn get_line_breaks(mathml: Element, braille: &[char]) -> Result<Vec<(usize, LineBreak)>> {
+    let key = braille.iter().map(|&ch| unhighlight(ch)).collect::<String>();
+    if let Some(line_breaks) = LINE_BREAKS.with(|cache| {
+        let cache = cache.borrow();
+        return if cache.0 == key {Some(cache.1.clone())} else {None};
+    }) {
+        return Ok(line_breaks);
+    }
+
+    let mut nodes = vec![];
+    for child in mathml.children() {
+        if let Some(child) = child.element() {
+            collect_line_break_nodes(child, &mut nodes);
+        }
+    }
+    let mut line_breaks = Vec::with_capacity(nodes.len());
+    for (node, kind) in nodes {
+        if let Some(id) = node.attribute_value("id") {
+            let (_, start, _) = braille_mathml_line(mathml, id)?;
+            if start > 0 {      // 0 means nothing was highlighted (e.g., invisible times) or it is at the start anyway
+                line_breaks.push( (start, kind) );
+            }
+        }
+    }
+    line_breaks.sort_by_key(|&(i, _)| i);
+    LINE_BREAKS.with(|cache| *cache.borrow_mut() = (key, line_breaks.clone()));
+    return Ok(line_breaks);
+
+    /// Find the operators in mrows and the rows of tables (other layouts such as fractions aren't broken)
+    fn collect_line_break_nodes<'a>(node: Element<'a>, nodes: &mut Vec<(Element<'a>, LineBreak)>) {
+        match name(node) {
+            "mrow" => {
+                for (i, child) in node.children().iter().enumerate() {
+                    let child = as_element(*child);
+                    if i > 0 && name(child) == "mo" && !crate::canonicalize::is_fence(child) {
+                        nodes.push( (child, if crate::canonicalize::is_relational_op(child) {LineBreak::Comparison} else {LineBreak::Operator}) );
+                    } else {
+                        collect_line_break_nodes(child, nodes);
+                    }
+                }
+            },
+            "mtable" => {
+                for (i, row) in node.children().iter().enumerate() {
+                    let row = as_element(*row);
+                    if i > 0 {
+                        nodes.push( (row, LineBreak::Forced) );
+                    }
+                    collect_line_break_nodes(row, nodes);
+                }
+            },
+            "mtr" | "mlabeledtr" | "mtd" => {
+                for child in node.children() {
+                    collect_line_break_nodes(as_element(child), nodes);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
 // FIX: if 8-dot braille is needed, perhaps the highlights can be shifted to a "highlighted" 256 char block in private space 
 //   they would need to be unshifted for the external world
 fn is_highlighted(ch: char) -> bool {
@@ -269,6 +571,94 @@ fn unhighlight(ch: char) -> char {
     }
 }
 
+/// Braille broken into lines along with the MathML node that each braille cell comes from (see [`crate::get_braille_lines`])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrailleLines {
+    /// The braille for each line
+    pub lines: Vec<String>,
+    /// For each line, the `id` and offset of the smallest MathML node that contains each braille cell
+    /// (the offset is the char in a leaf such as `<mn>123</mn>` and is otherwise 0)
+    pub cells: Vec<Vec<(String, usize)>>,
+}
+
+/// Braille 'mathml' broken into lines (as determined by the `BrailleLineLength` pref) and find the node for each braille cell.
+/// If 'nav_node_id' is not an empty string, it is highlighted.
+pub fn braille_lines(mathml: Element, nav_node_id: &str) -> Result<BrailleLines> {
+    let braille = braille_mathml_line(mathml, nav_node_id)?.0;
+    let n_chars = braille.chars().count();
+
+    // find the highlighted range of each node -- the smallest one containing a cell is the node for the cell
+    let mut cell_nodes: Vec<Option<(Element, usize, usize)>> = vec![None; n_chars];       // (node, start, len)
+    crate::interface::with_preferences(&[("BrailleNavHighlight", "EndPoints")], || {
+        return set_cell_nodes(mathml, mathml, &mut cell_nodes);
+    })?;
+
+    // the offset in a leaf counts from the end so that indicators (e.g., a numeric indicator) at the start don't throw it off
+    let mut n_cells_after = vec![0; n_chars];
+    let mut n_cells_seen = std::collections::HashMap::new();
+    for (i, cell_node) in cell_nodes.iter().enumerate().rev() {
+        if let Some((_, start, len)) = cell_node {
+            let n_seen = n_cells_seen.entry((*start, *len)).or_insert(0);
+            n_cells_after[i] = *n_seen;
+            *n_seen += 1;
+        }
+    }
+    let cell_ids = cell_nodes.iter().zip(n_cells_after)
+            .map(|(cell_node, n_after)| {
+                let node = cell_node.map_or(mathml, |(node, _, _)| node);
+                let offset = if is_leaf(node) {as_text(node).chars().count().saturating_sub(1 + n_after)} else {0};
+                return (node.attribute_value("id").unwrap_or_default().to_string(), offset);
+            })
+            .collect::<Vec<(String, usize)>>();
+
+    // break the lines and move the nodes to the new positions -- added chars (e.g., indentation) belong to 'mathml'
+    let line_length = PreferenceManager::get().borrow().pref_to_string("BrailleLineLength").parse::<f64>().unwrap_or(0.0).max(0.0) as usize;
+    let (braille, new_position) = if line_length == 0 {
+        (braille, (0..=n_chars).collect())
+    } else {
+        break_into_lines(mathml, braille, line_length)?
+    };
+    let math_id = (mathml.attribute_value("id").unwrap_or_default().to_string(), 0);
+    let mut new_cell_ids = vec![math_id; new_position[n_chars]];
+    for (i, cell_id) in cell_ids.into_iter().enumerate() {
+        // dropped chars have the position of the next char, so don't let them overwrite it
+        if new_position[i] < new_position[i + 1] {
+            new_cell_ids[new_position[i]] = cell_id;
+        }
+    }
+
+    let mut lines = vec![];
+    let mut cells = vec![];
+    let mut new_cell_ids = new_cell_ids.into_iter();
+    for line in braille.split(LINE_SEPARATOR) {
+        let n_line_chars = line.chars().count();
+        lines.push(crate::braille_encoding::encode_braille_using_prefs(line)?);
+        cells.push(new_cell_ids.by_ref().take(n_line_chars).collect::<Vec<(String, usize)>>());
+        new_cell_ids.next();        // skip the separator
+    }
+    return Ok( BrailleLines{ lines, cells } );
+
+    fn set_cell_nodes<'a>(mathml: Element<'a>, node: Element<'a>, cell_nodes: &mut [Option<(Element<'a>, usize, usize)>]) -> Result<()> {
+        if let Some(id) = node.attribute_value("id") {
+            let (_, start, end) = braille_mathml_line(mathml, id)?;
+            let is_highlighted = !(start == 0 && end == cell_nodes.len()) || node == mathml || get_parent(node) == mathml;
+            if is_highlighted {
+                for cell in cell_nodes.iter_mut().take(end).skip(start) {
+                    if cell.is_none_or(|(_, _, len)| end - start <= len) {     // on a tie, the child wins
+                        *cell = Some( (node, start, end - start) );
+                    }
+                }
+            }
+        }
+        for child in node.children() {
+            if let Some(child) = child.element() {
+                set_cell_nodes(mathml, child, cell_nodes)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
 use std::cell::RefCell;
 thread_local!{
     /// Count number of probes -- get a sense of how well algorithm is working (for debugging)
@@ -332,10 +722,12 @@ pub fn get_navigation_node_from_braille_position(mathml: Element, position: usiz
         };
         N_PROBES.with(|n| {*n.borrow_mut() += 1});
         let (braille, char_start, char_end) = braille_mathml(mathml, node_id)?;
+        // the braille might be on multiple lines, so '\n' (1 byte) can be in it along with the (3 byte) braille chars
+        let braille = braille.chars().collect::<Vec<char>>();
         let mut status = None;
         // debug!("find_navigation_node ({}, id={}): highlight=[{}, {});  target={}", name(node), node_id, char_start, char_end, target_position);
         if is_leaf(node) {
-            if char_start == 0 && char_end == braille.len()/3 {
+            if char_start == 0 && char_end == braille.len() {
                 // nothing highlighted -- probably invisible char not represented in braille -- continue looking to the right
                 // debug!("  return due invisible char (?)' ");
                 status = Some(SearchStatus::LookRight);
@@ -351,9 +743,9 @@ pub fn get_navigation_node_from_braille_position(mathml: Element, position: usiz
             } else if name(node) == "mo" {
                 // if there is whitespace before or after the operator, consider the operator to be a match
                 if (char_start > 0 && target_position == char_start - 1 && 
-                    braille_at(&braille, 3*(char_start - 1)) == '⠀' && is_operator_that_adds_whitespace(node)) ||
-                   (3*char_end < braille.len() && target_position == char_end &&
-                    braille_at(&braille, 3*char_end) == '⠀' && is_operator_that_adds_whitespace(node)) {
+                    braille[char_start - 1] == '⠀' && is_operator_that_adds_whitespace(node)) ||
+                   (char_end < braille.len() && target_position == char_end &&
+                    braille[char_end] == '⠀' && is_operator_that_adds_whitespace(node)) {
                     return Ok( SearchState {
                         status: SearchStatus::Found,
                         node,
@@ -1676,6 +2068,13 @@ fn stands_alone(chars: &[char], i: usize) -> (bool, &[char], usize) {
 }
 
 
+/// Return the UEB braille for the letter 'cells' with the group signs (e.g., "in" in "sin") contracted.
+/// This is used by back-translation to recognize contracted function names.
+pub(crate) fn contract_ueb_letters(cells: &[char]) -> String {
+    let chars = cells.iter().flat_map(|&cell| ['L', cell]).collect::<Vec<char>>();
+    return handle_contractions(&chars, chars.iter().collect()).replace('L', "");
+}
+
 /// Return a modified result if chars can be contracted.
 /// Otherwise, the original string is returned
 fn handle_contractions(chars: &[char], mut result: String) -> String {
@@ -2045,8 +2444,8 @@ static FINNISH_INDICATOR_REPLACEMENTS: phf::Map<&str, &str> = phf_map! {
     "s" => "⠆",     // typeface single char indicator
     "w" => "",     // typeface word indicator
     "e" => "",     // typeface & capital terminator 
-    "," => "⠂",     // comma
-    "." => "⠲",     // period
+    "," => "⠄",     // comma (the decimal comma in numbers is ⠂)
+    "." => "⠄",     // period
     "-" => "-",     // hyphen
     "—" => "⠠⠤",   // normal dash (2014) -- assume all normal dashes are unified here [RUEB appendix 3]
     "―" => "⠐⠠⠤",  // long dash (2015) -- assume all long dashes are unified here [RUEB appendix 3]
@@ -2060,7 +2459,7 @@ static FINNISH_INDICATOR_REPLACEMENTS: phf::Map<&str, &str> = phf_map! {
 };
 
 fn finnish_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) -> String {
-    static REPLACE_INDICATORS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([SB𝔹TIREDGVHUP𝐏C𝐶LlMmb↑↓Nn𝑁WwZ,()])").unwrap());
+    static REPLACE_INDICATORS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([SB𝔹TIREDGVHUP𝐏C𝐶LlMmb↑↓Nn𝑁WwZ,.()#])").unwrap());
     // Numbers need to end with a space, but sometimes there is one there for other reasons
     static DROP_NUMBER_SEPARATOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(n.)\)").unwrap());
     static NUMBER_MATCH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"((N.)+[^WN𝐶#↑↓Z])").unwrap());
@@ -2081,7 +2480,7 @@ fn finnish_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) ->
     });
 
     // FIX: need to implement this -- this is just a copy of the Vietnam code
-    let result = result.replace("CG", "⠘")
+    let result = result.replace("CG", "⠸")     // capital Greek letter
                                     .replace("𝔹C", "⠩")
                                     .replace("DC", "⠰");
 
@@ -2106,7 +2505,7 @@ fn finnish_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) ->
             "D" => &fraktur,
             "V" => &greek_variant,
             _ => match FINNISH_INDICATOR_REPLACEMENTS.get(matched_char) {
-                None => {error!("REPLACE_INDICATORS and SWEDISH_INDICATOR_REPLACEMENTS are not in sync: missing '{matched_char}'"); ""},
+                None => {error!("REPLACE_INDICATORS and FINNISH_INDICATOR_REPLACEMENTS are not in sync: missing '{matched_char}'"); ""},
                 Some(&ch) => ch,
             },
         }
@@ -2167,6 +2566,62 @@ fn swedish_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) ->
     return result.to_string();
 }
 
+static MARBURG_INDICATOR_REPLACEMENTS: phf::Map<&str, &str> = phf_map! {
+    // FIX: Marburg has typeface indicators, but they aren't implemented yet (bold, etc., are dropped)
+    "S" => "",      // sans-serif
+    "B" => "",      // bold
+    "𝔹" => "",      // blackboard
+    "T" => "",      // script
+    "I" => "",      // italic
+    "R" => "",      // roman
+    "D" => "",      // German (Fraktur)
+    "1" => "⠠",     // lowercase letter indicator (a-j after a number)
+    "L" => "",      // Letter left in to assist in locating letters
+    "G" => "⠰",     // Greek
+    "V" => "⠰",     // Greek Variants
+    "C" => "⠨",     // capital
+    "𝑐" => "",      // second or latter braille cell of a capital letter
+    "𝐶" => "⠨",     // capital (from chemical element)
+    "N" => "⠼",     // number indicator
+    "𝑁" => "",      // dropped digit (continues the number)
+    "W" => "⠀",     // whitespace
+    "𝐖"=> "⠀",     // whitespace
+    "w" => "⠀",     // whitespace after function name
+    "s" => "",      // typeface single char indicator
+    "e" => "",      // typeface & capital terminator
+    "#" => "⠼",     // restart numeric mode (mixed numbers)
+    "P" => "",      // punctuation (only needs an indicator after a number)
+};
+
+fn marburg_cleanup(_pref_manager: Ref<PreferenceManager>, raw_braille: String) -> String {
+    static REPLACE_INDICATORS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([SB𝔹TIRDGVC𝑐𝐶LN𝑁W𝐖wse1#P])").unwrap());
+    // Punctuation directly after a number needs the punctuation indicator so it isn't read as a (dropped) digit
+    static PUNCTUATION_AFTER_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([N𝑁].)P").unwrap());
+    // The lower cells are also the dropped digits of fractions and scripts, so they can't directly follow a digit
+    static LOWER_CELL_AFTER_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([N𝑁].)([⠂⠆⠒⠲⠢⠖⠶⠦⠔⠴])").unwrap());
+
+    // debug!("marburg_cleanup: start={}", raw_braille);
+    let result = PUNCTUATION_AFTER_NUMBER.replace_all(&raw_braille, "${1}⠠");
+    let result = LOWER_CELL_AFTER_NUMBER.replace_all(&result, "${1}W${2}");
+    let result = result.replace("CG", "⠰⠘");     // capital Greek
+    let result = result.replace("𝔹C", "⠨C");     // double-struck capitals (ℕ, ℝ, ...) have a doubled capital sign
+
+    // This reuses the code for getting rid of unnecessary "L"s and "N"s -- it also adds "1" before a-j after a digit
+    let result = remove_unneeded_mode_changes(&result, UEB_Mode::Grade1, UEB_Duration::Passage);
+    // debug!("   after removing mode changes={}", &result);
+
+    let result = REPLACE_INDICATORS.replace_all(&result, |cap: &Captures| {
+        match MARBURG_INDICATOR_REPLACEMENTS.get(&cap[0]) {
+            None => {error!("REPLACE_INDICATORS and MARBURG_INDICATOR_REPLACEMENTS are not in sync: missing '{}'", &cap[0]); ""},
+            Some(&ch) => ch,
+        }
+    });
+
+    let result = COLLAPSE_SPACES.replace_all(&result, "⠀");
+    let result = result.trim_matches('⠀');
+    return result.to_string();
+}
+
 #[allow(non_snake_case)]
 fn LaTeX_cleanup(_pref_manager: Ref<PreferenceManager>, raw_braille: String) -> String {
     static REMOVE_SPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" ([\^_,;)\]}])").unwrap()); // '^', '_', ',', ';', ')', ']', '}'
@@ -2217,57 +2672,54 @@ use sxd_xpath::{context, nodeset::*, Value};
 
 pub struct NemethNestingChars;
 const NEMETH_FRAC_LEVEL: &str = "data-nemeth-frac-level";    // name of attr where value is cached
-const FIRST_CHILD_ONLY: &[&str] = &["mroot", "msub", "msup", "msubsup", "munder", "mover", "munderover", "mmultiscripts"];
+// the intent names ('power', etc.) are used when this is called from speech rules (e.g., MathSpeak)
+const FIRST_CHILD_ONLY: &[&str] = &["mroot", "msub", "msup", "msubsup", "munder", "mover", "munderover", "mmultiscripts",
+                                    "root", "power", "indexed-by"];
 impl NemethNestingChars {
-    // returns a 'repeat_char' corresponding to the Nemeth rules for nesting
-    // note: this value is likely one char too long because the starting fraction is counted
-    fn nemeth_frac_value(node: Element, repeat_char: &str) -> String {
+    // returns the number of fraction levels corresponding to the Nemeth rules for nesting
+    // note: this value is one too large because the starting fraction is counted
+    fn nemeth_frac_value(node: Element) -> usize {
         let children = node.children();
         let name = name(node);
         if is_leaf(node) {
-            return "".to_string();
-        } else if name == "mfrac" {
+            return 0;
+        } else if name == "mfrac" || name == "fraction" {
             // have we already computed the value?
-            if let Some(value) = node.attribute_value(NEMETH_FRAC_LEVEL) {
-                return value.to_string();
+            if let Some(value) = node.attribute_value(NEMETH_FRAC_LEVEL).and_then(|value| value.parse::<usize>().ok()) {
+                return value;
             }
 
-            let num_value = NemethNestingChars::nemeth_frac_value(as_element(children[0]), repeat_char);
-            let denom_value = NemethNestingChars::nemeth_frac_value(as_element(children[1]), repeat_char);
-            let mut max_value = if num_value.len() > denom_value.len() {num_value} else {denom_value};
-            max_value += repeat_char;
-            node.set_attribute_value(NEMETH_FRAC_LEVEL, &max_value);
+            let num_value = NemethNestingChars::nemeth_frac_value(as_element(children[0]));
+            let denom_value = NemethNestingChars::nemeth_frac_value(as_element(children[1]));
+            let max_value = num_value.max(denom_value) + 1;
+            node.set_attribute_value(NEMETH_FRAC_LEVEL, &max_value.to_string());
             return max_value;
         } else if FIRST_CHILD_ONLY.contains(&name) {
             // only look at the base -- ignore scripts/index
-            return NemethNestingChars::nemeth_frac_value(as_element(children[0]), repeat_char);
+            return NemethNestingChars::nemeth_frac_value(as_element(children[0]));
         } else {
-            let mut result = "".to_string();
-            for child in children {
-                let value = NemethNestingChars::nemeth_frac_value(as_element(child), repeat_char);
-                if value.len() > result.len() {
-                    result = value;
-                }
-            }
-            return result;
+            return children.iter()
+                .map(|&child| NemethNestingChars::nemeth_frac_value(as_element(child)))
+                .max()
+                .unwrap_or(0);
         }
     }
 
-    fn nemeth_root_value(node: Element, repeat_char: &str) -> StdResult<String, XPathError> {
-        // returns the correct number of repeat_chars to use
+    fn nemeth_root_value(node: Element) -> StdResult<usize, XPathError> {
+        // returns the number of enclosing roots
         // note: because the highest count is toward the leaves and
         //    because this is a loop and not recursive, caching doesn't work without a lot of overhead
         let parent = node.parent().unwrap();
         if let ParentOfChild::Element(e) =  parent {
             let mut parent = e;
-            let mut result = "".to_string();
+            let mut result = 0;
             loop {
                 let name = name(parent);
                 if name == "math" {
                     return Ok( result );
                 }
-                if name == "msqrt" || name == "mroot" {
-                    result += repeat_char;
+                if matches!(name, "msqrt" | "mroot" | "square-root" | "root") {
+                    result += 1;
                 }
                 let parent_of_child = parent.parent().unwrap();
                 if let ParentOfChild::Element(e) =  parent_of_child {
@@ -2285,7 +2737,7 @@ impl Function for NemethNestingChars {
 /**
  * Returns a string with the correct number of nesting chars (could be an empty string)
  * @param(node) -- current node
- * @param(char) -- char (string) that should be repeated
+ * @param(char) -- char (string) that should be repeated -- this can also be a word (e.g., "Start" for MathSpeak)
  * Note: as a side effect, an attribute with the value so repeated calls to this or a child will be fast
  */
  fn evaluate<'d>(&self,
@@ -2300,13 +2752,12 @@ impl Function for NemethNestingChars {
         if let Node::Element(el) = node {
             let name = name(el);
             // it is likely a bug to call this one a non mfrac
-            if name == "mfrac" {
+            if name == "mfrac" || name == "fraction" {
                 // because it is called on itself, the fraction is counted one too many times -- chop one off
-                // this is slightly messy because we are chopping off a char, not a byte
-                const BRAILLE_BYTE_LEN: usize = "⠹".len();      // all Unicode braille symbols have the same number of bytes
-                return Ok( Value::String( NemethNestingChars::nemeth_frac_value(el, &repeat_char)[BRAILLE_BYTE_LEN..].to_string() ) );
-            } else if name == "msqrt" || name == "mroot" {
-                return Ok( Value::String( NemethNestingChars::nemeth_root_value(el, &repeat_char)? ) );
+                let level = NemethNestingChars::nemeth_frac_value(el);
+                return Ok( Value::String( repeat_char.repeat(level.saturating_sub(1)) ) );
+            } else if matches!(name, "msqrt" | "mroot" | "square-root" | "root") {
+                return Ok( Value::String( repeat_char.repeat(NemethNestingChars::nemeth_root_value(el)?) ) );
             } else {
                 return Err(XPathError::Other(format!("NestingChars chars should be used only on 'mfrac'. '{}' was passed in", name)));
             }
@@ -2329,7 +2780,8 @@ impl BrailleChars {
             "CMU" => BrailleChars:: get_braille_cmu_chars(node, text_range),
             "Vietnam" => BrailleChars:: get_braille_vietnam_chars(node, text_range),
             "Swedish" => BrailleChars:: get_braille_ueb_chars(node, text_range),    // FIX: need to figure out what to implement
-            "Finnish" => BrailleChars:: get_braille_ueb_chars(node, text_range),    // FIX: need to figure out what to implement
+            "Finnish" => BrailleChars:: get_braille_ueb_chars(node, text_range),    // typeforms/caps are left as flags for finnish_cleanup
+            "Marburg" => BrailleChars:: get_braille_marburg_chars(node, text_range),
             _ => return Err(sxd_xpath::function::Error::Other(format!("get_braille_chars: unknown braille code '{code}'")))
         };
         return match result {
@@ -2530,6 +2982,23 @@ impl BrailleChars {
         }
     }
 
+    fn get_braille_marburg_chars(node: Element, text_range: Option<Range<usize>>) -> Result<String> {
+        // This is the same as for UEB except for numbers:
+        //   Marburg always uses a decimal comma and a dot 3 to separate blocks of digits.
+        //   The separators in the MathML depend on the language/prefs, so they are normalized to ',' and '.' for unicode.yaml.
+        // The number sign and the lowercase letter prefix (a-j after a digit) are sorted out in marburg_cleanup()
+        if name(node) != "mn" || node.attribute("data-roman-numeral").is_some() {
+            return BrailleChars::get_braille_ueb_chars(node, text_range);
+        }
+        let decimal_separators = crate::interface::get_preference("DecimalSeparators")?;
+        let block_separators = crate::interface::get_preference("BlockSeparators")?;
+        let text = BrailleChars::substring(as_text(node), &text_range);
+        let text = text.chars()
+                    .map(|ch| if decimal_separators.contains(ch) {','} else if block_separators.contains(ch) {'.'} else {ch})
+                    .collect::<String>();
+        return braille_replace_chars(&text, node);
+    }
+
     fn get_braille_vietnam_chars(node: Element, text_range: Option<Range<usize>>) -> Result<String> {
         // this is basically the same as for ueb except:
         // 1. we deal with switching '.' and ',' if in English style for numbers
@@ -2860,6 +3329,53 @@ impl NeedsToBeGrouped {
         }
     }
 
+    /// Marburg uses auxiliary brackets to group an item that is more than a single "unit"
+    fn needs_grouping_for_marburg(mathml: Element, is_base: bool) -> bool {
+        use crate::xpath_functions::IsInDefinition;
+        let mut node_name = name(mathml);
+        if mathml.attribute_value("data-roman-numeral").is_some() {
+            node_name = "mi";           // roman numerals don't follow number rules
+        }
+
+        match node_name {
+            "mn" => return false,       // a number is a unit (including decimals)
+            "mi" | "mo" | "mtext" => {
+                let text = as_text(mathml);
+                let mut chars = text.chars();
+                chars.next();
+                let is_one_char = chars.next().is_none();
+                return !(is_one_char ||
+                         IsInDefinition::is_defined_in(text, &SPEECH_DEFINITIONS, "FunctionNames").unwrap());
+            },
+            "mfrac" => {
+                // numeric fractions are written as a number with a dropped denominator, so they are a unit
+                let children = mathml.children();
+                return !children.iter().all(|&child| {
+                    let child = as_element(child);
+                    name(child) == "mn" && as_text(child).chars().all(|ch| ch.is_ascii_digit())
+                });
+            },
+            "msqrt" | "mroot" => return false,      // the radicand is grouped if needed
+            "mrow" => {
+                if IsBracketed::is_bracketed(mathml, "", "", false, true) {
+                    return false;
+                }
+                // a prefix operator (e.g., "-x") stays with its operand
+                let children = mathml.children();
+                return !(children.len() == 2 && name(as_element(children[0])) == "mo");
+            },
+            "msub" | "msup" | "msubsup" => {
+                // nested scripts in the base of an under/over script need grouping
+                if !is_base {
+                    return false;
+                }
+                let parent_name = name(get_parent(mathml));
+                return parent_name == "munder" || parent_name == "mover" || parent_name == "munderover";
+            },
+            _ => return false,
+        }
+    }
+
     // ordinals often have an irregular start (e.g., "half") before becoming regular.
     // if the number is irregular, return the ordinal form, otherwise return 'None'.
     fn needs_grouping_for_swedish(mathml: Element, is_base: bool) -> bool {
@@ -3016,7 +3532,8 @@ impl Function for NeedsToBeGrouped {
                 "UEB" => NeedsToBeGrouped::needs_grouping_for_ueb(e, is_base),
                 "Finnish" => NeedsToBeGrouped::needs_grouping_for_finnish(e, is_base),
                 "Swedish" => NeedsToBeGrouped::needs_grouping_for_swedish(e, is_base),
-                _ => return Err(XPathError::Other(format!("NeedsToBeGrouped: braille code arg '{braille_code:?}' is not a known code ('UEB', 'CMU', or 'Swedish')"))),
+                "Marburg" => NeedsToBeGrouped::needs_grouping_for_marburg(e, is_base),
+                _ => return Err(XPathError::Other(format!("NeedsToBeGrouped: braille code arg '{braille_code:?}' is not a known code ('UEB', 'CMU', 'Finnish', 'Marburg', or 'Swedish')"))),
             };
             return Ok( Value::Boolean( answer ) );
         }
@@ -0,0 +1,135 @@
+#![allow(clippy::needless_return)]
+//! Output encodings for braille.
+//!
+//! MathCAT generates Unicode braille (U+2800 - U+28FF). Embossers and BRF files use North American ASCII braille (6 dots)
+//! and several European displays use an 8-dot Eurobraille table. The `BrailleEncoding` preference determines which is used.
+//! The encodings are one char per cell, so braille positions (e.g., from `get_braille_position`) are the same in all of them.
+//! Chars that aren't braille cells (e.g., the '\n' between lines) are left as is.
+use crate::errors::*;
+use crate::prefs::PreferenceManager;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// North American ASCII braille (BRF) for the 64 six-dot cells (indexed by the dots -- the low 6 bits of the Unicode braille char)
+const ASCII_BRAILLE: &[u8; 64] = b" A1B'K2L@CIF/MSP\"E3H9O6R^DJG>NTQ,*5<-U8V.%[$+X!&;:4\\0Z7(_?W]#Y)=";
+
+/// Eurobraille (Windows-1252 based) char for each of the 256 eight-dot cells (indexed by the dots).
+/// This is derived from `PythonScripts/euro-braille-dict.txt`.
+/// A few cells don't have a char in the table -- those are left as Unicode braille.
+const EUROBRAILLE: [char; 256] = [
+        ' ', 'a', ',', 'b', '.', 'k', ';', 'l', '"', 'c', 'i', 'f', '|', 'm', 's', 'p',
+        '!', 'e', ':', 'h', '*', 'o', '+', 'r', '>', 'd', 'j', 'g', '`', 'n', 't', 'q',
+        '\'', '1', '?', '2', '-', 'u', '(', 'v', '$', '3', '9', '6', '0', 'x', '~', '&',
+        '<', '5', '/', '8', ')', 'z', '=', '{', '⠸', '4', 'w', '7', '#', 'y', '}', '%',
+        '\u{a0}', 'A', '‹', 'B', '·', 'K', 'Œ', 'L', '’', 'C', 'I', 'F', '\\', 'M', 'S', 'P',
+        'Ì', 'E', '⡒', 'H', '§', 'O', 'Þ', 'R', '€', 'D', 'J', 'G', '@', 'N', 'T', 'Q',
+        '⡠', 'Â', 'Õ', 'Ê', '¡', 'U', '‘', 'V', 'œ', 'Î', 'Ø', '⡫', 'Ã', 'X', '^', 'Ç',
+        'Ä', 'Û', 'Ñ', 'Ž', 'Ð', 'Z', '⡶', '[', '_', 'Ô', 'W', '⡻', 'Å', 'Y', ']', 'É',
+        '⢀', '¹', 'Á', '²', '¿', '“', '…', '”', '¨', '³', '‰', '†', 'ì', 'µ', '×', 'þ',
+        '¢', '¦', 'Í', 'ª', 'Ö', 'õ', 'Ë', '®', '¯', '¶', 'º', '‡', 'ä', 'ñ', 'ð', '›',
+        '¸', 'á', 'Ú', '„', '–', '¼', 'Ü', '½', '¥', 'í', 'ö', 'ë', 'ò', '¾', 'è', '©',
+        '´', 'š', 'Ó', 'ü', 'Ý', '⢵', 'Ï', 'à', '°', 'ó', 'ý', 'ï', 'ß', 'ÿ', 'ù', 'é',
+        'ƒ', '⣁', '⣂', '⣃', 'ˆ', '⣅', '⣆', '⣇', 'æ', '⣉', '⣊', '⣋', '⣌', '⣍', '⣎', '⣏',
+        'Ò', '⣑', '˜', '⣓', 'È', '⣕', '±', '⣗', '»', '⣙', '⣚', '⣛', '⣜', '⣝', '⣞', '⣟',
+        'Š', 'â', '⣢', 'ê', '‚', '⣥', 'À', '⣧', '¤', 'î', 'ø', '⣫', 'ã', '⣭', '⣮', 'ç',
+        '«', 'û', '¬', '÷', 'Ù', '⣵', '⣶', '⣷', '⣸', 'ô', '⣺', '™', 'å', '⣽', '⣾', 'Ÿ',
+];
+
+static EUROBRAILLE_TO_UNICODE: LazyLock<HashMap<char, char>> = LazyLock::new(|| {
+    return EUROBRAILLE.iter().enumerate()
+        .filter(|&(i, &ch)| ch != unicode_braille(i))
+        .map(|(i, &ch)| (ch, unicode_braille(i)))
+        .collect();
+});
+
+fn unicode_braille(dots: usize) -> char {
+    return char::from_u32(0x2800 + dots as u32).unwrap();
+}
+
+/// Returns the dots (0-255) for 'ch' if it is a Unicode braille char
+fn braille_dots(ch: char) -> Option<usize> {
+    return if ('\u{2800}'..='\u{28FF}').contains(&ch) {Some(ch as usize - 0x2800)} else {None};
+}
+
+/// Convert the Unicode braille in 'braille' to the given encoding ("Unicode", "ASCII-BRF", or "Eurobraille").
+/// ASCII-BRF only has six dots, so dots 7 and 8 (e.g., from `BrailleNavHighlight`) are dropped.
+pub fn encode_braille(braille: &str, encoding: &str) -> Result<String> {
+    return match encoding {
+        "Unicode" => Ok(braille.to_string()),
+        "ASCII-BRF" => Ok(braille.chars()
+                .map(|ch| braille_dots(ch).map_or(ch, |dots| ASCII_BRAILLE[dots & 0x3F] as char))
+                .collect()),
+        "Eurobraille" => Ok(braille.chars()
+                .map(|ch| braille_dots(ch).map_or(ch, |dots| EUROBRAILLE[dots]))
+                .collect()),
+        _ => bail!("Unknown braille encoding '{}' (should be 'Unicode', 'ASCII-BRF', or 'Eurobraille')", encoding),
+    };
+}
+
+/// Convert the Unicode braille in 'braille' to the encoding given by the `BrailleEncoding` preference
+pub(crate) fn encode_braille_using_prefs(braille: &str) -> Result<String> {
+    let encoding = PreferenceManager::get().borrow().pref_to_string("BrailleEncoding");
+    return encode_braille(braille, &encoding);
+}
+
+/// Convert 'braille' in the given encoding back to Unicode braille (the inverse of [`encode_braille`]).
+/// Unicode braille chars and line breaks are accepted in any encoding. For ASCII-BRF, upper and lower case letters are the same.
+pub fn decode_braille(braille: &str, encoding: &str) -> Result<String> {
+    let mut result = String::with_capacity(3 * braille.len());
+    for ch in braille.chars() {
+        if braille_dots(ch).is_some() || ch == '\n' || ch == '\r' {
+            result.push(ch);
+            continue;
+        }
+        let cell = match encoding {
+            "Unicode" => None,
+            "ASCII-BRF" => {
+                let ascii = ch.to_ascii_uppercase();
+                // '`', '{', '|', '}', and '~' are lower case versions of '@', '[', '\', ']', and '^'
+                let ascii = if ('`'..='~').contains(&ascii) {(ascii as u8 - 0x20) as char} else {ascii};
+                ASCII_BRAILLE.iter().position(|&b| b as char == ascii).map(unicode_braille)
+            },
+            "Eurobraille" => EUROBRAILLE_TO_UNICODE.get(&ch).copied(),
+            _ => bail!("Unknown braille encoding '{}' (should be 'Unicode', 'ASCII-BRF', or 'Eurobraille')", encoding),
+        };
+        match cell {
+            Some(cell) => result.push(cell),
+            None => bail!("'{}' (U+{:04X}) is not a braille char in the '{}' braille encoding", ch, ch as u32, encoding),
+        }
+    }
+    return Ok(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_braille() -> Result<()> {
+        assert_eq!(encode_braille("⠠⠷⠼⠂⠀⠨⠅\n⣒⠀⠭⠘⠆", "ASCII-BRF")?, ",(#1 .K\n3 X^2");
+        assert_eq!(decode_braille(",(#1 .k\n3 x^2", "ASCII-BRF")?, "⠠⠷⠼⠂⠀⠨⠅\n⠒⠀⠭⠘⠆");
+        assert!(decode_braille("x \u{2200} y", "ASCII-BRF").is_err());
+        return Ok(());
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let all_cells = (0..256).map(unicode_braille).collect::<String>();
+        for encoding in ["Unicode", "Eurobraille"] {
+            assert_eq!(decode_braille(&encode_braille(&all_cells, encoding)?, encoding)?, all_cells);
+        }
+        let six_dot_cells = (0..64).map(unicode_braille).collect::<String>();
+        let ascii = encode_braille(&six_dot_cells, "ASCII-BRF")?;
+        assert!(ascii.chars().all(|ch| ch.is_ascii() && !ch.is_ascii_lowercase()));
+        assert_eq!(decode_braille(&ascii, "ASCII-BRF")?, six_dot_cells);
+        assert_eq!(decode_braille(&ascii.to_ascii_lowercase(), "ASCII-BRF")?, six_dot_cells);
+        return Ok(());
+    }
+
+    #[test]
+    fn eurobraille() -> Result<()> {
+        assert_eq!(encode_braille("⠁⡁⠼⠂", "Eurobraille")?, "aA#,");
+        assert!(encode_braille("⠁", "BRF").is_err());
+        return Ok(());
+    }
+}
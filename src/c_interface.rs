@@ -0,0 +1,400 @@
+//! The C API for MathCAT. It mirrors the functions in [`crate::interface`].
+//!
+//! A header (`mathcat.h`) is generated from this file when building with the `c-header` feature (see `build.rs`).
+//! It is written to the build script's `OUT_DIR`.
+//!
+//! Conventions:
+//! * All functions return a status code: `MATHCAT_OK` (0) or one of the `MATHCAT_ERROR_*` codes.
+//!   On an error, `mathcat_last_error()` returns a description of the error.
+//! * Strings passed in are NUL-terminated UTF-8 and are only borrowed for the duration of the call.
+//! * Strings returned via a `char **` out parameter are owned by the caller and must be freed with `mathcat_free_string()`.
+//!   The out parameter is set to NULL if there is an error.
+//! * The string returned by `mathcat_last_error()` is owned by MathCAT; it is valid until the next MathCAT call on that thread.
+//! * Like the Rust interface, the state (rules dir, preferences, MathML, navigation) is per thread.
+//!
+//! # Safety
+//! All pointer arguments must either be NULL or be valid for the access described above (NUL-terminated strings to read,
+//! or locations to write). NULL is only allowed where noted; otherwise `MATHCAT_ERROR_NULL_ARGUMENT` is returned.
+#![allow(clippy::needless_return, clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use crate::errors::*;
+use crate::interface::*;
+
+/// The version of the C API. This is incremented when a function is changed in an incompatible way.
+pub const MATHCAT_C_API_VERSION: u32 = 1;
+
+/// The call succeeded
+pub const MATHCAT_OK: c_int = 0;
+/// The call failed -- `mathcat_last_error()` describes the problem
+pub const MATHCAT_ERROR: c_int = 1;
+/// A required pointer argument was NULL
+pub const MATHCAT_ERROR_NULL_ARGUMENT: c_int = 2;
+/// A string argument was not valid UTF-8 (or a result contained a NUL char)
+pub const MATHCAT_ERROR_INVALID_STRING: c_int = 3;
+/// MathCAT crashed -- this is a bug in MathCAT
+pub const MATHCAT_ERROR_PANIC: c_int = 4;
+
+thread_local!{
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// An error in the arguments passed in (as opposed to an error from MathCAT)
+#[derive(Debug)]
+struct ArgumentError {
+    status: c_int,
+    message: String,
+}
+
+impl std::fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.message);
+    }
+}
+
+impl std::error::Error for ArgumentError {}
+
+/// Run 'f', set the last error, and return the status code
+fn run(f: impl FnOnce() -> Result<()>) -> c_int {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => (MATHCAT_OK, String::new()),
+        Ok(Err(e)) => match e.downcast_ref::<ArgumentError>() {
+            Some(argument_error) => (argument_error.status, argument_error.message.clone()),
+            None => (MATHCAT_ERROR, errors_to_string(&e)),
+        },
+        Err(_) => (MATHCAT_ERROR_PANIC, "MathCAT crashed -- please report this as a bug".to_string()),
+    };
+    LAST_ERROR.with(|last_error| {
+        *last_error.borrow_mut() = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    });
+    return status;
+}
+
+/// Borrow the string 'str' (named 'arg_name' for error messages)
+unsafe fn to_str<'a>(str: *const c_char, arg_name: &str) -> Result<&'a str> {
+    if str.is_null() {
+        return Err( ArgumentError { status: MATHCAT_ERROR_NULL_ARGUMENT, message: format!("'{arg_name}' is NULL") }.into() );
+    }
+    return match unsafe { CStr::from_ptr(str) }.to_str() {
+        Ok(str) => Ok(str),
+        Err(e) => Err( ArgumentError { status: MATHCAT_ERROR_INVALID_STRING, message: format!("'{arg_name}' is not UTF-8: {e}") }.into() ),
+    };
+}
+
+/// Make sure the out parameter 'out' can be written to
+fn check_out<T>(out: *mut T, arg_name: &str) -> Result<()> {
+    if out.is_null() {
+        return Err( ArgumentError { status: MATHCAT_ERROR_NULL_ARGUMENT, message: format!("'{arg_name}' is NULL") }.into() );
+    }
+    return Ok( () );
+}
+
+/// Set '*out' to a copy of 'str' that the caller owns
+unsafe fn set_out_string(out: *mut *mut c_char, str: String) -> Result<()> {
+    match CString::new(str) {
+        Ok(str) => unsafe { *out = str.into_raw() },
+        Err(e) => return Err( ArgumentError { status: MATHCAT_ERROR_INVALID_STRING, message: format!("result contains a NUL: {e}") }.into() ),
+    }
+    return Ok( () );
+}
+
+/// Initialize a `char **` out parameter to NULL (if not NULL)
+unsafe fn clear_out_string(out: *mut *mut c_char) {
+    if !out.is_null() {
+        unsafe { *out = std::ptr::null_mut() };
+    }
+}
+
+/// Returns `MATHCAT_C_API_VERSION` for the library that is loaded (compare it to the value in the header).
+#[unsafe(no_mangle)]
+pub extern "C" fn mathcat_c_api_version() -> u32 {
+    return MATHCAT_C_API_VERSION;
+}
+
+/// Free a string returned by MathCAT. NULL is allowed (nothing happens).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_free_string(str: *mut c_char) {
+    if !str.is_null() {
+        drop(unsafe { CString::from_raw(str) });
+    }
+}
+
+/// Returns a description of the last error on this thread (an empty string if the last call succeeded).
+/// The string is owned by MathCAT and is valid until the next MathCAT call on this thread. It must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn mathcat_last_error() -> *const c_char {
+    return LAST_ERROR.with(|last_error| last_error.borrow().as_ptr());
+}
+
+/// Returns the version number of MathCAT (e.g., "0.7.6") in `*version`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_version(version: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(version) };
+    return run(|| {
+        check_out(version, "version")?;
+        return unsafe { set_out_string(version, get_version()) };
+    });
+}
+
+/// Set the Rules directory.
+/// IMPORTANT: this should be the very first call to MathCAT unless the environment var MathCATRulesDir is set
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_rules_dir(dir: *const c_char) -> c_int {
+    return run(|| set_rules_dir(unsafe { to_str(dir, "dir")? }));
+}
+
+/// Set the MathML (overriding any previous MathML). If `canonical_mathml` isn't NULL, it is set to the canonical MathML
+/// with 'id's on every node (see `set_mathml` in the Rust interface).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_mathml(mathml: *const c_char, canonical_mathml: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(canonical_mathml) };
+    return run(|| {
+        let result = set_mathml(unsafe { to_str(mathml, "mathml")? })?;
+        if !canonical_mathml.is_null() {
+            unsafe { set_out_string(canonical_mathml, result)? };
+        }
+        return Ok( () );
+    });
+}
+
+/// Like `mathcat_set_mathml`, but the input is LaTeX.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_latex(latex: *const c_char, canonical_mathml: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(canonical_mathml) };
+    return run(|| {
+        let result = set_latex(unsafe { to_str(latex, "latex")? })?;
+        if !canonical_mathml.is_null() {
+            unsafe { set_out_string(canonical_mathml, result)? };
+        }
+        return Ok( () );
+    });
+}
+
+/// Like `mathcat_set_mathml`, but the input is ASCIIMath.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_asciimath(asciimath: *const c_char, canonical_mathml: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(canonical_mathml) };
+    return run(|| {
+        let result = set_asciimath(unsafe { to_str(asciimath, "asciimath")? })?;
+        if !canonical_mathml.is_null() {
+            unsafe { set_out_string(canonical_mathml, result)? };
+        }
+        return Ok( () );
+    });
+}
+
+/// Get the spoken text of the MathML that was set.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_spoken_text(text: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(text) };
+    return run(|| {
+        check_out(text, "text")?;
+        return unsafe { set_out_string(text, get_spoken_text()?) };
+    });
+}
+
+/// Get the spoken text for an overview of the MathML that was set.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_overview_text(text: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(text) };
+    return run(|| {
+        check_out(text, "text")?;
+        return unsafe { set_out_string(text, get_overview_text()?) };
+    });
+}
+
+/// Get the value of the named preference.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_preference(name: *const c_char, value: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(value) };
+    return run(|| {
+        check_out(value, "value")?;
+        let name = unsafe { to_str(name, "name")? };
+        return unsafe { set_out_string(value, get_preference(name)?) };
+    });
+}
+
+/// Set a MathCAT preference (see `set_preference` in the Rust interface).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_preference(name: *const c_char, value: *const c_char) -> c_int {
+    return run(|| set_preference(unsafe { to_str(name, "name")? }, unsafe { to_str(value, "value")? }));
+}
+
+/// Get the braille for the MathML that was set. `nav_node_id` is the id of the node to highlight (NULL or "" for none).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_braille(nav_node_id: *const c_char, braille: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(braille) };
+    return run(|| {
+        check_out(braille, "braille")?;
+        let nav_node_id = if nav_node_id.is_null() {""} else {unsafe { to_str(nav_node_id, "nav_node_id")? }};
+        return unsafe { set_out_string(braille, get_braille(nav_node_id)?) };
+    });
+}
+
+/// Get the braille for the MathML that was set with the current navigation node highlighted.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_navigation_braille(braille: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(braille) };
+    return run(|| {
+        check_out(braille, "braille")?;
+        return unsafe { set_out_string(braille, get_navigation_braille()?) };
+    });
+}
+
+/// Navigate using a key code along with the modifier keys. `*speech` is set to the text to speak.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_do_navigate_keypress(key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool,
+                                                      speech: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(speech) };
+    return run(|| {
+        check_out(speech, "speech")?;
+        let result = do_navigate_keypress(key, shift_key, control_key, alt_key, meta_key)?;
+        return unsafe { set_out_string(speech, result) };
+    });
+}
+
+/// Navigate using a command (e.g., "MoveNext"). `*speech` is set to the text to speak.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_do_navigate_command(command: *const c_char, speech: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(speech) };
+    return run(|| {
+        check_out(speech, "speech")?;
+        let result = do_navigate_command(unsafe { to_str(command, "command")? })?;
+        return unsafe { set_out_string(speech, result) };
+    });
+}
+
+/// Set the navigation node to the node with the given id and offset.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_set_navigation_node(id: *const c_char, offset: usize) -> c_int {
+    return run(|| set_navigation_node(unsafe { to_str(id, "id")? }, offset));
+}
+
+/// Get the MathML of the current navigation node along with the offset into it (for leaves).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_navigation_mathml(mathml: *mut *mut c_char, offset: *mut usize) -> c_int {
+    unsafe { clear_out_string(mathml) };
+    return run(|| {
+        check_out(mathml, "mathml")?;
+        check_out(offset, "offset")?;
+        let (result, result_offset) = get_navigation_mathml()?;
+        unsafe { *offset = result_offset };
+        return unsafe { set_out_string(mathml, result) };
+    });
+}
+
+/// Get the id of the current navigation node along with the offset into it (for leaves).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_navigation_mathml_id(id: *mut *mut c_char, offset: *mut usize) -> c_int {
+    unsafe { clear_out_string(id) };
+    return run(|| {
+        check_out(id, "id")?;
+        check_out(offset, "offset")?;
+        let (result, result_offset) = get_navigation_mathml_id()?;
+        unsafe { *offset = result_offset };
+        return unsafe { set_out_string(id, result) };
+    });
+}
+
+/// Get the start and end braille cell positions of the current navigation node.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_braille_position(start: *mut usize, end: *mut usize) -> c_int {
+    return run(|| {
+        check_out(start, "start")?;
+        check_out(end, "end")?;
+        let (result_start, result_end) = get_braille_position()?;
+        unsafe {
+            *start = result_start;
+            *end = result_end;
+        }
+        return Ok( () );
+    });
+}
+
+/// Get the id and offset of the node that corresponds to the given braille cell position.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_navigation_node_from_braille_position(position: usize, id: *mut *mut c_char, offset: *mut usize) -> c_int {
+    unsafe { clear_out_string(id) };
+    return run(|| {
+        check_out(id, "id")?;
+        check_out(offset, "offset")?;
+        let (result, result_offset) = get_navigation_node_from_braille_position(position)?;
+        unsafe { *offset = result_offset };
+        return unsafe { set_out_string(id, result) };
+    });
+}
+
+/// Get the supported braille codes as a newline-separated list.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_supported_braille_codes(codes: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(codes) };
+    return run(|| {
+        check_out(codes, "codes")?;
+        return unsafe { set_out_string(codes, get_supported_braille_codes()?.join("\n")) };
+    });
+}
+
+/// Get the supported languages as a newline-separated list.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mathcat_get_supported_languages(languages: *mut *mut c_char) -> c_int {
+    unsafe { clear_out_string(languages) };
+    return run(|| {
+        check_out(languages, "languages")?;
+        return unsafe { set_out_string(languages, get_supported_languages()?.join("\n")) };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take ownership of a returned string
+    fn take_string(str: *mut c_char) -> String {
+        assert!(!str.is_null());
+        let result = unsafe { CStr::from_ptr(str) }.to_str().unwrap().to_string();
+        unsafe { mathcat_free_string(str) };
+        return result;
+    }
+
+    fn last_error() -> String {
+        return unsafe { CStr::from_ptr(mathcat_last_error()) }.to_str().unwrap().to_string();
+    }
+
+    #[test]
+    fn speech_and_braille() {
+        let rules_dir = CString::new(crate::abs_rules_dir_path()).unwrap();
+        assert_eq!(unsafe { mathcat_set_rules_dir(rules_dir.as_ptr()) }, MATHCAT_OK);
+        let (name, value) = (CString::new("BrailleCode").unwrap(), CString::new("Nemeth").unwrap());
+        assert_eq!(unsafe { mathcat_set_preference(name.as_ptr(), value.as_ptr()) }, MATHCAT_OK);
+
+        let mathml = CString::new("<math><msup><mi>x</mi><mn>2</mn></msup></math>").unwrap();
+        assert_eq!(unsafe { mathcat_set_mathml(mathml.as_ptr(), std::ptr::null_mut()) }, MATHCAT_OK);
+        let mut speech = std::ptr::null_mut();
+        assert_eq!(unsafe { mathcat_get_spoken_text(&mut speech) }, MATHCAT_OK);
+        assert_eq!(take_string(speech), "x squared");
+        let mut braille = std::ptr::null_mut();
+        assert_eq!(unsafe { mathcat_get_braille(std::ptr::null(), &mut braille) }, MATHCAT_OK);
+        assert_eq!(take_string(braille), "⠭⠘⠆");
+        assert_eq!(last_error(), "");
+    }
+
+    #[test]
+    fn errors() {
+        let mut speech = std::ptr::null_mut();
+        assert_eq!(unsafe { mathcat_set_mathml(std::ptr::null(), &mut speech) }, MATHCAT_ERROR_NULL_ARGUMENT);
+        assert_eq!(last_error(), "'mathml' is NULL");
+        assert!(speech.is_null());
+
+        let invalid = [0xC0u8, 0x00];
+        assert_eq!(unsafe { mathcat_set_mathml(invalid.as_ptr() as *const c_char, std::ptr::null_mut()) }, MATHCAT_ERROR_INVALID_STRING);
+
+        let mathml = CString::new("<math><mi>x</mi>").unwrap();
+        assert_eq!(unsafe { mathcat_set_mathml(mathml.as_ptr(), std::ptr::null_mut()) }, MATHCAT_ERROR);
+        assert!(!last_error().is_empty());
+
+        assert_eq!(unsafe { mathcat_get_spoken_text(std::ptr::null_mut()) }, MATHCAT_ERROR_NULL_ARGUMENT);
+        assert_eq!(mathcat_c_api_version(), MATHCAT_C_API_VERSION);
+    }
+}
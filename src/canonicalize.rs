@@ -563,6 +563,7 @@ impl CanonicalizeContext {
 			root.append_child(math_element);
 			mathml = root.children()[0].element().unwrap();
 		}
+		crate::content_mathml::convert_content_mathml(mathml)?;
 		CanonicalizeContext::assure_mathml(mathml, 0)?;
 		let mathml = self.clean_mathml(mathml).unwrap();	// 'math' is never removed
 		self.assure_nary_tag_has_one_child(mathml);
@@ -1076,6 +1077,13 @@ impl CanonicalizeContext {
 				set_annotation_attrs(new_presentation, mathml);
 				return Some(new_presentation);
 			},
+			"mstack" | "mlongdiv" => {
+				return match self.clean_elementary_math(mathml) {
+					Some(mathml) => Some(mathml),
+					None if parent_requires_child => Some( CanonicalizeContext::create_empty_element(&mathml.document()) ),
+					None => None,		// nothing in it
+				};
+			},
 			_  => {
 				let children = mathml.children();
 				if element_name == "mrow" {
@@ -3041,6 +3049,290 @@ impl CanonicalizeContext {
 		}
 	}
 
+	/// Elementary math (`mstack` and `mlongdiv`) is laid out in columns. The alignment rules (`position`, `shift`,
+	/// `stackalign`, carries that align with the following row, ...) are hard to deal with in the speech and braille rules,
+	/// so here the layout is turned into a simple grid:
+	/// * every child (after the divisor of an `mlongdiv`) is an `msrow`, `mscarries`, or `msline` (`msgroup`s are removed)
+	/// * every `msrow` and `mscarries` has exactly one child per column, with `none` filling any empty columns
+	/// * numbers in a row are split into one `mn` per digit (or separator)
+	/// * the non-`none` children of `mscarries` are `mscarry` elements with any inherited `crossout` and `location` set on them
+	/// * every `msline` has a `length` (the number of columns) and a `position` (the number of columns from the right edge)
+	///
+	/// `None` is returned if there are no columns (e.g., an empty `mstack`).
+	fn clean_elementary_math<'a>(&self, mathml: Element<'a>) -> Option<Element<'a>> {
+		/// A row in the layout before the rows are aligned.
+		/// 'position' is the MathML position of the row (positive values move the row to the left)
+		enum StackRow<'a> {
+			/// 'align' is the index of the alignment point in 'cells'
+			Row{ element: Element<'a>, cells: Vec<Element<'a>>, align: isize, position: isize },
+			/// Carries are aligned to the right of the following row (their 'position' is relative to it)
+			Carries{ element: Element<'a>, cells: Vec<Element<'a>>, position: isize },
+			/// A 'length' of 0 means the line spans all the columns
+			Line{ element: Element<'a>, length: isize, position: isize },
+		}
+
+		let doc = mathml.document();
+		let stack_align = mathml.attribute_value("stackalign").unwrap_or("decimalpoint").to_string();
+		// an empty mstack/mlongdiv had content added to it -- that isn't a row
+		let children = mathml.children().iter()
+				.map(|&child| as_element(child))
+				.filter(|child| child.attribute_value("data-added") != Some("missing-content"))
+				.collect::<Vec<Element>>();
+		if children.is_empty() {
+			return None;
+		}
+		let (divisor, children) = if name(mathml) == "mlongdiv" {
+			let divisor = self.clean_mathml(children[0]).unwrap_or_else(|| CanonicalizeContext::create_empty_element(&doc));
+			(Some(divisor), &children[1..])
+		} else {
+			(None, &children[..])
+		};
+		for &child in children {
+			self.clean_elementary_cells(child);
+		}
+		if is_grid(children) {
+			return Some(mathml);		// already cleaned
+		}
+
+		let mut rows = vec![];
+		collect_rows(self, children, 0, 0, &stack_align, &mut rows);
+
+		// 'position', 'shift', and 'length' can move a row (or extend a line) arbitrarily far and the rows get padded to the
+		// full width of the grid, so limit them to the width of the widest row (nothing sensible goes farther than that)
+		let max_offset = rows.iter()
+				.map(|row| match row {
+					StackRow::Row{ cells, .. } | StackRow::Carries{ cells, .. } => cells.len() as isize,
+					StackRow::Line{ .. } => 0,
+				})
+				.max().unwrap_or(0).max(1);
+		for row in &mut rows {
+			match row {
+				StackRow::Row{ position, .. } | StackRow::Carries{ position, .. } => *position = (*position).clamp(-max_offset, max_offset),
+				StackRow::Line{ length, position, .. } => {
+					*length = (*length).min(max_offset);
+					*position = (*position).clamp(-max_offset, max_offset);
+				},
+			}
+		}
+
+		// compute the column ('x' increases to the right) of the first cell in each row, then the extent of the grid
+		let mut first_x = vec![0; rows.len()];
+		for i in (0..rows.len()).rev() {
+			first_x[i] = match &rows[i] {
+				StackRow::Row{ align, position, .. } => -align - position,
+				StackRow::Carries{ cells, position, .. } => {
+					// right aligned with the next row (or its own right edge if there isn't a row after it)
+					let right_x = match rows[i+1..].iter().position(|row| matches!(row, StackRow::Row{..})) {
+						Some(i_row) => first_x[i+1+i_row] + row_width(&rows[i+1+i_row]),
+						None => 0,
+					};
+					right_x - position - cells.len() as isize
+				},
+				StackRow::Line{ length, position, .. } => -position - length,
+			};
+		}
+		let min_x = rows.iter().enumerate()
+				.filter(|(_, row)| !matches!(row, StackRow::Line{length: 0, ..}))
+				.map(|(i, _)| first_x[i]).min().unwrap_or(0);
+		let max_x = rows.iter().enumerate()
+				.filter(|(_, row)| !matches!(row, StackRow::Line{length: 0, ..}))
+				.map(|(i, row)| first_x[i] + row_width(row)).max().unwrap_or(0);
+		if max_x <= min_x && divisor.is_none() {
+			return None;		// no columns -- nothing to say or braille
+		}
+
+		let mut new_children = Vec::with_capacity(rows.len() + 1);
+		if let Some(divisor) = divisor {
+			new_children.push(divisor);
+		}
+		for (row, x) in rows.into_iter().zip(first_x) {
+			let new_child = match row {
+				StackRow::Row{ element, cells, .. } => {
+					let n_cells = cells.len() as isize;
+					element.replace_children(pad_cells(&doc, cells, x - min_x, max_x - x - n_cells));
+					element.remove_attribute("position");
+					element
+				},
+				StackRow::Carries{ element, cells, .. } => {
+					let n_cells = cells.len() as isize;
+					element.replace_children(pad_cells(&doc, cells, x - min_x, max_x - x - n_cells));
+					element.remove_attribute("position");
+					element
+				},
+				StackRow::Line{ element, length, .. } => {
+					let (length, position) = if length == 0 {(max_x - min_x, 0)} else {(length, max_x - x - length)};
+					element.set_attribute_value("length", &length.to_string());
+					element.set_attribute_value("position", &position.to_string());
+					element
+				},
+			};
+			new_children.push(new_child);
+		}
+		mathml.replace_children(new_children);
+		return Some(mathml);
+
+		/// Returns true if 'rows' already is a grid (e.g., canonicalizing canonicalized MathML)
+		fn is_grid(rows: &[Element]) -> bool {
+			let mut n_columns = None;
+			for &row in rows {
+				match name(row) {
+					"msline" => if row.attribute("length").is_none() {return false},
+					"msrow" | "mscarries" => {
+						if row.attribute("position").is_some() || row.children().is_empty() ||
+						   n_columns.is_some_and(|n| n != row.children().len()) ||
+						   row.children().iter().any(|&cell| name(as_element(cell)) == "mn" && as_text(as_element(cell)).chars().count() > 1) {
+							return false;
+						}
+						n_columns = Some(row.children().len());
+					},
+					_ => return false,
+				}
+			}
+			return true;
+		}
+
+		fn collect_rows<'a>(context: &CanonicalizeContext, children: &[Element<'a>], position: isize, shift: isize,
+							stack_align: &str, rows: &mut Vec<StackRow<'a>>) {
+			let mut row_position = position;
+			for &child in children {
+				let child_position = row_position + int_attr(child, "position");
+				match name(child) {
+					"msgroup" => {
+						let group_children = child.children().iter().map(|&c| as_element(c)).collect::<Vec<Element>>();
+						collect_rows(context, &group_children, child_position, int_attr(child, "shift"), stack_align, rows);
+					},
+					"mscarries" => {
+						if !child.children().is_empty() {		// an empty mscarries is dropped
+							rows.push( StackRow::Carries{ element: child, cells: carry_cells(child), position: int_attr(child, "position") } );
+						}
+						continue;		// carries don't count as a row for 'shift'
+					},
+					"msline" => {
+						rows.push( StackRow::Line{ element: child, length: int_attr(child, "length").max(0), position: child_position } );
+					},
+					_ => {
+						let (element, row_children) = if name(child) == "msrow" {
+							(child, child.children().iter().map(|&c| as_element(c)).collect::<Vec<Element>>())
+						} else {
+							let msrow = create_mathml_element(&child.document(), "msrow");
+							msrow.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+							(msrow, vec![child])
+						};
+						let cells = row_children.into_iter().flat_map(|cell| split_cell(cell)).collect::<Vec<Element>>();
+						let align = match stack_align {
+							"left" => 0,
+							"center" => cells.len() / 2,
+							"right" => cells.len(),
+							_ => cells.iter()
+									.position(|&cell| name(cell) == "mn" && context.patterns.decimal_separator.is_match(as_text(cell)))
+									.unwrap_or(cells.len()),
+						};
+						rows.push( StackRow::Row{ element, cells, align: align as isize, position: child_position } );
+					},
+				}
+				row_position += shift;
+			}
+		}
+
+		/// Numbers are split into one cell per char; everything else is a single cell
+		fn split_cell(cell: Element) -> Vec<Element> {
+			if name(cell) != "mn" {
+				return vec![cell];
+			}
+			let doc = cell.document();
+			return as_text(cell).trim().chars()
+					.filter(|ch| !ch.is_whitespace())
+					.map(|ch| {
+						let mn = create_mathml_element(&doc, "mn");
+						mn.set_text(&ch.to_string());
+						mn
+					})
+					.collect();
+		}
+
+		/// Wrap the carries in 'mscarry' and set the inherited attrs on them
+		fn carry_cells(mscarries: Element) -> Vec<Element> {
+			let doc = mscarries.document();
+			return mscarries.children().iter()
+					.map(|&child| {
+						let child = as_element(child);
+						if name(child) == "none" {
+							return child;
+						}
+						let mscarry = if name(child) == "mscarry" {
+							child
+						} else {
+							let mscarry = create_mathml_element(&doc, "mscarry");
+							mscarry.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+							mscarry.append_child(child);
+							mscarry
+						};
+						for attr in ["crossout", "location"] {
+							if mscarry.attribute(attr).is_none() && let Some(value) = mscarries.attribute_value(attr) {
+								mscarry.set_attribute_value(attr, value);
+							}
+						}
+						mscarry
+					})
+					.collect();
+		}
+
+		fn row_width(row: &StackRow) -> isize {
+			return match row {
+				StackRow::Row{ cells, .. } | StackRow::Carries{ cells, .. } => cells.len() as isize,
+				StackRow::Line{ length, .. } => *length,
+			};
+		}
+
+		fn pad_cells<'a>(doc: &Document<'a>, cells: Vec<Element<'a>>, n_before: isize, n_after: isize) -> Vec<Element<'a>> {
+			let create_none = || {
+				let none = create_mathml_element(doc, "none");
+				none.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+				none
+			};
+			let mut result = Vec::with_capacity(cells.len() + (n_before + n_after).max(0) as usize);
+			result.extend( (0..n_before).map(|_| create_none()) );
+			result.extend(cells);
+			result.extend( (0..n_after).map(|_| create_none()) );
+			return result;
+		}
+
+		/// The value is limited to MAX_STACK_OFFSET (the values are added together, so they must not overflow)
+		fn int_attr(element: Element, attr: &str) -> isize {
+			const MAX_STACK_OFFSET: isize = 10_000;
+			return element.attribute_value(attr)
+					.and_then(|value| value.trim().parse::<isize>().ok())
+					.unwrap_or(0)
+					.clamp(-MAX_STACK_OFFSET, MAX_STACK_OFFSET);
+		}
+	}
+
+	/// Clean the contents of the rows of an `mstack` or `mlongdiv` (but not the row structure or the numbers that get split into digits)
+	fn clean_elementary_cells(&self, mathml: Element) {
+		match name(mathml) {
+			"msgroup" | "msrow" | "mscarries" | "mscarry" => {
+				for child in mathml.children() {
+					self.clean_elementary_cells(as_element(child));
+				}
+			},
+			"mn" | "none" | "msline" => (),
+			_ => {
+				let parent = get_parent(mathml);
+				let cleaned = self.clean_mathml(mathml).unwrap_or_else(|| {
+					let none = create_mathml_element(&mathml.document(), "none");
+					none.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+					none
+				});
+				if cleaned != mathml {
+					let new_children = parent.children().into_iter()
+							.map(|child| if child.element() == Some(mathml) {ChildOfElement::Element(cleaned)} else {child})
+							.collect::<Vec<ChildOfElement>>();
+					parent.replace_children(new_children);
+				}
+			},
+		}
+	}
+
 	fn canonicalize_mrows<'a>(&self, mathml: Element<'a>) -> Result<Element<'a>> {
 		let tag_name = name(mathml);
 		set_mathml_name(mathml, tag_name);	// add namespace
@@ -6935,4 +7227,40 @@ mod canonicalize_tests {
 	}
 
 
+
+	#[test]
+    fn mstack_grid() -> Result<()> {
+        let test_str = "<math><mstack>
+				<mscarries><mn>1</mn><none/></mscarries>
+				<mn>424</mn>
+				<msrow><mo>+</mo><mn>33</mn></msrow>
+				<msline/>
+				<mn>457</mn>
+			</mstack></math>";
+		let target_str = "<math><mstack>
+				<mscarries><none data-changed='added'/><mscarry data-changed='added'><mn>1</mn></mscarry><none/></mscarries>
+				<msrow data-changed='added'><mn>4</mn><mn>2</mn><mn>4</mn></msrow>
+				<msrow><mo>+</mo><mn>3</mn><mn>3</mn></msrow>
+				<msline length='3' position='0'/>
+				<msrow data-changed='added'><mn>4</mn><mn>5</mn><mn>7</mn></msrow>
+			</mstack></math>";
+        are_strs_canonically_equal_result(test_str, target_str, &[])
+	}
+
+	#[test]
+    fn mstack_huge_offsets() -> Result<()> {
+		// the rows are padded to the width of the grid, so a huge 'length' or 'position' must not make a huge grid
+        let test_str = "<math><mstack>
+				<mn>12</mn>
+				<msline length='100000000'/>
+				<msrow position='-100000000'><mn>3</mn></msrow>
+			</mstack></math>";
+		let target_str = "<math><mstack>
+				<msrow data-changed='added'><mn>1</mn><mn>2</mn><none data-changed='added'/><none data-changed='added'/></msrow>
+				<msline length='2' position='2'/>
+				<msrow><none data-changed='added'/><none data-changed='added'/><none data-changed='added'/><mn>3</mn></msrow>
+			</mstack></math>";
+        are_strs_canonically_equal_result(test_str, target_str, &[])
+	}
+
 }
@@ -38,7 +38,7 @@
 use sxd_document::dom::{Element, Document, ChildOfElement};
 use crate::canonicalize::*;
 use crate::pretty_print::mml_to_string;
-use crate::xpath_functions::{is_leaf, IsNode};
+use crate::xpath_functions::{is_leaf, validate_one_node, IsNode};
 use regex::Regex;
 use crate::xpath_functions::IsBracketed;
 use phf::{phf_map, phf_set};
@@ -49,6 +49,10 @@ use std::collections::HashSet;
 use std::cmp::Ordering;
 use crate::errors::*;
 use std::sync::LazyLock;
+use std::result::Result as StdResult;
+use sxd_xpath::function::Error as XPathError;
+use sxd_xpath::function::{Args, Function};
+use sxd_xpath::{context, nodeset::Node, Value};
 
 
 pub static NOT_CHEMISTRY: i32 = -10000;  // should overwhelm any positive signal
@@ -1871,6 +1875,129 @@ pub fn is_chemical_element(node: Element) -> bool {
            has_inherited_property(node, "chemical-formula");
 }
 
+/// Returns the formula as a string (e.g., "H2O", "NaCl", or "SO4^2-") so that it can be looked up in a table of compound names.
+/// Charges are written after a '^' with the number (if any) before the sign.
+/// This works for both canonical MathML and the intent tree.
+/// `None` is returned if `mathml` isn't a simple formula (e.g., it has a state, a bond, or a prescript).
+pub fn chemical_formula_key(mathml: Element) -> Option<String> {
+    let mut key = String::with_capacity(16);
+    return if append_formula_key(mathml, &mut key) {Some(key)} else {None};
+}
+
+fn append_formula_key(mathml: Element, key: &mut String) -> bool {
+    let children = mathml.children();
+    // intent tree elements (other than "chemical-formula") remember the MathML element they came from
+    let element_name = match name(mathml) {
+        "chemical-formula" => "chemical-formula",
+        element_name => mathml.attribute_value(MATHML_FROM_NAME_ATTR).unwrap_or(element_name),
+    };
+    match element_name {
+        "mi" | "mtext" | "chemical-element" => {
+            let text = token_text(mathml);
+            if !CHEMICAL_ELEMENT_ELECTRONEGATIVITY.contains_key(text) {
+                return false;
+            }
+            key.push_str(text);
+        },
+        "mn" => {
+            let text = token_text(mathml);
+            if text.is_empty() || !text.chars().all(|ch| ch.is_ascii_digit()) {
+                return false;
+            }
+            key.push_str(text);
+        },
+        "mo" | "chemical-formula-operator" | "chemical-equation-operator" => {
+            match token_text(mathml) {
+                "\u{2063}" => (),     // invisible separator between elements
+                text @ ("(" | ")" | "[" | "]") => key.push_str(text),
+                _ => return false,
+            }
+        },
+        "mrow" => {
+            return children.iter().all(|&child| append_formula_key(as_element(child), key));
+        },
+        "msub" | "msup" | "msubsup" => {
+            return append_scripts_key(element_name, &children.iter().map(|&child| as_element(child)).collect::<Vec<Element>>(), key);
+        },
+        "chemical-formula" => {
+            // the intent tree has the name of the original element as the first child
+            let children = children.iter().map(|&child| as_element(child)).collect::<Vec<Element>>();
+            if children.is_empty() {
+                return false;
+            }
+            return append_scripts_key(token_text(children[0]), &children[1..], key);
+        },
+        _ => return false,
+    }
+    return true;
+}
+
+/// The text of a token element -- this includes the intent tree's token elements (e.g., `chemical-element`) which aren't MathML leaves
+fn token_text(mathml: Element<'_>) -> &str {
+    let children = mathml.children();
+    if children.len() == 1 && let ChildOfElement::Text(t) = children[0] {
+        return t.text();
+    }
+    return "";
+}
+
+/// Append the key for a scripted element (`children` are the base and the scripts)
+fn append_scripts_key(script_name: &str, children: &[Element], key: &mut String) -> bool {
+    let (subscript, superscript) = match (script_name, children.len()) {
+        ("msub", 2) => (Some(children[1]), None),
+        ("msup", 2) => (None, Some(children[1])),
+        ("msubsup", 3) => (Some(children[1]), Some(children[2])),
+        _ => return false,
+    };
+    if !append_formula_key(children[0], key) {
+        return false;
+    }
+    if let Some(subscript) = subscript &&
+       (name(subscript) != "mn" || !append_formula_key(subscript, key)) {
+        return false;
+    }
+    if let Some(superscript) = superscript {
+        let charge = match name(superscript) {
+            "mo" => token_text(superscript).to_string(),
+            "mrow" if superscript.children().len() == 2 => {
+                let number = as_element(superscript.children()[0]);
+                let sign = as_element(superscript.children()[1]);
+                if name(number) != "mn" || name(sign) != "mo" {
+                    return false;
+                }
+                token_text(number).to_string() + token_text(sign)
+            },
+            _ => return false,
+        };
+        let charge = charge.replace('\u{2212}', "-");
+        if !(charge.ends_with('+') || charge.ends_with('-')) || !charge[..charge.len()-1].chars().all(|ch| ch.is_ascii_digit()) {
+            return false;
+        }
+        key.push('^');
+        key.push_str(&charge);
+    }
+    return true;
+}
+
+/// `ChemicalFormula(node)` -- returns the formula as a string (e.g, "H2O") that can be used to look up the name of a compound.
+/// An empty string is returned if the node isn't a simple chemical formula. See [`chemical_formula_key`].
+pub struct ChemicalFormula;
+impl Function for ChemicalFormula {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> StdResult<Value<'d>, XPathError>
+    {
+        let mut args = Args(args);
+        args.exactly(1)?;
+        let node = validate_one_node(args.pop_nodeset()?, "ChemicalFormula")?;
+        if let Node::Element(e) = node {
+            return Ok( Value::String(chemical_formula_key(e).unwrap_or_default()) );
+        }
+        return Ok( Value::String("".to_string()) );
+    }
+}
+
 
 #[cfg(test)]
 mod chem_tests {
@@ -1911,6 +2038,21 @@ mod chem_tests {
         assert!( parse_mathml_string(test, |mathml| has_noble_element( &collect_elements(mathml).unwrap() )) );
     }
 
+    #[test]
+    fn test_chemical_formula_key() {
+        // mathml test strings need to be canonical MathML since we aren't testing canonicalize()
+        let test = "<mrow> <msub><mi>H</mi><mn>2</mn></msub> <mo>&#x2063;</mo> <mi>O</mi> </mrow>";
+        assert!( parse_mathml_string(test, |mathml| chemical_formula_key(mathml) == Some("H2O".to_string())) );
+        let test = "<msup> <mrow><mi>S</mi><mo>&#x2063;</mo><msub><mi>O</mi><mn>4</mn></msub></mrow> <mrow><mn>2</mn><mo>&#x2212;</mo></mrow> </msup>";
+        assert!( parse_mathml_string(test, |mathml| chemical_formula_key(mathml) == Some("SO4^2-".to_string())) );
+        let test = "<mrow> <mi>Ca</mi> <mo>&#x2063;</mo> <msub><mrow><mo>(</mo><mrow><mi>O</mi><mo>&#x2063;</mo><mi>H</mi></mrow><mo>)</mo></mrow><mn>2</mn></msub> </mrow>";
+        assert!( parse_mathml_string(test, |mathml| chemical_formula_key(mathml) == Some("Ca(OH)2".to_string())) );
+        let test = "<mrow> <mi>H</mi> <mo>=</mo> <mi>O</mi> </mrow>";
+        assert!( parse_mathml_string(test, |mathml| chemical_formula_key(mathml).is_none()) );
+        let test = "<mrow> <mi>x</mi> <mo>&#x2063;</mo> <mi>O</mi> </mrow>";
+        assert!( parse_mathml_string(test, |mathml| chemical_formula_key(mathml).is_none()) );
+    }
+
     #[test]
     fn test_alphabetical_order() {
         // mathml test strings need to be canonical MathML since we aren't testing canonicalize()
@@ -0,0 +1,1012 @@
+#![allow(clippy::needless_return)]
+//! Convert Content MathML into presentation MathML.
+//!
+//! The rest of MathCAT only understands presentation MathML, so Content MathML (`apply`, `ci`, `cn`, `csymbol`, ...)
+//! is turned into presentation MathML before canonicalization starts.
+//! Content MathML says what the notation means, so the generated presentation is given an `intent` wherever
+//! the presentation alone would be ambiguous (e.g., `|x|` or `(a,b)`).
+//!
+//! A `semantics` element might have a presentation child along with a Content MathML `annotation-xml`.
+//! In that case, the author's presentation is kept but the intents from the converted annotation are copied onto it
+//! where the structures match. That way the author's meaning is used rather than a guess based on the notation.
+//!
+//! Only the commonly used parts of Content MathML are handled (see <https://www.w3.org/TR/mathml4/#contm>).
+//! Unknown heads are spoken as function applications.
+
+use sxd_document::dom::{Element, Document, ChildOfElement};
+use crate::canonicalize::{name, as_text, create_mathml_element};
+use crate::interface::copy_mathml;
+use anyhow::{Result, bail};
+use phf::{phf_map, phf_set};
+
+/// The Content MathML elements that can appear in place of a presentation element
+static CONTENT_ELEMENTS: phf::Set<&str> = phf_set! {
+	"apply", "bind", "ci", "cn", "csymbol", "cs", "cerror", "set", "list", "vector", "matrix", "interval", "piecewise", "lambda",
+};
+
+/// Content MathML constants and symbols that are written as a single (presentation) character
+static CONSTANTS: phf::Map<&str, &str> = phf_map! {
+	"pi" => "π", "exponentiale" => "ⅇ", "imaginaryi" => "ⅈ", "infinity" => "∞", "eulergamma" => "γ",
+	"emptyset" => "∅", "notanumber" => "NaN", "true" => "true", "false" => "false",
+	"naturalnumbers" => "ℕ", "integers" => "ℤ", "rationals" => "ℚ", "reals" => "ℝ", "complexes" => "ℂ", "primes" => "ℙ",
+};
+
+/// Infix operators: the operator and its precedence (higher binds tighter)
+static INFIX_OPERATORS: phf::Map<&str, (&str, usize)> = phf_map! {
+	"implies" => ("⇒", 1), "equivalent" => ("≡", 2),
+	"eq" => ("=", 2), "neq" => ("≠", 2), "lt" => ("<", 2), "gt" => (">", 2), "leq" => ("≤", 2), "geq" => ("≥", 2),
+	"approx" => ("≈", 2), "factorof" => ("∣", 2), "tendsto" => ("→", 2),
+	"in" => ("∈", 2), "notin" => ("∉", 2), "subset" => ("⊆", 2), "prsubset" => ("⊂", 2),
+	"notsubset" => ("⊈", 2), "notprsubset" => ("⊄", 2),
+	"or" => ("∨", 3), "xor" => ("⊻", 3), "and" => ("∧", 4),
+	"union" => ("∪", 5), "setdiff" => ("∖", 5), "plus" => ("+", 5), "minus" => ("−", 5),
+	"intersect" => ("∩", 6), "cartesianproduct" => ("×", 6), "times" => ("\u{2062}", 6),
+	"vectorproduct" => ("×", 6), "scalarproduct" => ("⋅", 6), "outerproduct" => ("⊗", 6),
+	"compose" => ("∘", 6), "rem" => ("mod", 6),
+};
+
+/// Functions written as a name followed by the argument(s). The value is the name that is displayed.
+static FUNCTIONS: phf::Map<&str, &str> = phf_map! {
+	"sin" => "sin", "cos" => "cos", "tan" => "tan", "sec" => "sec", "csc" => "csc", "cot" => "cot",
+	"sinh" => "sinh", "cosh" => "cosh", "tanh" => "tanh", "sech" => "sech", "csch" => "csch", "coth" => "coth",
+	"arcsin" => "arcsin", "arccos" => "arccos", "arctan" => "arctan", "arcsec" => "arcsec", "arccsc" => "arccsc", "arccot" => "arccot",
+	"arcsinh" => "arcsinh", "arccosh" => "arccosh", "arctanh" => "arctanh",
+	"arcsech" => "arcsech", "arccsch" => "arccsch", "arccoth" => "arccoth",
+	"ln" => "ln", "log" => "log", "exp" => "exp", "gcd" => "gcd", "lcm" => "lcm", "max" => "max", "min" => "min",
+	"arg" => "arg", "real" => "Re", "imaginary" => "Im", "determinant" => "det",
+	"mean" => "mean", "median" => "median", "mode" => "mode", "sdev" => "σ", "variance" => "var",
+	"divergence" => "div", "grad" => "grad", "curl" => "curl", "laplacian" => "∇²",
+	"not" => "¬", "quotient" => "quotient", "numerator" => "numerator", "denominator" => "denominator",
+};
+
+/// Elements of `apply` and `bind` that qualify the operator rather than being an argument
+static QUALIFIERS: phf::Set<&str> = phf_set! {
+	"bvar", "lowlimit", "uplimit", "degree", "logbase", "condition", "domainofapplication", "momentabout",
+};
+
+/// Precedence used for anything that never needs parentheses (leaves, fractions, function calls, ...)
+const ATOMIC: usize = 10;
+
+/// Convert any Content MathML inside of 'mathml' into presentation MathML.
+/// Content MathML inside of an `annotation-xml` is left alone, but is used to set intents on the presentation
+///   it annotates.
+pub fn convert_content_mathml(mathml: Element) -> Result<()> {
+	return convert_children(mathml, 0);
+}
+
+fn convert_children(mathml: Element, depth: usize) -> Result<()> {
+	if depth > crate::interface::MAX_DEPTH {
+		bail!("MathML is too deeply nested to process");
+	}
+	if name(mathml) == "semantics" {
+		return convert_semantics(mathml, depth);
+	}
+	for child in mathml.children() {
+		if let ChildOfElement::Element(child) = child {
+			if CONTENT_ELEMENTS.contains(name(child)) || is_empty_content_element(child) {
+				let presentation = Converter::new(child.document()).convert(child, depth)?;
+				replace_element(child, presentation);
+			} else {
+				convert_children(child, depth + 1)?;
+			}
+		}
+	}
+	return Ok( () );
+}
+
+/// Convert the presentation child of 'semantics' and, if there is a Content MathML annotation, use it for intents
+fn convert_semantics(semantics: Element, depth: usize) -> Result<()> {
+	let children = semantics.children();
+	let Some(&ChildOfElement::Element(first_child)) = children.first() else {
+		return Ok( () );
+	};
+	let first_child_name = name(first_child);
+	if first_child_name == "annotation" || first_child_name == "annotation-xml" {
+		// no presentation child -- use the Content MathML (if any) as the presentation
+		if let Some(content) = find_content_annotation(semantics) {
+			let presentation = Converter::new(semantics.document()).convert(copy_mathml(content), depth)?;
+			let mut new_children = vec![ChildOfElement::Element(presentation)];
+			new_children.extend(children);
+			semantics.replace_children(new_children);
+		}
+		return Ok( () );
+	}
+
+	if CONTENT_ELEMENTS.contains(first_child_name) || is_empty_content_element(first_child) {
+		let presentation = Converter::new(semantics.document()).convert(first_child, depth)?;
+		replace_element(first_child, presentation);
+		return Ok( () );
+	}
+
+	convert_children(first_child, depth + 1)?;
+	if let Some(content) = find_content_annotation(semantics) {
+		let generated = Converter::new(semantics.document()).convert(copy_mathml(content), depth)?;
+		copy_intents(generated, first_child);
+	}
+	return Ok( () );
+}
+
+/// Return the Content MathML inside of an `annotation-xml` child (if there is one)
+fn find_content_annotation(semantics: Element) -> Option<Element> {
+	return semantics.children().iter()
+		.filter_map(|child| child.element())
+		.filter(|&child| name(child) == "annotation-xml" &&
+			matches!(child.attribute_value("encoding"), Some("MathML-Content" | "application/mathml-content+xml")))
+		.find_map(|annotation| annotation.children().iter().find_map(|child| child.element()));
+}
+
+/// Copy the `intent` and `arg` attributes from 'generated' onto 'presentation' (the author's notation).
+/// This only happens while the two trees have the same shape; as soon as they differ, nothing more is copied for that branch.
+/// Anything the author wrote is left alone.
+fn copy_intents(generated: Element, presentation: Element) {
+	let generated_children = generated.children();
+	let presentation_children = presentation.children();
+	if name(generated) != name(presentation) || generated_children.len() != presentation_children.len() {
+		return;
+	}
+	if presentation.attribute_value("intent").is_none() &&
+	   let Some(intent) = generated.attribute_value("intent") {
+		presentation.set_attribute_value("intent", intent);
+	}
+	if presentation.attribute_value("arg").is_none() &&
+	   let Some(arg) = generated.attribute_value("arg") {
+		presentation.set_attribute_value("arg", arg);
+	}
+	for (generated_child, presentation_child) in generated_children.iter().zip(presentation_children.iter()) {
+		if let (Some(generated_child), Some(presentation_child)) = (generated_child.element(), presentation_child.element()) {
+			copy_intents(generated_child, presentation_child);
+		}
+	}
+}
+
+/// True if this is an empty element like `<pi/>` that stands for a constant
+fn is_empty_content_element(element: Element) -> bool {
+	return element.children().is_empty() && CONSTANTS.contains_key(name(element));
+}
+
+/// The text of the parts of 'cn' (they are separated by `sep` elements)
+fn cn_parts(cn: Element) -> Vec<String> {
+	let mut parts = vec![String::new()];
+	for child in cn.children() {
+		match child {
+			ChildOfElement::Text(text) => parts.last_mut().unwrap().push_str(text.text()),
+			ChildOfElement::Element(sep) if name(sep) == "sep" => parts.push(String::new()),
+			_ => (),
+		}
+	}
+	return parts.into_iter().map(|part| part.trim().to_string()).collect();
+}
+
+/// Split a number such as "1.5e-3" into its mantissa and exponent
+fn split_e_notation(number: &str) -> Option<(&str, &str)> {
+	let (mantissa, exponent) = number.split_once(['e', 'E'])?;
+	return if mantissa.parse::<f64>().is_ok() && exponent.parse::<i32>().is_ok() {Some( (mantissa, exponent) )} else {None};
+}
+
+/// Replace 'old' with 'new' in the parent of 'old'
+fn replace_element<'a>(old: Element<'a>, new: Element<'a>) {
+	let parent = old.parent().unwrap().element().unwrap();
+	let mut children = parent.children();
+	if let Some(i) = children.iter().position(|&child| child.element() == Some(old)) {
+		children[i] = ChildOfElement::Element(new);
+		parent.replace_children(children);
+	}
+}
+
+/// The parts of an `apply` or `bind`
+struct Application<'a> {
+	head: Element<'a>,
+	operator: String,
+	qualifiers: Vec<Element<'a>>,
+	args: Vec<Element<'a>>,
+}
+
+impl<'a> Application<'a> {
+	fn new(apply: Element<'a>) -> Result<Application<'a>> {
+		let children: Vec<Element> = apply.children().iter().filter_map(|child| child.element()).collect();
+		let Some((&head, rest)) = children.split_first() else {
+			bail!("'{}' element must have at least one child", name(apply));
+		};
+		let operator = match name(head) {
+			"csymbol" => as_text(head).trim().to_string(),
+			head_name if head.children().is_empty() => head_name.to_string(),
+			_ => String::new(),
+		};
+		let (qualifiers, args) = rest.iter().partition(|&&child| QUALIFIERS.contains(name(child)));
+		return Ok( Application { head, operator, qualifiers, args } );
+	}
+
+	/// The (first) child of the named qualifier, if present
+	fn qualifier(&self, qualifier_name: &str) -> Option<Element<'a>> {
+		return self.qualifiers.iter()
+			.find(|&&q| name(q) == qualifier_name)
+			.and_then(|q| q.children().iter().find_map(|child| child.element()));
+	}
+
+	/// The children of all the `bvar`s (ignoring any `degree` in them)
+	fn bvars(&self) -> Vec<Element<'a>> {
+		return self.qualifiers.iter()
+			.filter(|&&q| name(q) == "bvar")
+			.filter_map(|q| q.children().iter().filter_map(|child| child.element()).find(|&child| name(child) != "degree"))
+			.collect();
+	}
+
+	/// The degree given on the first `bvar` (e.g., for higher derivatives)
+	fn bvar_degree(&self) -> Option<Element<'a>> {
+		return self.qualifiers.iter()
+			.find(|&&q| name(q) == "bvar")
+			.and_then(|bvar| bvar.children().iter().filter_map(|child| child.element()).find(|&child| name(child) == "degree"))
+			.and_then(|degree| degree.children().iter().find_map(|child| child.element()));
+	}
+}
+
+struct Converter<'a> {
+	doc: Document<'a>,
+	n_args: usize,			// used to generate unique arg names
+}
+
+impl<'a> Converter<'a> {
+	fn new(doc: Document<'a>) -> Converter<'a> {
+		return Converter { doc, n_args: 0 };
+	}
+
+	fn leaf(&self, element_name: &str, text: &str) -> Element<'a> {
+		let leaf = create_mathml_element(&self.doc, element_name);
+		leaf.set_text(text);
+		return leaf;
+	}
+
+	fn node(&self, element_name: &str, children: Vec<Element<'a>>) -> Element<'a> {
+		let node = create_mathml_element(&self.doc, element_name);
+		node.append_children(children);
+		return node;
+	}
+
+	fn mo(&self, text: &str) -> Element<'a> {
+		return self.leaf("mo", text);
+	}
+
+	/// Give 'element' an arg name and return the reference to it (e.g., "$a1")
+	fn arg(&mut self, element: Element<'a>) -> String {
+		self.n_args += 1;
+		let arg_name = format!("a{}", self.n_args);
+		element.set_attribute_value("arg", &arg_name);
+		return format!("${arg_name}");
+	}
+
+	fn with_intent(&self, element: Element<'a>, intent: String) -> Element<'a> {
+		element.set_attribute_value("intent", &intent);
+		return element;
+	}
+
+	fn parens(&self, element: Element<'a>) -> Element<'a> {
+		return self.node("mrow", vec![self.mo("("), element, self.mo(")")]);
+	}
+
+	/// Convert a child and wrap it in parens if its precedence is less than 'precedence'
+	fn operand(&mut self, element: Element<'a>, precedence: usize, depth: usize) -> Result<Element<'a>> {
+		let needs_parens = Converter::precedence(element) < precedence;
+		let converted = self.convert(element, depth + 1)?;
+		return Ok( if needs_parens {self.parens(converted)} else {converted} );
+	}
+
+	/// The precedence of a Content MathML element
+	fn precedence(element: Element) -> usize {
+		if name(element) == "cn" {
+			let parts = cn_parts(element);
+			return match element.attribute_value("type") {
+				Some("complex-cartesian") if parts.len() == 2 => 5,
+				Some("complex-polar" | "e-notation") if parts.len() == 2 => 6,
+				Some("real" | "double") | None if parts.len() == 1 && split_e_notation(&parts[0]).is_some() => 6,
+				_ if parts[0].starts_with('-') => 7,
+				_ => ATOMIC,
+			};
+		}
+		if name(element) != "apply" {
+			return ATOMIC;
+		}
+		let Ok(apply) = Application::new(element) else {
+			return ATOMIC;
+		};
+		return match apply.operator.as_str() {
+			"minus" if apply.args.len() == 1 => 7,
+			"power" | "transpose" | "inverse" => 8,
+			"factorial" => 9,
+			"sum" | "product" | "int" | "limit" | "diff" | "partialdiff" | "forall" | "exists" => 6,
+			op => INFIX_OPERATORS.get(op).map_or(ATOMIC, |&(_, precedence)| precedence),
+		};
+	}
+
+	/// Convert Content MathML 'element' into presentation MathML (the result is a new tree)
+	fn convert(&mut self, element: Element<'a>, depth: usize) -> Result<Element<'a>> {
+		if depth > crate::interface::MAX_DEPTH {
+			bail!("MathML is too deeply nested to process");
+		}
+		let element_name = name(element);
+		if let Some(&text) = CONSTANTS.get(element_name) {
+			let leaf = self.leaf(if text.chars().count() > 1 {"mtext"} else {"mi"}, text);
+			return Ok(leaf);
+		}
+		return match element_name {
+			"cn" => self.convert_cn(element),
+			"ci" => {
+				if let Some(presentation) = element.children().iter().find_map(|child| child.element()) {
+					// e.g., <ci><msub><mi>x</mi><mn>1</mn></msub></ci>
+					Ok( copy_mathml(presentation) )
+				} else {
+					Ok( self.leaf("mi", as_text(element).trim()) )
+				}
+			},
+			"csymbol" => {
+				let symbol = as_text(element).trim();
+				Ok( match CONSTANTS.get(symbol) {
+					Some(&text) => self.leaf("mi", text),
+					None => self.leaf("mi", symbol),
+				})
+			},
+			"cs" => Ok( self.leaf("ms", as_text(element)) ),
+			"cerror" => Ok( self.node("merror", vec![self.leaf("mtext", "error")]) ),
+			"apply" | "bind" => self.convert_apply(element, depth),
+			"set" | "list" => self.convert_set_or_list(element, depth),
+			"interval" => self.convert_interval(element, depth),
+			"vector" => {
+				let rows = element.children().iter()
+					.filter_map(|child| child.element())
+					.map(|child| Ok( vec![self.convert(child, depth + 1)?] ))
+					.collect::<Result<Vec<Vec<Element>>>>()?;
+				Ok( self.node("mrow", vec![self.mo("("), self.table(rows), self.mo(")")]) )
+			},
+			"matrix" => {
+				let mut rows = vec![];
+				for row in element.children().iter().filter_map(|child| child.element()) {
+					let cells = row.children().iter()
+						.filter_map(|child| child.element())
+						.map(|cell| self.convert(cell, depth + 1))
+						.collect::<Result<Vec<Element>>>()?;
+					rows.push(cells);
+				}
+				Ok( self.node("mrow", vec![self.mo("("), self.table(rows), self.mo(")")]) )
+			},
+			"piecewise" => self.convert_piecewise(element, depth),
+			"lambda" => {
+				let lambda = Application::new(element)?;
+				let mut children = vec![self.mo("λ")];
+				for bvar in lambda.bvars() {
+					children.push(self.convert(bvar, depth + 1)?);
+				}
+				children.push(self.mo("."));
+				// 'Application' treats the first child as the head -- for lambda, that is a bvar
+				for arg in std::iter::once(lambda.head).chain(lambda.args).filter(|&child| name(child) != "bvar") {
+					children.push(self.convert(arg, depth + 1)?);
+				}
+				Ok( self.node("mrow", children) )
+			},
+			_ => {
+				if CONTENT_ELEMENTS.contains(element_name) {
+					bail!("Content MathML element '{}' is not supported", element_name);
+				}
+				// presentation MathML (e.g., inside of 'ci') -- keep it
+				Ok( copy_mathml(element) )
+			},
+		};
+	}
+
+	/// Convert a number. The `type` says how the parts separated by `sep` go together (see MathML 4, section 4.2.1.3).
+	fn convert_cn(&self, cn: Element<'a>) -> Result<Element<'a>> {
+		let parts = cn_parts(cn);
+		let parts: Vec<&str> = parts.iter().map(|part| part.as_str()).collect();
+		let cn_type = cn.attribute_value("type").unwrap_or("real");
+		return Ok( match (cn_type, parts.as_slice()) {
+			("rational", [numerator, denominator]) => self.node("mfrac", vec![self.number(numerator), self.number(denominator)]),
+			("complex-cartesian", [real, imaginary]) => {
+				let (op, imaginary) = match imaginary.strip_prefix('-') {
+					Some(imaginary) => ("−", imaginary),
+					None => ("+", *imaginary),
+				};
+				let imaginary = self.node("mrow", vec![self.leaf("mn", imaginary), self.mo("\u{2062}"), self.leaf("mi", "ⅈ")]);
+				self.node("mrow", vec![self.number(real), self.mo(op), imaginary])
+			},
+			("complex-polar", [magnitude, angle]) => {
+				let exponent = self.node("mrow", vec![self.leaf("mi", "ⅈ"), self.mo("\u{2062}"), self.number(angle)]);
+				let exponential = self.node("msup", vec![self.leaf("mi", "ⅇ"), exponent]);
+				self.node("mrow", vec![self.number(magnitude), self.mo("\u{2062}"), exponential])
+			},
+			("e-notation", [mantissa, exponent]) => self.e_notation(mantissa, exponent),
+			("constant", [constant]) => self.leaf("mi", CONSTANTS.get(constant).unwrap_or(constant)),
+			("double", ["INF"]) => self.leaf("mi", "∞"),
+			("double", ["-INF"]) => self.node("mrow", vec![self.mo("−"), self.leaf("mi", "∞")]),
+			("double", ["NaN"]) => self.leaf("mtext", "NaN"),
+			("real" | "double", [number]) if split_e_notation(number).is_some() => {
+				let (mantissa, exponent) = split_e_notation(number).unwrap();
+				self.e_notation(mantissa, exponent)
+			},
+			("integer" | "real", [number]) if cn.attribute_value("base").is_some_and(|base| base.trim() != "10") => {
+				let base = cn.attribute_value("base").unwrap().trim();
+				self.node("msub", vec![self.leaf("mn", number), self.leaf("mn", base)])
+			},
+			("integer" | "real" | "double" | "hexdouble", [number]) => self.number(number),
+			_ => bail!("'cn' of type '{}' can't have {} part(s) separated by 'sep'", cn_type, parts.len()),
+		});
+	}
+
+	/// A number that might be negative (e.g., "-2" is −2)
+	fn number(&self, text: &str) -> Element<'a> {
+		return match text.strip_prefix('-') {
+			Some(negative) => self.node("mrow", vec![self.mo("−"), self.leaf("mn", negative)]),
+			None => self.leaf("mn", text),
+		};
+	}
+
+	/// mantissa × 10^exponent
+	fn e_notation(&self, mantissa: &str, exponent: &str) -> Element<'a> {
+		let power = self.node("msup", vec![self.leaf("mn", "10"), self.number(exponent)]);
+		return self.node("mrow", vec![self.number(mantissa), self.mo("×"), power]);
+	}
+
+	fn convert_apply(&mut self, element: Element<'a>, depth: usize) -> Result<Element<'a>> {
+		let apply = Application::new(element)?;
+		let op = apply.operator.clone();
+		let args = apply.args.clone();
+		if let Some((min, max)) = Converter::arity(&op) && !(min..=max).contains(&args.len()) {
+			bail!("'{}' can't be applied to {} argument(s)", op, args.len());
+		}
+		match op.as_str() {
+			"minus" if args.len() == 1 => {
+				let operand = self.operand(args[0], 8, depth)?;
+				return Ok( self.node("mrow", vec![self.mo("−"), operand]) );
+			},
+			"minus" if args.len() == 2 => {
+				let left = self.operand(args[0], 5, depth)?;
+				let right = self.operand(args[1], 6, depth)?;
+				return Ok( self.node("mrow", vec![left, self.mo("−"), right]) );
+			},
+			"plus" => {
+				let mut children = vec![];
+				for (i, &arg) in args.iter().enumerate() {
+					// a + (-b) is written as a - b
+					if i > 0 && let Some(negated) = Converter::negated_operand(arg) {
+						children.push(self.mo("−"));
+						children.push(self.operand(negated, 6, depth)?);
+						continue;
+					}
+					if i > 0 {
+						children.push(self.mo("+"));
+					}
+					children.push(self.operand(arg, if i == 0 {5} else {6}, depth)?);
+				}
+				return Ok( self.node("mrow", children) );
+			},
+			"times" => {
+				let mut children = vec![];
+				for (i, &arg) in args.iter().enumerate() {
+					if i > 0 {
+						// numbers next to each other need a visible times sign
+						let times = if name(arg) == "cn" && name(args[i-1]) == "cn" {"×"} else {"\u{2062}"};
+						children.push(self.mo(times));
+					}
+					children.push(self.operand(arg, 6, depth)?);
+				}
+				return Ok( self.node("mrow", children) );
+			},
+			"divide" if args.len() == 2 => {
+				let numerator = self.convert(args[0], depth + 1)?;
+				let denominator = self.convert(args[1], depth + 1)?;
+				return Ok( self.node("mfrac", vec![numerator, denominator]) );
+			},
+			"power" if args.len() == 2 => {
+				let base = self.operand(args[0], ATOMIC, depth)?;
+				let exponent = self.convert(args[1], depth + 1)?;
+				return Ok( self.node("msup", vec![base, exponent]) );
+			},
+			"root" if args.len() == 1 => {
+				let radicand = self.convert(args[0], depth + 1)?;
+				return Ok( match apply.qualifier("degree") {
+					Some(degree) if !(name(degree) == "cn" && as_text(degree).trim() == "2") => {
+						let index = self.convert(degree, depth + 1)?;
+						self.node("mroot", vec![radicand, index])
+					},
+					_ => self.node("msqrt", vec![radicand]),
+				});
+			},
+			"abs" | "floor" | "ceiling" | "card" if args.len() == 1 => {
+				let (open, close, intent) = match op.as_str() {
+					"abs" => ("|", "|", "absolute-value"),
+					"floor" => ("⌊", "⌋", "floor"),
+					"ceiling" => ("⌈", "⌉", "ceiling"),
+					_ => ("|", "|", "cardinality"),
+				};
+				let operand = self.convert(args[0], depth + 1)?;
+				let arg = self.arg(operand);
+				let mrow = self.node("mrow", vec![self.mo(open), operand, self.mo(close)]);
+				return Ok( self.with_intent(mrow, format!("{intent}({arg})")) );
+			},
+			"factorial" if args.len() == 1 => {
+				let operand = self.operand(args[0], ATOMIC, depth)?;
+				let arg = self.arg(operand);
+				let mrow = self.node("mrow", vec![operand, self.mo("!")]);
+				return Ok( self.with_intent(mrow, format!("factorial({arg})")) );
+			},
+			"transpose" | "inverse" if args.len() == 1 => {
+				let operand = self.operand(args[0], ATOMIC, depth)?;
+				let arg = self.arg(operand);
+				let script = if op == "transpose" {
+					self.leaf("mi", "T")
+				} else {
+					self.node("mrow", vec![self.mo("−"), self.leaf("mn", "1")])
+				};
+				let msup = self.node("msup", vec![operand, script]);
+				return Ok( self.with_intent(msup, format!("{op}({arg})")) );
+			},
+			"conjugate" if args.len() == 1 => {
+				let operand = self.convert(args[0], depth + 1)?;
+				let arg = self.arg(operand);
+				let mover = self.node("mover", vec![operand, self.mo("¯")]);
+				return Ok( self.with_intent(mover, format!("complex-conjugate({arg})")) );
+			},
+			"binomial" if args.len() == 2 => {
+				let n = self.convert(args[0], depth + 1)?;
+				let k = self.convert(args[1], depth + 1)?;
+				let (n_arg, k_arg) = (self.arg(n), self.arg(k));
+				let mfrac = self.node("mfrac", vec![n, k]);
+				mfrac.set_attribute_value("linethickness", "0");
+				let mrow = self.node("mrow", vec![self.mo("("), mfrac, self.mo(")")]);
+				return Ok( self.with_intent(mrow, format!("binomial({n_arg},{k_arg})")) );
+			},
+			"log" if apply.qualifier("logbase").is_some() => {
+				let base = self.convert(apply.qualifier("logbase").unwrap(), depth + 1)?;
+				let log = self.node("msub", vec![self.leaf("mi", "log"), base]);
+				return self.function_application(log, &args, depth);
+			},
+			"exp" if args.len() == 1 => {
+				let exponent = self.convert(args[0], depth + 1)?;
+				return Ok( self.node("msup", vec![self.leaf("mi", "ⅇ"), exponent]) );
+			},
+			"diff" | "partialdiff" => return self.convert_derivative(&apply, depth),
+			"int" => return self.convert_integral(&apply, depth),
+			"sum" | "product" => return self.convert_large_op(&apply, if op == "sum" {"∑"} else {"∏"}, depth),
+			"limit" => return self.convert_limit(&apply, depth),
+			"forall" | "exists" => {
+				let mut children = vec![self.mo(if op == "forall" {"∀"} else {"∃"})];
+				for bvar in apply.bvars() {
+					children.push(self.convert(bvar, depth + 1)?);
+				}
+				if let Some(condition) = apply.qualifier("condition") {
+					children.push(self.mo(","));
+					children.push(self.convert(condition, depth + 1)?);
+				}
+				if !args.is_empty() {
+					children.push(self.mo(":"));
+					for &arg in &args {
+						children.push(self.convert(arg, depth + 1)?);
+					}
+				}
+				return Ok( self.node("mrow", children) );
+			},
+			_ => (),
+		};
+
+		if let Some(&(mo, precedence)) = INFIX_OPERATORS.get(op.as_str()) {
+			let mut children = vec![];
+			for (i, &arg) in args.iter().enumerate() {
+				if i > 0 {
+					children.push(self.mo(mo));
+				}
+				children.push(self.operand(arg, precedence + 1, depth)?);
+			}
+			return Ok( self.node("mrow", children) );
+		}
+
+		let function = match FUNCTIONS.get(op.as_str()) {
+			Some(&function_name) => self.leaf("mi", function_name),
+			None if name(apply.head) == "csymbol" || op.is_empty() => self.convert(apply.head, depth + 1)?,
+			None => self.leaf("mi", &op),
+		};
+		return self.function_application(function, &args, depth);
+	}
+
+	/// The smallest and largest number of arguments that the operator 'op' can have (None if it isn't checked)
+	fn arity(op: &str) -> Option<(usize, usize)> {
+		return match op {
+			"minus" => Some( (1, 2) ),
+			"divide" | "power" | "binomial" => Some( (2, 2) ),
+			"root" | "abs" | "floor" | "ceiling" | "card" | "factorial" | "transpose" | "inverse" | "conjugate" => Some( (1, 1) ),
+			_ if INFIX_OPERATORS.contains_key(op) => Some( (1, usize::MAX) ),
+			_ => None,
+		};
+	}
+
+	/// If 'element' is a unary minus, return its operand
+	fn negated_operand(element: Element<'a>) -> Option<Element<'a>> {
+		if name(element) != "apply" {
+			return None;
+		}
+		let apply = Application::new(element).ok()?;
+		return if apply.operator == "minus" && apply.args.len() == 1 {Some(apply.args[0])} else {None};
+	}
+
+	/// f⁡(a, b, ...) -- the parens are left off for a single leaf argument of a function like 'sin'
+	fn function_application(&mut self, function: Element<'a>, args: &[Element<'a>], depth: usize) -> Result<Element<'a>> {
+		let is_named_function = name(function) != "mi" || as_text(function).chars().count() > 1;
+		let mut converted_args = vec![];
+		for (i, &arg) in args.iter().enumerate() {
+			if i > 0 {
+				converted_args.push(self.mo(","));
+			}
+			converted_args.push(self.convert(arg, depth + 1)?);
+		}
+		let argument = if args.len() == 1 && is_named_function && Converter::is_simple(args[0]) {
+			converted_args.pop().unwrap()
+		} else if converted_args.len() == 1 {
+			self.parens(converted_args.pop().unwrap())
+		} else {
+			self.parens(self.node("mrow", converted_args))
+		};
+		return Ok( self.node("mrow", vec![function, self.mo("\u{2061}"), argument]) );
+	}
+
+	/// True if 'element' is a leaf that doesn't need parens as a function argument
+	fn is_simple(element: Element) -> bool {
+		return matches!(name(element), "ci" | "csymbol") || is_empty_content_element(element) ||
+			   (name(element) == "cn" && Converter::precedence(element) == ATOMIC);
+	}
+
+	/// Convert the bounds given by lowlimit/uplimit, an interval, or a condition
+	fn limits(&mut self, apply: &Application<'a>, with_bvar: bool, depth: usize) -> Result<(Option<Element<'a>>, Option<Element<'a>>)> {
+		let bvar = apply.bvars().first().copied();
+		let (lower, upper) = if let Some(interval) = apply.qualifier("domainofapplication").filter(|&d| name(d) == "interval") {
+			let bounds: Vec<Element> = interval.children().iter().filter_map(|child| child.element()).collect();
+			(bounds.first().copied(), bounds.get(1).copied())
+		} else {
+			(apply.qualifier("lowlimit"), apply.qualifier("uplimit"))
+		};
+		let lower = match (lower, apply.qualifier("condition").or(apply.qualifier("domainofapplication"))) {
+			(Some(lower), _) => {
+				let lower = self.convert(lower, depth + 1)?;
+				match bvar {
+					Some(bvar) if with_bvar => {
+						let bvar = self.convert(bvar, depth + 1)?;
+						Some( self.node("mrow", vec![bvar, self.mo("="), lower]) )
+					},
+					_ => Some(lower),
+				}
+			},
+			(None, Some(condition)) => Some( self.convert(condition, depth + 1)? ),
+			(None, None) => None,
+		};
+		let upper = match upper {
+			Some(upper) => Some( self.convert(upper, depth + 1)? ),
+			None => None,
+		};
+		return Ok( (lower, upper) );
+	}
+
+	/// Add the lower and upper limits to 'op' (as under/over scripts or as sub/superscripts)
+	fn scripted(&self, op: Element<'a>, lower: Option<Element<'a>>, upper: Option<Element<'a>>, under_over: bool) -> Element<'a> {
+		let (both, only_lower, only_upper) = if under_over {("munderover", "munder", "mover")} else {("msubsup", "msub", "msup")};
+		return match (lower, upper) {
+			(Some(lower), Some(upper)) => self.node(both, vec![op, lower, upper]),
+			(Some(lower), None) => self.node(only_lower, vec![op, lower]),
+			(None, Some(upper)) => self.node(only_upper, vec![op, upper]),
+			(None, None) => op,
+		};
+	}
+
+	fn convert_large_op(&mut self, apply: &Application<'a>, op: &str, depth: usize) -> Result<Element<'a>> {
+		let (lower, upper) = self.limits(apply, true, depth)?;
+		let large_op = self.scripted(self.mo(op), lower, upper, true);
+		let mut children = vec![large_op];
+		for &arg in &apply.args {
+			children.push(self.operand(arg, 6, depth)?);
+		}
+		return Ok( self.node("mrow", children) );
+	}
+
+	fn convert_integral(&mut self, apply: &Application<'a>, depth: usize) -> Result<Element<'a>> {
+		let (lower, upper) = self.limits(apply, false, depth)?;
+		let integral = self.scripted(self.mo("∫"), lower, upper, false);
+		let mut children = vec![integral];
+		for &arg in &apply.args {
+			children.push(self.operand(arg, 6, depth)?);
+		}
+		for bvar in apply.bvars() {
+			let bvar = self.convert(bvar, depth + 1)?;
+			children.push(self.node("mrow", vec![self.leaf("mi", "d"), bvar]));
+		}
+		return Ok( self.node("mrow", children) );
+	}
+
+	fn convert_limit(&mut self, apply: &Application<'a>, depth: usize) -> Result<Element<'a>> {
+		let under = match (apply.bvars().first(), apply.qualifier("lowlimit")) {
+			(Some(&bvar), Some(lowlimit)) => {
+				let bvar = self.convert(bvar, depth + 1)?;
+				let lowlimit = self.convert(lowlimit, depth + 1)?;
+				Some( self.node("mrow", vec![bvar, self.mo("→"), lowlimit]) )
+			},
+			_ => match apply.qualifier("condition") {
+				Some(condition) => Some( self.convert(condition, depth + 1)? ),
+				None => None,
+			},
+		};
+		let lim = self.scripted(self.leaf("mi", "lim"), under, None, true);
+		let mut children = vec![lim];
+		for &arg in &apply.args {
+			children.push(self.operand(arg, 6, depth)?);
+		}
+		return Ok( self.node("mrow", children) );
+	}
+
+	/// d/dx f or ∂²/∂x² f
+	fn convert_derivative(&mut self, apply: &Application<'a>, depth: usize) -> Result<Element<'a>> {
+		let d = if apply.operator == "diff" {"d"} else {"∂"};
+		let bvars = apply.bvars();
+		if bvars.is_empty() {
+			// <apply><diff/><ci>f</ci></apply> is f′
+			let Some(&function) = apply.args.first() else {
+				bail!("'diff' has no argument");
+			};
+			let function = self.operand(function, ATOMIC, depth)?;
+			return Ok( self.node("msup", vec![function, self.mo("′")]) );
+		}
+		let degree = match apply.bvar_degree() {
+			Some(degree) => Some( self.convert(degree, depth + 1)? ),
+			None => None,
+		};
+		let numerator = match &degree {
+			Some(degree) => self.node("msup", vec![self.leaf("mi", d), copy_mathml(*degree)]),
+			None => self.leaf("mi", d),
+		};
+		let mut denominator = vec![];
+		for bvar in bvars {
+			let bvar = self.convert(bvar, depth + 1)?;
+			let bvar = match &degree {
+				Some(degree) => self.node("msup", vec![bvar, copy_mathml(*degree)]),
+				None => bvar,
+			};
+			denominator.push(self.node("mrow", vec![self.leaf("mi", d), bvar]));
+		}
+		let denominator = if denominator.len() == 1 {denominator.pop().unwrap()} else {self.node("mrow", denominator)};
+		let mut children = vec![self.node("mfrac", vec![numerator, denominator])];
+		for &arg in &apply.args {
+			children.push(self.operand(arg, ATOMIC, depth)?);
+		}
+		return Ok( self.node("mrow", children) );
+	}
+
+	/// {a, b, c} or (a, b, c) or { x | condition }
+	fn convert_set_or_list(&mut self, element: Element<'a>, depth: usize) -> Result<Element<'a>> {
+		let (open, close) = if name(element) == "set" {("{", "}")} else {("(", ")")};
+		let children: Vec<Element> = element.children().iter().filter_map(|child| child.element()).collect();
+		let mut contents = vec![];
+		if children.iter().any(|&child| name(child) == "bvar") {
+			for &child in children.iter().filter(|&&child| name(child) == "bvar") {
+				let bvar = child.children().iter().find_map(|child| child.element());
+				if let Some(bvar) = bvar {
+					contents.push(self.convert(bvar, depth + 1)?);
+				}
+			}
+			contents.push(self.mo("|"));
+			for &child in children.iter().filter(|&&child| name(child) == "condition") {
+				if let Some(condition) = child.children().iter().find_map(|child| child.element()) {
+					contents.push(self.convert(condition, depth + 1)?);
+				}
+			}
+		} else {
+			for (i, &child) in children.iter().enumerate() {
+				if i > 0 {
+					contents.push(self.mo(","));
+				}
+				contents.push(self.convert(child, depth + 1)?);
+			}
+		}
+		let mut mrow = vec![self.mo(open)];
+		if !contents.is_empty() {
+			mrow.push(self.node("mrow", contents));
+		}
+		mrow.push(self.mo(close));
+		return Ok( self.node("mrow", mrow) );
+	}
+
+	fn convert_interval(&mut self, element: Element<'a>, depth: usize) -> Result<Element<'a>> {
+		let ends: Vec<Element> = element.children().iter().filter_map(|child| child.element()).collect();
+		if ends.len() != 2 {
+			bail!("'interval' must have two children");
+		}
+		let (open, close, intent) = match element.attribute_value("closure").unwrap_or("closed") {
+			"open" => ("(", ")", "open-interval"),
+			"open-closed" => ("(", "]", "open-closed-interval"),
+			"closed-open" => ("[", ")", "closed-open-interval"),
+			_ => ("[", "]", "closed-interval"),
+		};
+		let start = self.convert(ends[0], depth + 1)?;
+		let end = self.convert(ends[1], depth + 1)?;
+		let (start_arg, end_arg) = (self.arg(start), self.arg(end));
+		let contents = self.node("mrow", vec![start, self.mo(","), end]);
+		let mrow = self.node("mrow", vec![self.mo(open), contents, self.mo(close)]);
+		return Ok( self.with_intent(mrow, format!("{intent}({start_arg},{end_arg})")) );
+	}
+
+	/// { value if condition ... } written as a table
+	fn convert_piecewise(&mut self, element: Element<'a>, depth: usize) -> Result<Element<'a>> {
+		let mut rows = vec![];
+		for piece in element.children().iter().filter_map(|child| child.element()) {
+			let parts: Vec<Element> = piece.children().iter().filter_map(|child| child.element()).collect();
+			match (name(piece), parts.as_slice()) {
+				("piece", [value, condition]) => {
+					let value = self.convert(*value, depth + 1)?;
+					let condition = self.convert(*condition, depth + 1)?;
+					rows.push(vec![value, self.node("mrow", vec![self.leaf("mtext", "if"), condition])]);
+				},
+				("otherwise", [value]) => {
+					let value = self.convert(*value, depth + 1)?;
+					rows.push(vec![value, self.leaf("mtext", "otherwise")]);
+				},
+				_ => bail!("Illegal child '{}' of 'piecewise'", name(piece)),
+			}
+		}
+		return Ok( self.node("mrow", vec![self.mo("{"), self.table(rows)]) );
+	}
+
+	fn table(&self, rows: Vec<Vec<Element<'a>>>) -> Element<'a> {
+		let mtrs = rows.into_iter()
+			.map(|cells| {
+				let mtds = cells.into_iter().map(|cell| self.node("mtd", vec![cell])).collect();
+				self.node("mtr", mtds)
+			})
+			.collect();
+		return self.node("mtable", mtrs);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::interface::{get_element, trim_element, is_same_element};
+	use crate::pretty_print::mml_to_string;
+	use sxd_document::parser;
+
+	/// Convert the Content MathML in 'test' and compare it to 'target'
+	fn is_converted_to(test: &str, target: &str) -> Result<()> {
+		let test_package = parser::parse(test).expect("Failed to parse test input");
+		let test_mathml = get_element(&test_package);
+		trim_element(test_mathml, false);
+		convert_content_mathml(test_mathml)?;
+		let target_package = parser::parse(target).expect("Failed to parse target input");
+		let target_mathml = get_element(&target_package);
+		trim_element(target_mathml, false);
+		if let Err(e) = is_same_element(test_mathml, target_mathml, &[]) {
+			bail!("{}\nResult:\n{}", e, mml_to_string(test_mathml));
+		}
+		return Ok( () );
+	}
+
+	/// Check that set_mathml gives the same (canonical) MathML for the Content MathML 'content' as for 'presentation'
+	fn is_set_as(content: &str, presentation: &str) -> Result<()> {
+		crate::interface::set_rules_dir(crate::abs_rules_dir_path())?;
+		let expected = crate::interface::set_mathml(presentation)?;
+		let result = crate::interface::set_mathml(content)?;
+		let (result_package, expected_package) = (parser::parse(&result)?, parser::parse(&expected)?);
+		if let Err(e) = is_same_element(get_element(&result_package), get_element(&expected_package), &["id", "data-id-added", "data-changed"]) {
+			bail!("{}\nset_mathml({}) gave\n{}\nbut expected\n{}", e, content, result, expected);
+		}
+		return Ok( () );
+	}
+
+	#[test]
+	fn cn_types() -> Result<()> {
+		is_set_as("<math><cn>42</cn></math>", "<math><mn>42</mn></math>")?;
+		is_set_as("<math><cn type='integer'>-7</cn></math>", "<math><mo>−</mo><mn>7</mn></math>")?;
+		is_set_as("<math><cn type='integer' base='16'>FF</cn></math>", "<math><msub><mn>FF</mn><mn>16</mn></msub></math>")?;
+		is_set_as("<math><cn type='real'>3.25</cn></math>", "<math><mn>3.25</mn></math>")?;
+		is_set_as("<math><cn type='double'>1.5e-3</cn></math>",
+			"<math><mn>1.5</mn><mo>×</mo><msup><mn>10</mn><mrow><mo>−</mo><mn>3</mn></mrow></msup></math>")?;
+		is_set_as("<math><cn type='double'>-INF</cn></math>", "<math><mo>−</mo><mi>∞</mi></math>")?;
+		is_set_as("<math><cn type='hexdouble'>7F800000</cn></math>", "<math><mn>7F800000</mn></math>")?;
+		is_set_as("<math><cn type='e-notation'>12.3<sep/>5</cn></math>",
+			"<math><mn>12.3</mn><mo>×</mo><msup><mn>10</mn><mn>5</mn></msup></math>")?;
+		is_set_as("<math><cn type='rational'> 1 <sep/> 2 </cn></math>", "<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>")?;
+		is_set_as("<math><cn type='complex-cartesian'>3<sep/>-4</cn></math>",
+			"<math><mn>3</mn><mo>−</mo><mn>4</mn><mo>\u{2062}</mo><mi>ⅈ</mi></math>")?;
+		is_set_as("<math><cn type='complex-polar'>2<sep/>3.1</cn></math>",
+			"<math><mn>2</mn><mo>\u{2062}</mo><msup><mi>ⅇ</mi><mrow><mi>ⅈ</mi><mo>\u{2062}</mo><mn>3.1</mn></mrow></msup></math>")?;
+		is_set_as("<math><cn type='constant'>π</cn></math>", "<math><mi>π</mi></math>")?;
+		// numbers that contain an operator are parenthesized when needed
+		is_set_as("<math><apply><power/><cn type='complex-cartesian'>1<sep/>2</cn><cn>2</cn></apply></math>",
+			"<math><msup><mrow><mo>(</mo><mn>1</mn><mo>+</mo><mn>2</mn><mo>\u{2062}</mo><mi>ⅈ</mi><mo>)</mo></mrow><mn>2</mn></msup></math>")?;
+		return Ok( () );
+	}
+
+	#[test]
+	fn errors() {
+		crate::interface::set_rules_dir(crate::abs_rules_dir_path()).unwrap();
+		assert!(crate::interface::set_mathml("<math><apply><plus/></apply></math>").is_err());
+		assert!(crate::interface::set_mathml("<math><apply><divide/><cn>1</cn></apply></math>").is_err());
+		assert!(crate::interface::set_mathml("<math><cn type='rational'>1</cn></math>").is_err());
+		assert!(crate::interface::set_mathml("<math><cn>1<sep/>2</cn></math>").is_err());
+	}
+
+	#[test]
+	fn infix_and_precedence() -> Result<()> {
+		let content = "<math>
+			<apply><times/>
+				<apply><plus/><ci>a</ci><apply><minus/><ci>b</ci></apply></apply>
+				<apply><power/><ci>x</ci><cn>2</cn></apply>
+			</apply>
+		</math>";
+		let presentation = "<math>
+			<mrow>
+				<mrow><mo>(</mo><mrow><mi>a</mi><mo>−</mo><mi>b</mi></mrow><mo>)</mo></mrow>
+				<mo>\u{2062}</mo>
+				<msup><mi>x</mi><mn>2</mn></msup>
+			</mrow>
+		</math>";
+		return is_converted_to(content, presentation);
+	}
+
+	#[test]
+	fn functions_and_intents() -> Result<()> {
+		let content = "<math>
+			<apply><eq/>
+				<apply><abs/><apply><sin/><ci>x</ci></apply></apply>
+				<interval closure='open-closed'><cn>0</cn><infinity/></interval>
+			</apply>
+		</math>";
+		let presentation = "<math>
+			<mrow>
+				<mrow intent='absolute-value($a1)'>
+					<mo>|</mo><mrow arg='a1'><mi>sin</mi><mo>\u{2061}</mo><mi>x</mi></mrow><mo>|</mo>
+				</mrow>
+				<mo>=</mo>
+				<mrow intent='open-closed-interval($a2,$a3)'>
+					<mo>(</mo><mrow><mn arg='a2'>0</mn><mo>,</mo><mi arg='a3'>∞</mi></mrow><mo>]</mo>
+				</mrow>
+			</mrow>
+		</math>";
+		return is_converted_to(content, presentation);
+	}
+
+	#[test]
+	fn large_ops() -> Result<()> {
+		let content = "<math>
+			<apply><sum/>
+				<bvar><ci>i</ci></bvar><lowlimit><cn>1</cn></lowlimit><uplimit><ci>n</ci></uplimit>
+				<apply><int/>
+					<bvar><ci>x</ci></bvar><lowlimit><cn>0</cn></lowlimit><uplimit><ci>i</ci></uplimit>
+					<apply><exp/><ci>x</ci></apply>
+				</apply>
+			</apply>
+		</math>";
+		let presentation = "<math>
+			<mrow>
+				<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>
+				<mrow>
+					<msubsup><mo>∫</mo><mn>0</mn><mi>i</mi></msubsup>
+					<msup><mi>ⅇ</mi><mi>x</mi></msup>
+					<mrow><mi>d</mi><mi>x</mi></mrow>
+				</mrow>
+			</mrow>
+		</math>";
+		return is_converted_to(content, presentation);
+	}
+
+	#[test]
+	fn content_annotation() -> Result<()> {
+		// the author's (0,1) is an interval, not a point
+		let content = "<math>
+			<semantics>
+				<mrow><mo>(</mo><mrow><mn>0</mn><mo>,</mo><mn>1</mn></mrow><mo>)</mo></mrow>
+				<annotation-xml encoding='MathML-Content'>
+					<interval closure='open'><cn>0</cn><cn>1</cn></interval>
+				</annotation-xml>
+			</semantics>
+		</math>";
+		let presentation = "<math>
+			<semantics>
+				<mrow intent='open-interval($a1,$a2)'><mo>(</mo><mrow><mn arg='a1'>0</mn><mo>,</mo><mn arg='a2'>1</mn></mrow><mo>)</mo></mrow>
+				<annotation-xml encoding='MathML-Content'>
+					<interval closure='open'><cn>0</cn><cn>1</cn></interval>
+				</annotation-xml>
+			</semantics>
+		</math>";
+		return is_converted_to(content, presentation);
+	}
+}
@@ -8,9 +8,11 @@
 //!   in some way so they can be written at runtime when the files are read.
 //!   This is done by putting them inside of a lock (`thread_local`).
 //!
-//! Furthermore, it was necessary to use `RefCell` and `Rc` to deal with interior mutability.
+//! Furthermore, it was necessary to use `RefCell` to deal with interior mutability.
 //! All of this means that a lock needs to be obtained _and_ the contents borrowed to access a definition.
 //!
+//! The definitions don't change once they are read, so the definitions read from a file are shared (via `Arc`) by all threads.
+//!
 //! To minimize the global variable footprint, all of the definitions are put inside of a single global variable [`DEFINITIONS`].
 //!
 //! //! Note: some of the variables are `vec`s and some are `hashset`s.
@@ -28,29 +30,26 @@ use yaml_rust::yaml::Hash;
 use yaml_rust::Yaml;
 use crate::errors::*;
 use crate::prefs::*;
-use std::{cell::RefCell, cell::Ref, cell::RefMut, rc::Rc};
+use std::{cell::RefCell, cell::RefMut};
+use std::sync::{Arc, LazyLock, RwLock};
 use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
-use crate::shim_filesystem::read_to_string_shim;
 
 /// An enum to paper over the different types of data access needed.
 ///
-/// Having a Rc<RefCell<FromFileVariable>> seems a bit complicated in terms of types but...
-/// 1. The rust book seems to endorse the Rc<RefCell<...>>> approach when there are multiple owners of mutable date.
-///    See <https://doc.rust-lang.org/book/ch15-05-interior-mutability.html> towards the end
-/// 2. When a file is read, we need to clear and add data to the structure being read (reassigning could work for clearing).
-///    When we use the data, we either want to index into it or test if an item is there.
-///    The structures we use are either a Vec or a HashMap, so we need to abstract that away in `FromFileVariable`.
-///    Unfortunately, traits don't quite work as an option here:
-///    *  Vec implements extends (`add`), but there is no test/contains
-///    *  Hashmap implements `index`, but panics if the item isn't there
+/// The values are built when a file is read and don't change after that, so they are in an `Arc` so they can be shared by all threads.
+/// When we use the data, we either want to index into it or test if an item is there.
+/// The structures we use are either a Vec or a HashMap, so we need to abstract that away in `FromFileVariable`.
+/// Unfortunately, traits don't quite work as an option here:
+/// *  Vec implements extends (`add`), but there is no test/contains
+/// *  Hashmap implements `index`, but panics if the item isn't there
 ///
 /// Because of the above limitations, we introduce the enum [`Contains`] which dispatches appropriately to Vec/Hashmap
 #[derive(Debug, Clone)]
 pub enum Contains {
-    Vec(Rc<RefCell<Vec<String>>>),
-    Set(Rc<RefCell<HashSet<String>>>),
-    Map(Rc<RefCell<HashMap<String, String>>>),
+    Vec(Arc<Vec<String>>),
+    Set(Arc<HashSet<String>>),
+    Map(Arc<HashMap<String, String>>),
 }
 
 impl Contains {
@@ -80,13 +79,13 @@ type VariableDefHashMap = HashMap<String, CollectionFromFile>;
 /// There should only be one instance of this structure ([`DEFINITIONS`])
 // FIX: this probably can done with a macro to remove all the repetition
 pub struct Definitions {
-    pub name_to_var_mapping: VariableDefHashMap,
+    pub name_to_var_mapping: Arc<VariableDefHashMap>,     // shared with other threads that use the same definitions file
 }
 
 impl Default for Definitions {
     fn default() -> Self {
         Definitions {
-            name_to_var_mapping: HashMap::with_capacity(30),
+            name_to_var_mapping: Arc::new( HashMap::with_capacity(30) ),
         }
     }
 }
@@ -94,30 +93,30 @@ impl Default for Definitions {
 impl Definitions {
     fn new() -> Self {
         Definitions {
-            name_to_var_mapping: HashMap::with_capacity(30),
+            name_to_var_mapping: Arc::new( HashMap::with_capacity(30) ),
         }
     }
 
-    pub fn get_hashset(&self, name: &str) -> Option<Ref<'_, HashSet<String>>> {
+    pub fn get_hashset(&self, name: &str) -> Option<&HashSet<String>> {
         let names = self.name_to_var_mapping.get(name);
         if let Some(Contains::Set(set)) = names {
-            return Some(set.borrow());
+            return Some(set);
         }
         return None;
     }
 
-    pub fn get_hashmap(&self, name: &str) ->  Option<Ref<'_, HashMap<String, String>>> {
+    pub fn get_hashmap(&self, name: &str) ->  Option<&HashMap<String, String>> {
         let names = self.name_to_var_mapping.get(name);
         if let Some(Contains::Map(map)) = names {
-            return Some(map.borrow());
+            return Some(map);
         }
         return None;
     }
 
-    pub fn get_vec(&self, name: &str) -> Option<Ref<'_, Vec<String>>> {
+    pub fn get_vec(&self, name: &str) -> Option<&Vec<String>> {
         let names = self.name_to_var_mapping.get(name);
         if let Some(Contains::Vec(vec)) = names {
-            return Some(vec.borrow());
+            return Some(vec);
         }
         return None;
     }
@@ -131,7 +130,18 @@ thread_local!{
     pub static DEFINITIONS: &'static std::thread::LocalKey<RefCell<Definitions>> = const { &SPEECH_DEFINITIONS };
 }
 
+/// The definitions read from each file (shared by all threads)
+static SHARED_DEFINITIONS: SharedTables<VariableDefHashMap> = LazyLock::new(|| RwLock::new( HashMap::with_capacity(15) ));
+
+/// Forget the definitions shared by the threads so that they are read again the next time they are needed.
+pub fn clear_shared_definitions() {
+    SHARED_DEFINITIONS.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
 /// Reads the `definitions.yaml` files specified by current_files -- these are presumed to need updating. 
+/// If another thread has already read the (unchanged) files, its definitions are used.
 ///
 /// If there is a failure during read, the error is propagated to the caller
 pub fn read_definitions_file(use_speech_defs: bool) -> Result<Vec<PathBuf>> {
@@ -139,18 +149,23 @@ pub fn read_definitions_file(use_speech_defs: bool) -> Result<Vec<PathBuf>> {
     let pref_manager = PreferenceManager::get();
     let pref_manager = pref_manager.borrow();
     let file_path = pref_manager.get_definitions_file(use_speech_defs);
+    let should_ignore_file_time = pref_manager.pref_to_string("CheckRuleFiles") != "All";
     let definitions = if use_speech_defs {&SPEECH_DEFINITIONS} else {&BRAILLE_DEFINITIONS};
-    definitions.with( |defs| defs.borrow_mut().name_to_var_mapping.clear() );
-    let mut new_files = vec![file_path.to_path_buf()];
-    let mut files_read = read_one_definitions_file(use_speech_defs, file_path).with_context(|| format!("in file '{}", file_path.to_string_lossy()))?;
-    new_files.append(&mut files_read);
-
-    // merge the contents of `TrigFunctions` into a set that contains all the function names (from `AdditionalFunctionNames`).
-    return definitions.with(|defs| {
-        let mut defs = defs.borrow_mut();
-        make_all_set_references_valid(&mut defs);
-        return Ok(new_files);
-    });
+    let (name_to_var_mapping, files) = get_shared_table(&SHARED_DEFINITIONS, file_path, should_ignore_file_time, || {
+        definitions.with( |defs| defs.borrow_mut().name_to_var_mapping = Arc::new( HashMap::with_capacity(30) ) );
+        let mut new_files = vec![file_path.to_path_buf()];
+        let mut files_read = read_one_definitions_file(use_speech_defs, file_path).with_context(|| format!("in file '{}", file_path.to_string_lossy()))?;
+        new_files.append(&mut files_read);
+
+        // merge the contents of `TrigFunctions` into a set that contains all the function names (from `AdditionalFunctionNames`).
+        return definitions.with(|defs| {
+            let mut defs = defs.borrow_mut();
+            make_all_set_references_valid(&mut defs);
+            return Ok( (defs.name_to_var_mapping.clone(), new_files) );
+        });
+    })?;
+    definitions.with(|defs| defs.borrow_mut().name_to_var_mapping = name_to_var_mapping);
+    return Ok(files.paths());
     
 
     /// Make references to all used set be valid by creating empty sets if they weren't defined
@@ -161,12 +176,12 @@ pub fn read_definitions_file(use_speech_defs: bool) -> Result<Vec<PathBuf>> {
         // let name_to_mapping = defs.name_to_var_mapping.borrow_mut();
         for set_name in used_set_names {
             if defs.get_hashset(set_name).is_none() {
-                defs.name_to_var_mapping.insert(set_name.to_string(), Contains::Set( Rc::new( RefCell::new( HashSet::with_capacity(0) ) ) ));
+                Arc::make_mut(&mut defs.name_to_var_mapping).insert(set_name.to_string(), Contains::Set( Arc::new( HashSet::with_capacity(0) ) ));
             }
         }
         if defs.get_hashset("FunctionNames").is_none() {
             let all_functions = build_all_functions_set(defs);
-            defs.name_to_var_mapping.insert("FunctionNames".to_string(), Contains::Set( Rc::new( RefCell::new( all_functions ) ) ));
+            Arc::make_mut(&mut defs.name_to_var_mapping).insert("FunctionNames".to_string(), Contains::Set( Arc::new( all_functions ) ));
         }
     }
 
@@ -183,10 +198,6 @@ pub fn read_definitions_file(use_speech_defs: bool) -> Result<Vec<PathBuf>> {
 
 use crate::speech::*;
 fn read_one_definitions_file(use_speech_defs: bool, path: &Path) -> Result<Vec<PathBuf>> {
-    // read in the file contents   
-    let definition_file_contents = read_to_string_shim(path)
-            .with_context(|| format!("trying to read {}", path.to_str().unwrap()))?;
-
     // callback to do the work of building up the defined vectors/hashmaps (in 'build_values') from YAML
     let defs_build_fn = |variable_def_list: &Yaml| {
         // Rule::DefinitionList
@@ -202,8 +213,8 @@ fn read_one_definitions_file(use_speech_defs: bool, path: &Path) -> Result<Vec<P
         return Ok(files_read);
     };
 
-    // Convert the file contents to YAML and call the callback
-    return crate::speech::compile_rule(&definition_file_contents, defs_build_fn)
+    // Read the file (or use the already parsed YAML) and call the callback
+    return crate::speech::compile_rule_file(path, defs_build_fn)
         .with_context(|| format!("In file '{}'", path.to_str().unwrap()));
 }
 
@@ -230,7 +241,7 @@ fn build_values(definition: &Yaml, use_speech_defs: bool, path: &Path) -> Result
 
     let result;
     if def_name.starts_with("Numbers") || def_name.ends_with("_vec") {
-         result = Contains::Vec( Rc::new( RefCell::new( get_vec_values(value.as_vec().unwrap())? ) ) );
+         result = Contains::Vec( Arc::new( get_vec_values(value.as_vec().unwrap())? ) );
     } else {
         // match value.as_vec() {
         //     Some(vec) => {
@@ -244,22 +255,22 @@ fn build_values(definition: &Yaml, use_speech_defs: bool, path: &Path) -> Result
         // }
         let dict = value.as_hash().ok_or_else(|| anyhow!("definition list value '{}' is not an array or dictionary", yaml_to_type(value)))?;
         if dict.is_empty() {
-            result = Contains::Set( Rc::new( RefCell::new( HashSet::with_capacity(0) ) ) );
+            result = Contains::Set( Arc::new( HashSet::with_capacity(0) ) );
         } else {
             // peak and see if this is a set or a map
             let (_, entry_value) = dict.iter().next().unwrap();
             if entry_value.is_null() {
-                result = Contains::Set( Rc::new( RefCell::new( get_set_values(dict)
-                            .with_context(||format!("while reading value '{def_name}'"))? ) ) );
+                result = Contains::Set( Arc::new( get_set_values(dict)
+                            .with_context(||format!("while reading value '{def_name}'"))? ) );
             } else {
                 // peak and see if this is a set or a map
                 let (_, entry_value) = dict.iter().next().unwrap();
                 if entry_value.is_null() {
-                    result = Contains::Set( Rc::new( RefCell::new( get_set_values(dict)
-                                .with_context(||format!("while reading value '{def_name}'"))? ) ) );
+                    result = Contains::Set( Arc::new( get_set_values(dict)
+                                .with_context(||format!("while reading value '{def_name}'"))? ) );
                 } else {
-                    result = Contains::Map( Rc::new( RefCell::new( get_map_values(dict)
-                                .with_context(||format!("while reading value '{def_name}'"))? ) ) );
+                    result = Contains::Map( Arc::new( get_map_values(dict)
+                                .with_context(||format!("while reading value '{def_name}'"))? ) );
                 }
             }
         }
@@ -268,7 +279,7 @@ fn build_values(definition: &Yaml, use_speech_defs: bool, path: &Path) -> Result
     let definitions = if use_speech_defs {&SPEECH_DEFINITIONS} else {&BRAILLE_DEFINITIONS};
     return definitions.with(|definitions| {
         let name_definition_map = &mut definitions.borrow_mut().name_to_var_mapping;
-        name_definition_map.insert(def_name.to_string(), result);
+        Arc::make_mut(name_definition_map).insert(def_name.to_string(), result);
         return Ok(None);
     });
 
@@ -0,0 +1,289 @@
+//! A [`MathDocument`] holds one MathML expression along with its own navigation state.
+//!
+//! The functions in [`crate::interface`] work on a single expression per thread: every call to [`crate::set_mathml`]
+//! replaces the previous expression and resets navigation.
+//! Applications that present many expressions (e.g., a page with hundreds of equations) can instead create a
+//! `MathDocument` for each expression. Each document keeps its own canonicalized tree and navigation stack, so
+//! a user can move to another expression and later return to where they were.
+//!
+//! All documents on a thread share the rule files and preferences that are loaded for that thread
+//! (see [`crate::set_rules_dir`] and [`crate::set_preference`]).
+#![allow(clippy::needless_return)]
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use sxd_document::Package;
+
+use crate::errors::*;
+use crate::interface::*;
+use crate::navigate::*;
+use crate::pretty_print::mml_to_string;
+use crate::tts::SpeechSegment;
+use crate::braille::BrailleLines;
+
+/// A single MathML expression along with its navigation state.
+pub struct MathDocument {
+    package: Package,
+    nav_state: NavigationState,
+}
+
+impl std::fmt::Debug for MathDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "MathDocument {{ mathml: {} }}", self.get_mathml());
+    }
+}
+
+impl MathDocument {
+    /// Parse and canonicalize `mathml_str`.
+    /// Like [`crate::set_mathml`], 'id's are added to any node that doesn't have an id (see [`MathDocument::get_mathml`]).
+    pub fn new(mathml_str: impl AsRef<str>) -> Result<MathDocument> {
+        enable_logs();
+        let mathml_str = mathml_str.as_ref();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let package = parse_mathml_impl(mathml_str)?;
+            return Ok(MathDocument { package, nav_state: NavigationState::new() });
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Convert `latex` to MathML and then parse and canonicalize it (see [`crate::set_latex`]).
+    pub fn from_latex(latex: impl AsRef<str>) -> Result<MathDocument> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| crate::latex::latex_to_mathml(latex.as_ref())));
+        return MathDocument::new(report_any_panic(result)?);
+    }
+
+    /// Convert `asciimath` to MathML and then parse and canonicalize it (see [`crate::set_asciimath`]).
+    pub fn from_asciimath(asciimath: impl AsRef<str>) -> Result<MathDocument> {
+        return MathDocument::new(crate::asciimath::asciimath_to_mathml(asciimath.as_ref()));
+    }
+
+    /// Returns the canonical MathML with 'id's set on all the nodes.
+    pub fn get_mathml(&self) -> String {
+        return mml_to_string(get_element(&self.package));
+    }
+
+    /// Get the spoken text of the expression (see [`crate::get_spoken_text`]).
+    pub fn get_spoken_text(&self) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_spoken_text_impl(get_element(&self.package));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the speech for the expression as a list of segments (see [`crate::get_spoken_segments`]).
+    pub fn get_spoken_segments(&self) -> Result<Vec<SpeechSegment>> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_spoken_segments_impl(get_element(&self.package));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the spoken text for an overview of the expression (see [`crate::get_overview_text`]).
+    pub fn get_overview_text(&self) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_overview_text_impl(get_element(&self.package));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the spoken text for an overview of the current navigation node (see [`crate::get_navigation_overview_text`]).
+    pub fn get_navigation_overview_text(&self) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return overview_navigation_node(&self.nav_state, get_element(&self.package));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the braille for the expression (see [`crate::get_braille`]).
+    /// If 'nav_node_id' is given, it is highlighted based on the value of `BrailleNavHighlight`.
+    pub fn get_braille(&self, nav_node_id: impl AsRef<str>) -> Result<String> {
+        enable_logs();
+        let nav_node_id = nav_node_id.as_ref();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let braille = crate::braille::braille_mathml(get_element(&self.package), nav_node_id)?.0;
+            return crate::braille_encoding::encode_braille_using_prefs(&braille);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the braille for the expression broken into lines along with the MathML node for each braille cell (see [`crate::get_braille_lines`]).
+    pub fn get_braille_lines(&self, nav_node_id: impl AsRef<str>, line_length: usize) -> Result<BrailleLines> {
+        enable_logs();
+        let nav_node_id = nav_node_id.as_ref();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_braille_lines_impl(get_element(&self.package), nav_node_id, line_length);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the braille for the current navigation node as if it were the entire expression (see [`crate::get_navigation_braille`]).
+    pub fn get_navigation_braille(&self) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_navigation_braille_impl(get_element(&self.package), &self.nav_state);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Get the current navigation node in the format given by the `CopyAs` preference (see [`crate::get_navigation_copy`]).
+    pub fn get_navigation_copy(&self) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_navigation_copy_impl(get_element(&self.package), &self.nav_state);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Move the navigation node based on a key press and return the speech (see [`crate::do_navigate_keypress`]).
+    pub fn do_navigate_keypress(&mut self, key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool) -> Result<String> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mathml = get_element(&self.package);
+            return do_mathml_navigate_key_press_with_state(&mut self.nav_state, mathml, key, shift_key, control_key, alt_key, meta_key);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Move the navigation node based on a navigation command and return the speech (see [`crate::do_navigate_command`]).
+    pub fn do_navigate_command(&mut self, command: impl AsRef<str>) -> Result<String> {
+        enable_logs();
+        let command = command.as_ref();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let cmd = nav_command_from_string(command)?;
+            let mathml = get_element(&self.package);
+            return do_navigate_command_string_with_state(&mut self.nav_state, mathml, cmd);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Set the navigation node to the node with the given `id` and offset (for tokens).
+    /// An error is returned if the 'id' doesn't exist.
+    pub fn set_navigation_node(&mut self, id: impl AsRef<str>, offset: usize) -> Result<()> {
+        enable_logs();
+        let id = id.as_ref();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mathml = get_element(&self.package);
+            return set_navigation_node_from_id_with_state(&mut self.nav_state, mathml, id, offset);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Return the MathML associated with the current navigation node and the offset (0-based) from that mathml.
+    pub fn get_navigation_mathml(&self) -> Result<(String, usize)> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let (found, offset) = self.nav_state.get_navigation_mathml(get_element(&self.package))?;
+            return Ok((mml_to_string(found), offset));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Return the `id` and `offset` (0-based) associated with the current navigation node.
+    pub fn get_navigation_mathml_id(&self) -> Result<(String, usize)> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return Ok(self.nav_state.get_navigation_mathml_id(get_element(&self.package)));
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Return the start and end braille character positions associated with the current navigation node.
+    pub fn get_braille_position(&self) -> Result<(usize, usize)> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return get_braille_position_impl(get_element(&self.package), &self.nav_state);
+        }));
+        return report_any_panic(result);
+    }
+
+    /// Given a 0-based braille position, return the smallest MathML node enclosing it.
+    /// This node might be a leaf with an offset.
+    pub fn get_navigation_node_from_braille_position(&self, position: usize) -> Result<(String, usize)> {
+        enable_logs();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            return crate::braille::get_navigation_node_from_braille_position(get_element(&self.package), position);
+        }));
+        return report_any_panic(result);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init() {
+        init_panic_handler();
+        set_rules_dir(crate::abs_rules_dir_path()).unwrap();
+        set_preference("Language", "en").unwrap();
+        set_preference("SpeechStyle", "SimpleSpeak").unwrap();
+        set_preference("Verbosity", "Medium").unwrap();
+        set_preference("NavMode", "Enhanced").unwrap();
+    }
+
+    #[test]
+    fn independent_navigation() -> Result<()> {
+        init();
+        let mut doc1 = MathDocument::new("<math id='a'><mi id='a1'>x</mi><mo id='a2'>+</mo><mn id='a3'>1</mn></math>")?;
+        let mut doc2 = MathDocument::new("<math id='b'><mfrac id='b1'><mi id='b2'>a</mi><mi id='b3'>b</mi></mfrac></math>")?;
+        assert_eq!(doc1.get_spoken_text()?, "x plus 1");
+        assert_eq!(doc2.get_spoken_text()?, "eigh over b");
+
+        doc1.set_navigation_node("a3", 0)?;
+        doc2.do_navigate_command("ZoomIn")?;
+        assert_eq!(doc1.get_navigation_mathml_id()?.0, "a3");
+        assert_eq!(doc2.get_navigation_mathml_id()?.0, "b2");
+
+        // moving in one document doesn't affect the other
+        doc2.do_navigate_command("MoveNext")?;
+        assert_eq!(doc1.get_navigation_mathml_id()?.0, "a3");
+        assert_eq!(doc2.get_navigation_mathml_id()?.0, "b3");
+        doc1.do_navigate_command("MovePrevious")?;
+        assert_eq!(doc1.get_navigation_mathml_id()?.0, "a2");
+        assert_eq!(doc2.get_navigation_mathml_id()?.0, "b3");
+        return Ok(());
+    }
+
+    #[test]
+    fn does_not_change_set_mathml() {
+        init();
+        set_mathml("<math id='m'><mi id='m1'>y</mi><mo id='m2'>=</mo><mn id='m3'>2</mn></math>").unwrap();
+        set_navigation_node("m3", 0).unwrap();
+        let mut doc = MathDocument::new("<math id='a'><mi id='a1'>x</mi><mo id='a2'>+</mo><mn id='a3'>1</mn></math>").unwrap();
+        doc.do_navigate_command("ZoomIn").unwrap();
+        assert_eq!(doc.get_navigation_mathml_id().unwrap().0, "a1");
+        assert_eq!(get_navigation_mathml_id().unwrap().0, "m3");
+        assert_eq!(get_spoken_text().unwrap(), "y is equal to 2");
+    }
+
+    #[test]
+    fn overview() -> Result<()> {
+        init();
+        let mut doc = MathDocument::new("<math id='a'><mi id='a1'>x</mi><mo id='a2'>=</mo>
+                <mfrac id='a3'><mrow id='a4'><mi>b</mi><mo>+</mo><mn>1</mn></mrow><mn id='a5'>3</mn></mfrac></math>")?;
+        assert_eq!(doc.get_overview_text()?, "an equation with x on the left and a fraction on the right");
+        doc.set_navigation_node("a3", 0)?;
+        assert_eq!(doc.get_navigation_overview_text()?, "a fraction with numerator b plus 1 and denominator 3");
+        return Ok(());
+    }
+
+    #[test]
+    fn spoken_segments() -> Result<()> {
+        init();
+        let doc = MathDocument::new("<math id='a'><mi id='a1'>x</mi><mo id='a2'>=</mo><mn id='a3'>2</mn></math>")?;
+        let segments = doc.get_spoken_segments()?;
+        let texts = segments.iter().map(|segment| (segment.text.as_str(), segment.id.as_str())).collect::<Vec<_>>();
+        assert_eq!(texts, vec![("x", "a1"), ("is equal to", "a2"), ("2", "a3")]);
+        return Ok(());
+    }
+
+    #[test]
+    fn bad_mathml() {
+        init();
+        assert!(MathDocument::new("<math><mi>x</mi>").is_err());
+    }
+}
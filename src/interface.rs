@@ -19,6 +19,8 @@ use log::{debug, error};
 
 use crate::navigate::*;
 use crate::pretty_print::mml_to_string;
+use crate::tts::SpeechSegment;
+use crate::braille::BrailleLines;
 use crate::xpath_functions::{is_leaf, IsNode};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
@@ -30,7 +32,7 @@ use std::sync::Once;
 #[cfg(feature = "enable-logs")]
 static INIT: Once = Once::new();
 
-fn enable_logs() {
+pub(crate) fn enable_logs() {
     #[cfg(feature = "enable-logs")]
     INIT.call_once(||{
         #[cfg(target_os = "android")]
@@ -150,6 +152,55 @@ pub fn get_version() -> String {
 /// The ids can be used for sync highlighting if the `Bookmark` API preference is true.
 pub fn set_mathml(mathml_str: impl AsRef<str>) -> Result<String> {
     enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        NAVIGATION_STATE.with(|nav_stack| {
+            nav_stack.borrow_mut().reset();
+        });
+
+        return MATHML_INSTANCE.with(|old_package| {
+            match parse_mathml_impl(mathml_str.as_ref()) {
+                Ok(new_package) => {
+                    let mathml_string = mml_to_string(get_element(&new_package));
+                    old_package.replace(new_package);
+                    return Ok(mathml_string);
+                },
+                Err(e) => {
+                    // Clear stale state so subsequent API calls do not return previous user's data (security issue)
+                    old_package.replace(parser::parse("<math></math>").unwrap());
+                    return Err(e);
+                },
+            }
+        });
+    }));
+
+    return report_any_panic(result);
+}
+
+/// Convert `latex` (a practical subset of LaTeX math, optionally surrounded by `$...$`, `\(...\)`, etc.) to MathML
+/// and then set it as with [`set_mathml`].
+/// Errors in the LaTeX give the position in `latex` where the problem was found.
+pub fn set_latex(latex: impl AsRef<str>) -> Result<String> {
+    enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| crate::latex::latex_to_mathml(latex.as_ref())));
+    match report_any_panic(result) {
+        Ok(mathml) => return set_mathml(mathml),
+        Err(e) => {
+            // Clear stale state (as set_mathml does) so subsequent calls don't use the previous expression
+            set_mathml("<math></math>")?;
+            return Err(e);
+        },
+    }
+}
+
+/// Convert `asciimath` (see <https://asciimath.org>) to MathML and then set it as with [`set_mathml`].
+pub fn set_asciimath(asciimath: impl AsRef<str>) -> Result<String> {
+    enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| Ok( crate::asciimath::asciimath_to_mathml(asciimath.as_ref()) )));
+    return set_mathml(report_any_panic(result)?);
+}
+
+/// Parse `mathml_str` and clean it up (canonicalize it and add ids) -- shared by [`set_mathml`] and [`crate::document::MathDocument`]
+pub(crate) fn parse_mathml_impl(mathml_str: &str) -> Result<Package> {
     // if these are present when resent to MathJaX, MathJaX crashes (https://github.com/mathjax/MathJax/issues/2822)
     static MATHJAX_V2: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"class *= *['"]MJX-.*?['"]"#).unwrap());
     static MATHJAX_V3: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"class *= *['"]data-mjx-.*?['"]"#).unwrap());
@@ -162,69 +213,50 @@ pub fn set_mathml(mathml_str: impl AsRef<str>) -> Result<String> {
     static NAMESPACE_DECL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"xmlns:[[:alpha:]]{1,32}"#).unwrap());
     static PREFIX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(</?)[[:alpha:]]{1,32}:"#).unwrap());
     static HTML_ENTITIES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"&([a-zA-Z]{2,10});"#).unwrap());
-    let result = catch_unwind(AssertUnwindSafe(|| {
-        NAVIGATION_STATE.with(|nav_stack| {
-            nav_stack.borrow_mut().reset();
-        });
+    static HTML_ENTITIES_MAPPING: phf::Map<&str, &str> = include!("entities.in");
 
-        // We need the main definitions files to be read in so canonicalize can work.
-        // This call reads all of them for the current preferences, but that's ok since they will likely be used
-        crate::speech::SPEECH_RULES.with(|rules| rules.borrow_mut().read_files())?;
-
-        let mathml_str = mathml_str.as_ref();
-        // Safety guard: Reject strings > 1MB to prevent DoS/Stack issues
-        if mathml_str.len() > 1024 * 1024 {
-            bail!("MathML string of size {} bytes exceeds length limit of 1MB", mathml_str.len());
-        }
+    // We need the main definitions files to be read in so canonicalize can work.
+    // This call reads all of them for the current preferences, but that's ok since they will likely be used
+    crate::speech::SPEECH_RULES.with(|rules| rules.borrow_mut().read_files())?;
 
-        return MATHML_INSTANCE.with(|old_package| {
-            static HTML_ENTITIES_MAPPING: phf::Map<&str, &str> = include!("entities.in");
-
-            let mut error_message = "".to_string(); // can't return a result inside the replace_all, so we do this hack of setting the message and then returning the error
-                                                                     
-            let mathml_str = XML_COMMENT.replace_all(mathml_str, "");
-            let mathml_str = PROCESSING_INSTRUCTION.replace_all(&mathml_str, "");
-            // FIX: need to deal with character data and convert to something the parser knows
-            let mathml_str = HTML_ENTITIES.replace_all(&mathml_str, |cap: &Captures| match HTML_ENTITIES_MAPPING.get(&cap[1]) {
-                    None => {
-                        error_message = format!("No entity named '{}'", &cap[0]);
-                        cap[0].to_string()
-                    }
-                    Some(&ch) => ch.to_string(),
-                });
+    // Safety guard: Reject strings > 1MB to prevent DoS/Stack issues
+    if mathml_str.len() > 1024 * 1024 {
+        bail!("MathML string of size {} bytes exceeds length limit of 1MB", mathml_str.len());
+    }
 
-            if !error_message.is_empty() {
-                // Clear stale state so subsequent API calls do not return previous user's data (security issue)
-                old_package.replace(parser::parse("<math></math>").unwrap());
-                bail!(error_message);
+    let mut error_message = "".to_string(); // can't return a result inside the replace_all, so we do this hack of setting the message and then returning the error
+                                                             
+    let mathml_str = XML_COMMENT.replace_all(mathml_str, "");
+    let mathml_str = PROCESSING_INSTRUCTION.replace_all(&mathml_str, "");
+    // FIX: need to deal with character data and convert to something the parser knows
+    let mathml_str = HTML_ENTITIES.replace_all(&mathml_str, |cap: &Captures| match HTML_ENTITIES_MAPPING.get(&cap[1]) {
+            None => {
+                error_message = format!("No entity named '{}'", &cap[0]);
+                cap[0].to_string()
             }
-            let mathml_str = MATHJAX_V2.replace_all(&mathml_str, "");
-            let mathml_str = MATHJAX_V3.replace_all(&mathml_str, "");
-
-            // the speech rules use the xpath "name" function and that includes the prefix
-            // getting rid of the prefix properly probably involves a recursive replacement in the tree
-            // if the prefix is used, it is almost certainly something like "m" or "mml", so this cheat will work.
-            let mathml_str = NAMESPACE_DECL.replace(&mathml_str, "xmlns"); // do this before the PREFIX replace!
-            let mathml_str = PREFIX.replace_all(&mathml_str, "$1");
-
-            let new_package = parser::parse(&mathml_str);
-            if let Err(e) = new_package {
-                // Clear stale state so subsequent API calls do not return previous user's data (security issue)
-                old_package.replace(parser::parse("<math></math>").unwrap());
-                bail!("Invalid MathML input:\n{}\nError is: {}", &mathml_str, &e.to_string());
-            }
-
-            let new_package = new_package.unwrap();
-            let mathml = get_element(&new_package);
-            let mathml = cleanup_mathml(mathml)?;
-            let mathml_string = mml_to_string(mathml);
-            old_package.replace(new_package);
-
-            return Ok(mathml_string);
+            Some(&ch) => ch.to_string(),
         });
-    }));
 
-    return report_any_panic(result);
+    if !error_message.is_empty() {
+        bail!(error_message);
+    }
+    let mathml_str = MATHJAX_V2.replace_all(&mathml_str, "");
+    let mathml_str = MATHJAX_V3.replace_all(&mathml_str, "");
+
+    // the speech rules use the xpath "name" function and that includes the prefix
+    // getting rid of the prefix properly probably involves a recursive replacement in the tree
+    // if the prefix is used, it is almost certainly something like "m" or "mml", so this cheat will work.
+    let mathml_str = NAMESPACE_DECL.replace(&mathml_str, "xmlns"); // do this before the PREFIX replace!
+    let mathml_str = PREFIX.replace_all(&mathml_str, "$1");
+
+    let new_package = match parser::parse(&mathml_str) {
+        Ok(package) => package,
+        Err(e) => bail!("Invalid MathML input:\n{}\nError is: {}", &mathml_str, &e.to_string()),
+    };
+
+    let mathml = get_element(&new_package);
+    cleanup_mathml(mathml)?;
+    return Ok(new_package);
 }
 
 /// Get the spoken text of the MathML that was set.
@@ -235,27 +267,78 @@ pub fn get_spoken_text() -> Result<String> {
         MATHML_INSTANCE.with(|package_instance| {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
-            let new_package = Package::new();
-            let intent = crate::speech::intent_from_mathml(mathml, new_package.as_document())?;
-            debug!("Intent tree:\n{}", mml_to_string(intent));
-            let speech = crate::speech::speak_mathml(intent, "", 0)?;
-            return Ok(speech);
+            return get_spoken_text_impl(mathml);
+        })
+    }));
+    return report_any_panic(result);
+}
+
+pub(crate) fn get_spoken_text_impl(mathml: Element) -> Result<String> {
+    let new_package = Package::new();
+    let intent = crate::speech::intent_from_mathml(mathml, new_package.as_document())?;
+    debug!("Intent tree:\n{}", mml_to_string(intent));
+    let speech = crate::speech::speak_mathml(intent, "", 0)?;
+    let prefs = crate::prefs::PreferenceManager::get();
+    let prefs = prefs.borrow();
+    if prefs.pref_to_string("SpeechSound") != "Beep" {
+        return Ok(speech);
+    }
+    let tts = prefs.get_tts();
+    return Ok( tts.earcon(&prefs, "MathStart") + &speech + &tts.earcon(&prefs, "MathEnd") );
+}
+
+/// Get the speech for the MathML that was set as a list of [`SpeechSegment`]s.
+/// Each segment has the text along with the `id` of the node it came from and how it should be spoken (pauses, pitch, etc.).
+/// This is meant for callers that drive the speech engine themselves and want to highlight what is being spoken;
+///   the `TTS` and `Bookmark` preferences are ignored.
+pub fn get_spoken_segments() -> Result<Vec<SpeechSegment>> {
+    enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            return get_spoken_segments_impl(mathml);
         })
     }));
     return report_any_panic(result);
 }
 
+pub(crate) fn get_spoken_segments_impl(mathml: Element) -> Result<Vec<SpeechSegment>> {
+    let speech = with_preferences(&[("TTS", "Segments"), ("Bookmark", "true")], || get_spoken_text_impl(mathml))?;
+    return Ok( crate::tts::speech_segments(&speech) );
+}
+
 /// Get the spoken text for an overview of the MathML that was set.
+/// The overview describes the structure of the expression (e.g., "an equation with a fraction on the left and a sum of 4 terms on the right").
+/// The amount of detail is controlled by the `OverviewDepth` (levels of structure to describe) and
+///   `OverviewLength` (maximum number of words) preferences.
 /// The speech takes into account any AT or user preferences.
-/// Note: this implementation for is currently minimal and should not be used.
 pub fn get_overview_text() -> Result<String> {
     enable_logs();
     let result = catch_unwind(AssertUnwindSafe(|| {
         MATHML_INSTANCE.with(|package_instance| {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
-            let speech = crate::speech::overview_mathml(mathml, "", 0)?;
-            return Ok(speech);
+            return get_overview_text_impl(mathml);
+        })
+    }));
+    return report_any_panic(result);
+}
+
+pub(crate) fn get_overview_text_impl(mathml: Element) -> Result<String> {
+    let new_package = Package::new();
+    let intent = crate::speech::intent_from_mathml(mathml, new_package.as_document())?;
+    return crate::speech::overview_mathml(intent);
+}
+
+/// Get the spoken text for an overview of the current navigation node (see [`get_overview_text`]).
+pub fn get_navigation_overview_text() -> Result<String> {
+    enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            return NAVIGATION_STATE.with(|nav_stack| overview_navigation_node(&nav_stack.borrow(), mathml));
         })
     }));
     return report_any_panic(result);
@@ -289,7 +372,7 @@ pub fn get_preference(name: impl AsRef<str>) -> Result<String> {
 /// The value should either be a string or a number (depending upon the preference being set)
 /// The list of known user preferences is in the MathCAT user documentation.
 /// Here are common preferences set by programs (not settable by the user):
-/// * TTS -- SSML, SAPI5, None
+/// * TTS -- SSML, SAPI5, Eloquence, Mac, None
 /// * Pitch -- normalized at '1.0'
 /// * Rate -- words per minute (should match current speech rate).
 ///   There is a separate "MathRate" that is user settable that causes a relative percentage change from this rate.
@@ -366,7 +449,8 @@ fn set_preference_impl(name: &str, value: &str) -> Result<()> {
         pref_manager.set_api_boolean_pref(name, value.to_lowercase() == "true");
     } else {
         match name {
-            "Pitch" | "Rate" | "Volume" | "CapitalLetters_Pitch" | "MathRate" | "PauseFactor" => {
+            "Pitch" | "Rate" | "Volume" | "CapitalLetters_Pitch" | "MathRate" | "PauseFactor" | "OverviewDepth" | "OverviewLength" |
+            "BrailleLineLength" => {
                 pref_manager.set_api_float_pref(name, to_float(name, &value)?)
             }
             _ => {
@@ -378,6 +462,35 @@ fn set_preference_impl(name: &str, value: &str) -> Result<()> {
     return Ok(());
 }
 
+/// Call `f` with the preferences in `prefs` (pairs of name and value) set, then restore their previous values.
+///
+/// This is meant for servers where each request can have its own preferences (e.g., `Language` or `BrailleCode`)
+/// on top of the preferences shared by the thread. The previous values are restored even if `f` returns an error.
+/// The compiled rules are shared by all threads, so changing preferences this way doesn't rebuild rules that some thread already built.
+pub fn with_preferences<T>(prefs: &[(&str, &str)], f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let mut old_prefs = Vec::with_capacity(prefs.len());
+    let mut result = Ok(());
+    for (name, value) in prefs {
+        result = get_preference(name)
+                .and_then(|old_value| {
+                    old_prefs.push((*name, old_value));
+                    set_preference(name, value)
+                });
+        if result.is_err() {
+            break;
+        }
+    }
+    let result = result.and_then(|_| f());
+
+    // restore in reverse order in case the same preference was set more than once
+    for (name, old_value) in old_prefs.into_iter().rev() {
+        if let Err(e) = set_preference(name, &old_value) {
+            error!("with_preferences: failed to restore '{}' to '{}': {}", name, old_value, errors_to_string(&e));
+        }
+    }
+    return result;
+}
+
 fn to_float(name: &str, value: &str) -> Result<f64> {
     return match value.parse::<f64>() {
         Ok(val) => Ok(val),
@@ -396,12 +509,68 @@ pub fn get_braille(nav_node_id: impl AsRef<str>) -> Result<String> {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
             let braille = crate::braille::braille_mathml(mathml, &nav_node_id)?.0;
-            return Ok(braille);
+            return crate::braille_encoding::encode_braille_using_prefs(&braille);
+        })
+    }));
+    return report_any_panic(result);
+}
+
+/// Get the braille associated with the MathML that was set by [`set_mathml`] broken into lines of at most `line_length` cells
+/// (e.g., 40 for a refreshable display or 32 for an embosser), along with the MathML node (id and offset) for each braille cell.
+/// Lines are broken where the braille code allows it (e.g., before an operator) and each `mtable` row starts on a new line.
+/// If 'nav_node_id' is given, it is highlighted based on the value of `BrailleNavHighlight`.
+///
+/// To get the same lines from [`get_braille`] (separated by '\n'), [`get_braille_position`], and [`get_navigation_node_from_braille_position`],
+/// set the `BrailleLineLength` preference. The '\n' between lines counts as a position.
+pub fn get_braille_lines(nav_node_id: impl AsRef<str>, line_length: usize) -> Result<BrailleLines> {
+    enable_logs();
+    let nav_node_id = nav_node_id.as_ref().to_string();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            return get_braille_lines_impl(mathml, &nav_node_id, line_length);
         })
     }));
     return report_any_panic(result);
 }
 
+pub(crate) fn get_braille_lines_impl(mathml: Element, nav_node_id: &str, line_length: usize) -> Result<BrailleLines> {
+    return with_preferences(&[("BrailleLineLength", &line_length.to_string())], || crate::braille::braille_lines(mathml, nav_node_id));
+}
+
+/// Convert braille in the encoding given by the `BrailleEncoding` preference (e.g., text from a BRF file) to Unicode braille.
+/// This is the inverse of the encoding done by [`get_braille`] and [`get_braille_lines`].
+pub fn braille_to_unicode(braille: impl AsRef<str>) -> Result<String> {
+    enable_logs();
+    let braille = braille.as_ref();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let encoding = crate::prefs::PreferenceManager::get().borrow().pref_to_string("BrailleEncoding");
+        return crate::braille_encoding::decode_braille(braille, &encoding);
+    }));
+    return report_any_panic(result);
+}
+
+/// Convert braille in the code given by the `BrailleCode` preference (currently Nemeth or UEB) to MathML.
+/// The braille can be in any encoding, as long as it matches the `BrailleEncoding` preference (see [`braille_to_unicode`]).
+/// The returned MathML can be passed to [`set_mathml`] (e.g., to hear what was written).
+pub fn braille_to_mathml(braille: impl AsRef<str>) -> Result<String> {
+    enable_logs();
+    let braille = braille.as_ref();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        // the speech definitions are used to recognize function names
+        crate::speech::SPEECH_RULES.with(|rules| rules.borrow_mut().read_files())?;
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        let (code, encoding) = {
+            let pref_manager = pref_manager.borrow();
+            (pref_manager.pref_to_string("BrailleCode"), pref_manager.pref_to_string("BrailleEncoding"))
+        };
+        let braille = crate::braille_encoding::decode_braille(braille, &encoding)?;
+        return crate::back_translate::braille_to_mathml(&braille, &code);
+    }));
+    return report_any_panic(result);
+}
+
 /// Get the braille associated with the current navigation focus of the MathML that was set by [`set_mathml`].
 /// The braille returned depends upon the preference for the `code` preference (default `Nemeth`).
 /// The returned braille is brailled as if the current navigation focus is the entire expression to be brailled.
@@ -411,54 +580,109 @@ pub fn get_navigation_braille() -> Result<String> {
         MATHML_INSTANCE.with(|package_instance| {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
-            let new_package = Package::new(); // used if we need to create a new tree
-            let new_doc = new_package.as_document();
-            let nav_mathml = NAVIGATION_STATE.with(|nav_stack| {
-                return match nav_stack.borrow_mut().get_navigation_mathml(mathml) {
-                    Err(e) => Err(e),
-                    Ok((found, offset)) => {
-                        // get the MathML node and wrap it inside of a <math> element
-                        // if the offset is given, we need to get the character it references
-                        if offset == 0 {
-                            if name(found) == "math" {
-                                Ok(found)
-                            } else {
-                                let new_mathml = create_mathml_element(&new_doc, "math");
-                                new_mathml.append_child(copy_mathml(found));
-                                new_doc.root().append_child(new_mathml);
-                                Ok(new_mathml)
-                            }
-                        } else if !is_leaf(found) {
-                            bail!(
-                                "Internal error: non-zero offset '{}' on a non-leaf element '{}'",
-                                offset,
-                                name(found)
-                            );
-                        } else if let Some(ch) = as_text(found).chars().nth(offset) {
-                            let internal_mathml = create_mathml_element(&new_doc, name(found));
-                            internal_mathml.set_text(&ch.to_string());
-                            let new_mathml = create_mathml_element(&new_doc, "math");
-                            new_mathml.append_child(internal_mathml);
-                            new_doc.root().append_child(new_mathml);
-                            Ok(new_mathml)
-                        } else {
-                            bail!(
-                                "Internal error: offset '{}' on leaf element '{}' doesn't exist",
-                                offset,
-                                mml_to_string(found)
-                            );
-                        }
-                    }
-                };
-            })?;
+            return NAVIGATION_STATE.with(|nav_stack| get_navigation_braille_impl(mathml, &nav_stack.borrow()));
+        })
+    }));
+    return report_any_panic(result);
+}
+
+pub(crate) fn get_navigation_braille_impl(mathml: Element, nav_state: &NavigationState) -> Result<String> {
+    let new_package = Package::new(); // used if we need to create a new tree
+    let nav_mathml = navigation_node_as_math(mathml, nav_state, new_package.as_document())?;
+    let braille = crate::braille::braille_mathml(nav_mathml, "")?.0;
+    return crate::braille_encoding::encode_braille_using_prefs(&braille);
+}
 
-            let braille = crate::braille::braille_mathml(nav_mathml, "")?.0;
-            return Ok(braille);
+/// Returns the navigation node as a `math` element (the node is copied into `new_doc` if it isn't `math`)
+/// If the node is a leaf with an offset, the `math` element contains just the character at the offset.
+fn navigation_node_as_math<'a>(mathml: Element<'a>, nav_state: &NavigationState, new_doc: Document<'a>) -> Result<Element<'a>> {
+    return match nav_state.get_navigation_mathml(mathml) {
+        Err(e) => Err(e),
+        Ok((found, offset)) => {
+            // get the MathML node and wrap it inside of a <math> element
+            // if the offset is given, we need to get the character it references
+            if offset == 0 {
+                if name(found) == "math" {
+                    Ok(found)
+                } else {
+                    let new_mathml = create_mathml_element(&new_doc, "math");
+                    new_mathml.append_child(copy_mathml(found));
+                    new_doc.root().append_child(new_mathml);
+                    Ok(new_mathml)
+                }
+            } else if !is_leaf(found) {
+                bail!(
+                    "Internal error: non-zero offset '{}' on a non-leaf element '{}'",
+                    offset,
+                    name(found)
+                );
+            } else if let Some(ch) = as_text(found).chars().nth(offset) {
+                let internal_mathml = create_mathml_element(&new_doc, name(found));
+                internal_mathml.set_text(&ch.to_string());
+                let new_mathml = create_mathml_element(&new_doc, "math");
+                new_mathml.append_child(internal_mathml);
+                new_doc.root().append_child(new_mathml);
+                Ok(new_mathml)
+            } else {
+                bail!(
+                    "Internal error: offset '{}' on leaf element '{}' doesn't exist",
+                    offset,
+                    mml_to_string(found)
+                );
+            }
+        }
+    };
+}
+
+/// Get the current navigation node in the format given by the `CopyAs` preference (`MathML`, `LaTeX`, or `ASCIIMath`).
+/// If there hasn't been any navigation, the whole expression is returned.
+/// This is meant for copying math to the clipboard, so LaTeX and ASCIIMath are returned as plain text (not as braille).
+/// For MathML, the `id`s and `data-` attributes that MathCAT adds are removed.
+pub fn get_navigation_copy() -> Result<String> {
+    enable_logs();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            return NAVIGATION_STATE.with(|nav_stack| get_navigation_copy_impl(mathml, &nav_stack.borrow()));
         })
     }));
     return report_any_panic(result);
 }
 
+pub(crate) fn get_navigation_copy_impl(mathml: Element, nav_state: &NavigationState) -> Result<String> {
+    let new_package = Package::new();
+    let nav_mathml = navigation_node_as_math(mathml, nav_state, new_package.as_document())?;
+    let copy_as = get_preference("CopyAs")?;
+    return match copy_as.as_str() {
+        "MathML" => {
+            let copy = copy_mathml(nav_mathml);
+            remove_added_attrs(copy);
+            Ok(mml_to_string(copy))
+        },
+        "LaTeX" => crate::latex::mathml_to_latex(nav_mathml),
+        "ASCIIMath" => crate::asciimath::mathml_to_asciimath(nav_mathml),
+        _ => bail!("Unknown value '{}' for the 'CopyAs' preference (should be 'MathML', 'LaTeX', or 'ASCIIMath')", copy_as),
+    };
+
+    /// Remove the `id`s that MathCAT added along with any `data-` attributes
+    fn remove_added_attrs(mathml: Element) {
+        if mathml.attribute_value("data-id-added").is_some() {
+            mathml.remove_attribute("id");
+        }
+        for attr in mathml.attributes() {
+            if attr.name().local_part().starts_with("data-") {
+                mathml.remove_attribute(attr.name());
+            }
+        }
+        for child in mathml.children() {
+            if let Some(child) = child.element() {
+                remove_added_attrs(child);
+            }
+        }
+    }
+}
+
 /// Given a key code along with the modifier keys, the current node is moved accordingly (or value reported in some cases).
 /// `key` is the [keycode](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/keyCode#constants_for_keycode_value) for the key (in JavaScript, `ev.key_code`)
 /// The spoken text for the new current node is returned.
@@ -493,6 +717,8 @@ pub fn do_navigate_keypress(
 ///   `MoveLastLocation`
 /// * Read commands (standard speech):
 ///   `ReadPrevious`, `ReadNext`, `ReadCurrent`, `ReadCellCurrent`, `ReadStart`, `ReadEnd`, `ReadLineStart`, `ReadLineEnd`
+/// * Read from the current node to the start/end of the expression or line (the current node doesn't change):
+///   `ReadToStart`, `ReadToEnd`, `ReadToLineStart`, `ReadToLineEnd`
 /// * Describe commands (overview):
 ///   `DescribePrevious`, `DescribeNext`, `DescribeCurrent`
 /// * Location information:
@@ -511,17 +737,15 @@ pub fn do_navigate_keypress(
 ///   `Describe0`, `Describe1`, `Describe2`, `Describe3`, `Describe4`, `Describe5`, `Describe6`, `Describe7`, `Describe8`, `Describe9`
 /// * Moving:
 ///   `MoveTo0`, `MoveTo1`, `MoveTo2`, `MoveTo3`, `MoveTo4`, `MoveTo5`, `MoveTo6`, `MoveTo7`, `MoveTo8`, `MoveTo9`
+/// * Reading from the current node to the place marker:
+///   `ReadTo0`, `ReadTo1`, `ReadTo2`, `ReadTo3`, `ReadTo4`, `ReadTo5`, `ReadTo6`, `ReadTo7`, `ReadTo8`, `ReadTo9`
 ///
 /// When done with Navigation, call with `Exit`
 pub fn do_navigate_command(command: impl AsRef<str>) -> Result<String> {
     enable_logs();
     let command = command.as_ref().to_string();
     let result = catch_unwind(AssertUnwindSafe(|| {
-        let cmd = NAV_COMMANDS.get_key(&command); // gets a &'static version of the command
-        if cmd.is_none() {
-            bail!("Unknown command in call to DoNavigateCommand()");
-        };
-        let cmd = *cmd.unwrap();
+        let cmd = nav_command_from_string(&command)?;
         MATHML_INSTANCE.with(|package_instance| {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
@@ -531,6 +755,14 @@ pub fn do_navigate_command(command: impl AsRef<str>) -> Result<String> {
     return report_any_panic(result);
 }
 
+/// Returns the `&'static` version of the navigation command (an error if it isn't a known command)
+pub(crate) fn nav_command_from_string(command: &str) -> Result<&'static str> {
+    return match NAV_COMMANDS.get_key(command) {
+        Some(cmd) => Ok(*cmd),
+        None => bail!("Unknown command in call to DoNavigateCommand()"),
+    };
+}
+
 /// Given an 'id' and an offset (for tokens), set the navigation node to that id.
 /// An error is returned if the 'id' doesn't exist
 pub fn set_navigation_node(id: impl AsRef<str>, offset: usize) -> Result<()> {
@@ -555,7 +787,7 @@ pub fn get_navigation_mathml() -> Result<(String, usize)> {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
             return NAVIGATION_STATE.with(|nav_stack| {
-                return match nav_stack.borrow_mut().get_navigation_mathml(mathml) {
+                return match nav_stack.borrow().get_navigation_mathml(mathml) {
                     Err(e) => Err(e),
                     Ok((found, offset)) => Ok((mml_to_string(found), offset)),
                 };
@@ -589,14 +821,18 @@ pub fn get_braille_position() -> Result<(usize, usize)> {
         MATHML_INSTANCE.with(|package_instance| {
             let package_instance = package_instance.borrow();
             let mathml = get_element(&package_instance);
-            let nav_node = get_navigation_mathml_id()?;
-            let (_, start, end) = crate::braille::braille_mathml(mathml, &nav_node.0)?;
-            return Ok((start, end));
+            return NAVIGATION_STATE.with(|nav_stack| get_braille_position_impl(mathml, &nav_stack.borrow()));
         })
     }));
     return report_any_panic(result);
 }
 
+pub(crate) fn get_braille_position_impl(mathml: Element, nav_state: &NavigationState) -> Result<(usize, usize)> {
+    let (nav_node_id, _) = nav_state.get_navigation_mathml_id(mathml);
+    let (_, start, end) = crate::braille::braille_mathml(mathml, &nav_node_id)?;
+    return Ok((start, end));
+}
+
 /// Given a 0-based braille position, return the smallest MathML node enclosing it.
 /// This node might be a leaf with an offset.
 pub fn get_navigation_node_from_braille_position(position: usize) -> Result<(String, usize)> {
@@ -806,6 +1042,11 @@ pub fn trim_element(e: Element, allow_structure_in_leaves: bool) {
     static WHITESPACE_MATCH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"[ \u{0009}\u{000A}\u{00C}\u{000D}]+"#).unwrap());
 
     if is_leaf(e) && (!allow_structure_in_leaves || IsNode::is_mathml(e)) {
+        if name(e) == "cn" && e.children().iter().any(|child| child.element().is_some_and(|sep| name(sep) == "sep")) {
+            // Content MathML's <sep/> separates the parts of a number (e.g., the numerator and denominator of a rational)
+            trim_cn_with_sep(e);
+            return;
+        }
         // Assume it is HTML inside of the leaf -- turn the HTML into a string
         make_leaf_element(e);
         return;
@@ -844,6 +1085,27 @@ pub fn trim_element(e: Element, allow_structure_in_leaves: bool) {
         e.set_text(&WHITESPACE_MATCH.replace_all(&single_text, " "));
     }
 
+    /// Trim the text around the `sep`s in 'cn', keeping the `sep`s (anything else is dropped)
+    fn trim_cn_with_sep(cn: Element) {
+        let doc = cn.document();
+        let mut children = vec![];
+        let mut text = "".to_string();
+        for child in cn.children() {
+            match child {
+                ChildOfElement::Text(t) => text += t.text(),
+                ChildOfElement::Element(sep) if name(sep) == "sep" => {
+                    children.push(ChildOfElement::Text(doc.create_text(text.trim_matches(WHITESPACE))));
+                    sep.clear_children();
+                    children.push(child);
+                    text.clear();
+                },
+                _ => (),
+            }
+        }
+        children.push(ChildOfElement::Text(doc.create_text(text.trim_matches(WHITESPACE))));
+        cn.replace_children(children);
+    }
+
     fn make_leaf_element(mathml_leaf: Element) {
         // MathML leaves like <mn> really shouldn't have non-textual content, but you could have embedded HTML
         // Here, we convert them to leaves by grabbing up all the text and making that the content
@@ -1262,6 +1524,68 @@ mod tests {
         assert_eq!(entity_str, converted_str, "special entities <,>,& test failed");
     }
 
+    #[test]
+    fn navigation_copy() {
+        set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_mathml("<math display='block'><mfrac id='frac'><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></mfrac>
+                    <mo>=</mo><msqrt><msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><mi>β</mi></msqrt></math>").unwrap();
+        set_preference("CopyAs", "LaTeX").unwrap();
+        assert_eq!(get_navigation_copy().unwrap(), r"\frac{x+1}{2}=\sqrt{a^2+\beta}");
+        set_preference("CopyAs", "ASCIIMath").unwrap();
+        assert_eq!(get_navigation_copy().unwrap(), "(x+1)/2 = sqrt(a^2+beta)");
+
+        set_navigation_node("frac", 0).unwrap();
+        assert_eq!(get_navigation_copy().unwrap(), "(x+1)/2");
+        set_preference("CopyAs", "LaTeX").unwrap();
+        assert_eq!(get_navigation_copy().unwrap(), r"\frac{x+1}{2}");
+        set_preference("CopyAs", "MathML").unwrap();
+        assert!(are_parsed_strs_equal(&get_navigation_copy().unwrap(),
+                "<math><mfrac id='frac'><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></mfrac></math>"));
+
+        // the braille code isn't changed
+        assert_eq!(get_preference("BrailleCode").unwrap(), "Nemeth");
+        set_preference("CopyAs", "Speech").unwrap();
+        assert!(get_navigation_copy().is_err());
+    }
+
+    #[test]
+    fn navigation_copy_text_and_integrals() {
+        set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        let copy_as = |copy_as: &str| {
+            set_preference("CopyAs", copy_as).unwrap();
+            return get_navigation_copy().unwrap();
+        };
+
+        set_mathml("<math><msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup><mi>x</mi><mi>d</mi><mi>x</mi></math>").unwrap();
+        assert_eq!(copy_as("LaTeX"), r"\int_0^1 x\,dx");
+        assert_eq!(copy_as("ASCIIMath"), "int_0^1 x dx");
+
+        set_mathml("<math><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mrow><mo>{</mo><mtable>
+                <mtr><mtd><mi>x</mi></mtd><mtd><mtext>if x&gt;0</mtext></mtd></mtr>
+                <mtr><mtd><mn>0</mn></mtd><mtd><mtext>otherwise</mtext></mtd></mtr>
+            </mtable></mrow></math>").unwrap();
+        assert_eq!(copy_as("LaTeX"), r"f(x)=\begin{cases}x & \text{if x>0} \\ 0 & \text{otherwise}\end{cases}");
+        assert_eq!(copy_as("ASCIIMath"), r#"f(x) = {(x, "if x>0"),(0, "otherwise"):}"#);
+
+        set_mathml("<math><mi>x</mi><mtext>&#xA0;if&#xA0;</mtext><mi>x</mi><mo>&gt;</mo><mn>0</mn></math>").unwrap();
+        assert_eq!(copy_as("LaTeX"), r"x\text{ if }x>0");
+        assert_eq!(copy_as("ASCIIMath"), r#"x " if " x > 0"#);
+
+        // function names, Greek letters, and scripts are separated from what follows them
+        set_mathml("<math><mi>sin</mi><mi>θ</mi><mo>+</mo><mi>α</mi><mi>x</mi><mo>+</mo>
+                <munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msup><mi>i</mi><mn>2</mn></msup>
+                <mo>≤</mo><mover><mi>x</mi><mo>¯</mo></mover><mo>+</mo><mi mathvariant='double-struck'>R</mi></math>").unwrap();
+        assert_eq!(copy_as("LaTeX"), r"\sin\theta+\alpha x+\sum_{i=1}^n i^2\le\overline{x}+\mathbb{R}");
+        assert_eq!(copy_as("ASCIIMath"), "sin theta+alpha x+sum_(i=1)^n i^2 <= bar(x)+RR");
+
+        set_mathml("<math><mrow><mo>[</mo><mtable>
+                <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+                <mtr><mtd><mi>x</mi></mtd><mtd><mi>y</mi></mtd></mtr>
+            </mtable><mo>]</mo></mrow></math>").unwrap();
+        assert_eq!(copy_as("LaTeX"), r"\begin{bmatrix}1 & 2 \\ x & y\end{bmatrix}");
+        assert_eq!(copy_as("ASCIIMath"), "[(1,2),(x,y)]");
+    }
+
     #[test]
     fn can_recover_from_invalid_set_rules_dir() {
         use std::env;
@@ -1359,7 +1683,7 @@ mod tests {
                 <mrow> <mi>x</mi><mo>-</mo><mi>y</mi> </mrow>
             </mfrac>
         </math>";
-        set_mathml(&expr)?;
+        set_mathml(expr)?;
         let speech = get_spoken_text()?;
         // Rule-generated SSML must pass through verbatim (not XML-entity-encoded).
         assert!(!speech.contains("&lt;"));
@@ -1368,6 +1692,117 @@ mod tests {
         return Ok(());
     }
 
+    #[test]
+    fn eloquence_and_mac_speech() -> Result<()> {
+        setup_speech_ssml();
+        set_preference("SpeechOverrides_CapitalLetters", "")?;
+        set_preference("CapitalLetters_Pitch", "30")?;
+        set_mathml("<math><mfrac><mi>X</mi><mi>y</mi></mfrac><mo>+</mo><mn>1</mn></math>")?;
+        set_preference("TTS", "Eloquence")?;
+        assert_eq!(get_spoken_text()?, "`vs19  cap `vb37 x`vb26  over y `p200   plus  1`vs27");
+        set_preference("TTS", "Mac")?;
+        assert_eq!(get_spoken_text()?,
+                   "[[rate -36]] cap [[pbas +4.5]][[char LTRL]]x[[char NORM]][[pbas -4.5]] over [[char LTRL]]y[[char NORM]] [[slnc 200]]  plus  1[[rate +36]]");
+        return Ok(());
+    }
+
+    #[test]
+    fn spoken_segments() -> Result<()> {
+        setup_speech_ssml();
+        set_preference("SpeechOverrides_CapitalLetters", "")?;
+        set_preference("CapitalLetters_Pitch", "30")?;
+        set_mathml("<math id='m'><mrow id='r'><mfrac id='f'><mi id='X'>X</mi><mi id='y'>y</mi></mfrac><mo id='plus'>+</mo><mn id='one'>1</mn></mrow></math>")?;
+        let segments = get_spoken_segments()?;
+        let texts = segments.iter().map(|segment| (segment.text.as_str(), segment.id.as_str())).collect::<Vec<_>>();
+        assert_eq!(texts, vec![("cap", "X"), ("x", "X"), ("over", "X"), ("y", "y"), ("plus", "plus"), ("1", "one")]);
+        assert!(segments.iter().all(|segment| segment.rate == 80.0 && segment.volume == 0.0 && segment.pronounce.is_none()));
+        assert_eq!((segments[1].pitch, segments[1].spell), (30.0, true));
+        assert_eq!((segments[2].pitch, segments[2].spell), (0.0, false));
+        assert_eq!(segments[3].pause, 200.0);
+        // the TTS and Bookmark preferences are not changed
+        assert_eq!(get_preference("TTS")?, "SSML");
+        assert_eq!(get_preference("Bookmark")?, "false");
+        return Ok(());
+    }
+
+    #[test]
+    fn speech_overrides() -> Result<()> {
+        setup_speech_ssml();
+        set_preference("TTS", "None")?;
+        set_mathml("<math><mn>2</mn><mo>(</mo><mi>x</mi><mo>&#x2212;</mo><mn>1</mn><mo>)</mo></math>")?;
+        assert_eq!(get_spoken_text()?, "2 times, open paren x minus 1, close paren");
+        set_preference("SpeechOverrides_LeftParen", "left paren")?;
+        set_preference("SpeechOverrides_RightParen", "right paren")?;
+        set_preference("SpeechOverrides_Symbols_-", "take away")?;
+        assert_eq!(get_spoken_text()?, "2 times, left paren x take away 1, right paren");
+        assert_eq!(get_preference("SpeechOverrides_Symbols_-")?, "take away");
+        set_preference("SpeechOverrides_LeftParen", "")?;
+        set_preference("SpeechOverrides_RightParen", "")?;
+        set_preference("SpeechOverrides_Symbols_-", "")?;
+        assert_eq!(get_spoken_text()?, "2 times, open paren x minus 1, close paren");
+        assert!(set_preference("SpeechOverrides_Symbols_", "oops").is_err());
+
+        // intent names can be overridden also
+        set_mathml("<math><mrow intent='plus-or-minus($a,$b)'><mi arg='a'>x</mi><mo>±</mo><mn arg='b'>1</mn></mrow></math>")?;
+        assert_eq!(get_spoken_text()?, "x plus or minus 1");
+        set_preference("SpeechOverrides_Symbols_plus-or-minus", "give or take")?;
+        assert_eq!(get_spoken_text()?, "x give or take 1");
+        set_preference("SpeechOverrides_Symbols_plus-or-minus", "")?;
+        return Ok(());
+    }
+
+    #[test]
+    fn earcons() -> Result<()> {
+        setup_speech_ssml();
+        set_preference("MathRate", "100")?;
+        set_preference("SpeechSound", "Beep")?;
+        set_preference("CapitalLetters_Beep", "true")?;
+        set_preference("Earcons_MathStart", "start.wav")?;
+        set_mathml("<math><mi>X</mi><mo>+</mo><mn>1</mn></math>")?;
+        assert_eq!(get_spoken_text()?,
+                   "<audio src='start.wav'></audio><audio src='beep.mp4'></audio> cap <say-as interpret-as='characters'>x</say-as>  plus  1<audio src='math-end.mp4'></audio>");
+        set_preference("TTS", "SAPI5")?;
+        assert_eq!(get_spoken_text()?,
+                   "<wav src='start.wav'></wav><wav src='beep.mp4'></wav> cap <spell>x</spell>  plus  1<wav src='math-end.mp4'></wav>");
+        let segments = get_spoken_segments()?;
+        let sounds = segments.iter().map(|segment| (segment.text.as_str(), segment.audio.as_deref())).collect::<Vec<_>>();
+        assert_eq!(sounds, vec![("", Some("start.wav")), ("", Some("beep.mp4")), ("cap", None), ("x", None), ("plus", None), ("1", None), ("", Some("math-end.mp4"))]);
+        set_preference("TTS", "SSML")?;
+        set_preference("NavVerbosity", "Verbose")?;
+        do_navigate_command("MoveEnd")?;
+        assert_eq!(do_navigate_command("MoveNext")?,
+                   "<audio src='boundary.mp4'></audio> cannot move right <break time='200ms'/> end of math <break time='800ms'/>");
+        set_preference("SpeechSound", "None")?;
+        set_preference("CapitalLetters_Beep", "false")?;
+        assert_eq!(get_spoken_text()?, "cap <say-as interpret-as='characters'>x</say-as>  plus  1");
+        return Ok(());
+    }
+
+    #[test]
+    fn earcons_at_boundaries() -> Result<()> {
+        setup_speech_ssml();
+        set_preference("MathRate", "100")?;
+        set_preference("SpeechSound", "Beep")?;
+        set_preference("NavVerbosity", "Terse")?;
+        set_preference("Earcons_Boundary", "it's <edge>.wav")?;
+        set_mathml("<math><mi>x</mi><mo>+</mo><mn>1</mn></math>")?;
+        for language in ["en", "de"] {
+            set_preference("Language", language)?;
+            do_navigate_command("MoveStart")?;
+            assert!(!do_navigate_command("ZoomOut")?.contains("<audio"), "ZoomOut in '{language}'");
+            assert!(do_navigate_command("ZoomOut")?.starts_with("<audio src='it&#x27;s &lt;edge&gt;.wav'></audio>"), "ZoomOut in '{language}'");
+            assert!(do_navigate_command("MovePrevious")?.starts_with("<audio src='it&#x27;s &lt;edge&gt;.wav'></audio>"), "MovePrevious in '{language}'");
+            do_navigate_command("ZoomIn")?;
+            assert!(do_navigate_command("ZoomIn")?.starts_with("<audio src='it&#x27;s &lt;edge&gt;.wav'></audio>"), "ZoomIn in '{language}'");
+            assert!(!do_navigate_command("MoveNext")?.contains("<audio"), "MoveNext in '{language}'");
+            do_navigate_command("MoveEnd")?;
+            assert!(do_navigate_command("MoveNext")?.starts_with("<audio src='it&#x27;s &lt;edge&gt;.wav'></audio>"), "MoveNext in '{language}'");
+        }
+        set_preference("TTS", "SAPI5")?;
+        assert!(do_navigate_command("MoveNext")?.starts_with("<wav src='it&#x27;s &lt;edge&gt;.wav'></wav>"));
+        return Ok(());
+    }
+
     /// The attack payload must not pass through verbatim (rule-generated SSML may contain `<break`).
     fn assert_ssml_attack_neutralized(speech: &str, illegal_ssml: &str) {
         assert!(
@@ -1447,4 +1882,88 @@ mod tests {
         })?;
         return Ok(());
     }
+
+    #[test]
+    fn braille_lines() -> Result<()> {
+        set_rules_dir(super::super::abs_rules_dir_path())?;
+        set_preference("Language", "en")?;
+        set_preference("BrailleCode", "Nemeth")?;
+        set_mathml("<math><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>=</mo>
+                    <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>12345</mn><mi>y</mi><mo>-</mo><mn>7</mn><mo>+</mo><mi>z</mi></math>")?;
+        let braille = get_braille_lines("", 12)?;
+        assert_eq!(braille.lines, ["⠁⠬⠃⠬⠹⠂⠌⠆⠼⠐", "⠀⠀⠨⠅⠀⠭⠘⠆⠐⠐", "⠀⠀⠬⠂⠆⠒⠲⠢⠽⠤⠶⠐", "⠀⠀⠬⠵"]);
+        for (line, cells) in braille.lines.iter().zip(&braille.cells) {
+            assert_eq!(line.chars().count(), cells.len());
+        }
+        // '⠬' at the start of the third line is the '+' before 12345
+        let (plus_id, offset) = &braille.cells[2][2];
+        assert_eq!(*offset, 0);
+        let plus_braille_position = braille.lines[..2].iter().map(|line| line.chars().count() + 1).sum::<usize>() + 2;
+
+        // the preference gives the same lines and positions take the line breaks into account
+        with_preferences(&[("BrailleLineLength", "12")], || {
+            assert_eq!(get_braille("")?, braille.lines.join("\n"));
+            assert_eq!(&get_navigation_node_from_braille_position(plus_braille_position)?.0, plus_id);
+            set_navigation_node(plus_id, 0)?;
+            assert_eq!(get_braille_position()?, (plus_braille_position, plus_braille_position + 1));
+            return Ok(());
+        })?;
+        assert_eq!(get_braille("")?, "⠁⠬⠃⠬⠹⠂⠌⠆⠼⠀⠨⠅⠀⠭⠘⠆⠐⠬⠂⠆⠒⠲⠢⠽⠤⠶⠬⠵");
+        return Ok(());
+    }
+
+    #[test]
+    fn braille_encoding() -> Result<()> {
+        set_rules_dir(super::super::abs_rules_dir_path())?;
+        set_preference("Language", "en")?;
+        set_preference("BrailleCode", "Nemeth")?;
+        set_mathml("<math><msup><mi>x</mi><mn>2</mn></msup><mo>=</mo><mn>4</mn></math>")?;
+        let unicode = get_braille("")?;
+        assert_eq!(unicode, "⠭⠘⠆⠀⠨⠅⠀⠼⠲");
+        let ascii = with_preferences(&[("BrailleEncoding", "ASCII-BRF")], || {
+            let ascii = get_braille("")?;
+            assert_eq!(braille_to_unicode(&ascii)?, unicode);
+            assert_eq!(braille_to_mathml(&ascii)?, "<math><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>=</mo><mn>4</mn></mrow></math>");
+            return Ok(ascii);
+        })?;
+        assert_eq!(ascii, "X^2 .K #4");
+        with_preferences(&[("BrailleEncoding", "Eurobraille")], || {
+            assert_eq!(braille_to_unicode(get_braille("")?)?, unicode);
+            return Ok(());
+        })?;
+        return Ok(());
+    }
+
+    #[test]
+    fn with_preferences_restores_prefs() -> Result<()> {
+        set_rules_dir(super::super::abs_rules_dir_path())?;
+        set_preference("Language", "en")?;
+        set_preference("SpeechStyle", "SimpleSpeak")?;
+        set_mathml("<math><mfrac><mi>x</mi><mi>y</mi></mfrac></math>")?;
+        let speech = with_preferences(&[("SpeechStyle", "ClearSpeak")], get_spoken_text)?;
+        assert_eq!(speech, "x over y");
+        assert_eq!(get_preference("SpeechStyle")?, "SimpleSpeak");
+
+        // restored even when there is an error
+        assert!(with_preferences(&[("Language", "es"), ("Language", "not a language")], get_spoken_text).is_err());
+        assert_eq!(get_preference("Language")?, "en");
+        return Ok(());
+    }
+
+    #[test]
+    fn parallel_translations() {
+        let handles = [("en", "x over y"), ("es", "x partido por y"), ("en", "x over y"), ("es", "x partido por y")].into_iter()
+            .map(|(lang, expected)| std::thread::spawn(move || {
+                set_rules_dir(super::super::abs_rules_dir_path())?;
+                set_preference("SpeechStyle", "ClearSpeak")?;
+                let doc = crate::MathDocument::new("<math><mfrac><mi>x</mi><mi>y</mi></mfrac></math>")?;
+                let speech = with_preferences(&[("Language", lang)], || doc.get_spoken_text())?;
+                assert_eq!(speech, expected);
+                return Ok::<(), Error>(());
+            }))
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+    }
 }
@@ -0,0 +1,1200 @@
+#![allow(clippy::needless_return)]
+//! Convert LaTeX math into MathML so that TeX (e.g., from `alt` text or `$...$` in Markdown) can be passed to MathCAT.
+//!
+//! This handles a practical subset of LaTeX math: fractions, scripts, roots, `\left...\right`, accents, fonts
+//! (`\mathbb`, ...), `\operatorname`, `\text`, and the common environments (matrix variants, `cases`, `array`, `align`, ...).
+//! Macro definitions are not supported.
+//!
+//! The MathML that is generated is not canonical; it goes through the same cleanup as MathML passed to `set_mathml`.
+//! Errors give the (character) position in the TeX source where the problem was found.
+
+use anyhow::{Result, anyhow, bail};
+use phf::{phf_map, phf_set};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use sxd_document::dom::Element;
+use crate::canonicalize::{as_element, as_text, name};
+use crate::xpath_functions::is_leaf;
+
+/// Greek letters and other letter-like commands that are identifiers
+static IDENTIFIERS: phf::Map<&str, &str> = phf_map! {
+	"alpha" => "α", "beta" => "β", "gamma" => "γ", "delta" => "δ", "epsilon" => "ϵ", "varepsilon" => "ε",
+	"zeta" => "ζ", "eta" => "η", "theta" => "θ", "vartheta" => "ϑ", "iota" => "ι", "kappa" => "κ",
+	"lambda" => "λ", "mu" => "μ", "nu" => "ν", "xi" => "ξ", "pi" => "π", "varpi" => "ϖ", "rho" => "ρ",
+	"varrho" => "ϱ", "sigma" => "σ", "varsigma" => "ς", "tau" => "τ", "upsilon" => "υ", "phi" => "ϕ",
+	"varphi" => "φ", "chi" => "χ", "psi" => "ψ", "omega" => "ω",
+	"Gamma" => "Γ", "Delta" => "Δ", "Theta" => "Θ", "Lambda" => "Λ", "Xi" => "Ξ", "Pi" => "Π",
+	"Sigma" => "Σ", "Upsilon" => "Υ", "Phi" => "Φ", "Psi" => "Ψ", "Omega" => "Ω",
+	"infty" => "∞", "partial" => "∂", "nabla" => "∇", "emptyset" => "∅", "varnothing" => "∅",
+	"ell" => "ℓ", "hbar" => "ℏ", "Re" => "ℜ", "Im" => "ℑ", "aleph" => "ℵ", "wp" => "℘", "imath" => "ı", "jmath" => "ȷ",
+	"angle" => "∠", "triangle" => "△", "top" => "⊤", "bot" => "⊥", "degree" => "°",
+};
+
+/// Commands that are operators (the value is the operator character)
+static OPERATORS: phf::Map<&str, &str> = phf_map! {
+	"times" => "×", "cdot" => "⋅", "pm" => "±", "mp" => "∓", "div" => "÷", "ast" => "∗", "star" => "⋆", "circ" => "∘",
+	"bullet" => "∙", "oplus" => "⊕", "ominus" => "⊖", "otimes" => "⊗", "odot" => "⊙", "setminus" => "∖",
+	"cup" => "∪", "cap" => "∩", "wedge" => "∧", "land" => "∧", "vee" => "∨", "lor" => "∨", "neg" => "¬", "lnot" => "¬",
+	"leq" => "≤", "le" => "≤", "geq" => "≥", "ge" => "≥", "neq" => "≠", "ne" => "≠", "ll" => "≪", "gg" => "≫",
+	"approx" => "≈", "equiv" => "≡", "sim" => "∼", "simeq" => "≃", "cong" => "≅", "propto" => "∝", "doteq" => "≐",
+	"in" => "∈", "notin" => "∉", "ni" => "∋", "subset" => "⊂", "subseteq" => "⊆", "supset" => "⊃", "supseteq" => "⊇",
+	"subsetneq" => "⊊", "supsetneq" => "⊋", "mid" => "|", "nmid" => "∤", "parallel" => "∥", "perp" => "⊥",
+	"to" => "→", "rightarrow" => "→", "leftarrow" => "←", "gets" => "←", "leftrightarrow" => "↔", "mapsto" => "↦",
+	"Rightarrow" => "⇒", "Leftarrow" => "⇐", "Leftrightarrow" => "⇔", "implies" => "⟹", "impliedby" => "⟸", "iff" => "⟺",
+	"longrightarrow" => "⟶", "longleftarrow" => "⟵", "uparrow" => "↑", "downarrow" => "↓",
+	"forall" => "∀", "exists" => "∃", "nexists" => "∄", "therefore" => "∴", "because" => "∵",
+	"ldots" => "…", "dots" => "…", "cdots" => "⋯", "vdots" => "⋮", "ddots" => "⋱",
+	"prime" => "′", "colon" => ":", "vert" => "|", "Vert" => "‖", "|" => "‖", "{" => "{", "}" => "}",
+	"langle" => "⟨", "rangle" => "⟩", "lfloor" => "⌊", "rfloor" => "⌋", "lceil" => "⌈", "rceil" => "⌉",
+	"lvert" => "|", "rvert" => "|", "lVert" => "‖", "rVert" => "‖", "backslash" => "∖",
+	"mod" => "mod", "bmod" => "mod",
+};
+
+/// Large operators: the operator and whether its scripts are written as limits (under/over)
+static LARGE_OPERATORS: phf::Map<&str, (&str, bool)> = phf_map! {
+	"sum" => ("∑", true), "prod" => ("∏", true), "coprod" => ("∐", true),
+	"bigcup" => ("⋃", true), "bigcap" => ("⋂", true), "bigvee" => ("⋁", true), "bigwedge" => ("⋀", true),
+	"bigoplus" => ("⨁", true), "bigotimes" => ("⨂", true), "bigodot" => ("⨀", true), "biguplus" => ("⨄", true),
+	"int" => ("∫", false), "iint" => ("∬", false), "iiint" => ("∭", false), "oint" => ("∮", false),
+};
+
+/// Function names; the value is true if the scripts are written as limits (e.g., `\lim_{x \to 0}`)
+static FUNCTIONS: phf::Map<&str, bool> = phf_map! {
+	"sin" => false, "cos" => false, "tan" => false, "sec" => false, "csc" => false, "cot" => false,
+	"sinh" => false, "cosh" => false, "tanh" => false, "coth" => false,
+	"arcsin" => false, "arccos" => false, "arctan" => false,
+	"log" => false, "ln" => false, "lg" => false, "exp" => false, "arg" => false, "deg" => false, "dim" => false,
+	"ker" => false, "hom" => false,
+	"lim" => true, "limsup" => true, "liminf" => true, "max" => true, "min" => true, "sup" => true, "inf" => true,
+	"det" => true, "gcd" => true, "Pr" => true,
+};
+
+/// Accents: the character and whether it goes over (true) or under the base
+static ACCENTS: phf::Map<&str, (&str, bool)> = phf_map! {
+	"hat" => ("^", true), "widehat" => ("^", true), "bar" => ("¯", true), "overline" => ("¯", true),
+	"vec" => ("→", true), "overrightarrow" => ("→", true), "overleftarrow" => ("←", true),
+	"dot" => ("˙", true), "ddot" => ("¨", true), "tilde" => ("~", true), "widetilde" => ("~", true),
+	"check" => ("ˇ", true), "breve" => ("˘", true), "acute" => ("´", true), "grave" => ("`", true),
+	"overbrace" => ("⏞", true), "underbrace" => ("⏟", false), "underline" => ("_", false),
+};
+
+static FONTS: phf::Map<&str, &str> = phf_map! {
+	"mathbb" => "double-struck", "mathbf" => "bold", "mathit" => "italic", "mathrm" => "normal",
+	"mathcal" => "script", "mathscr" => "script", "mathfrak" => "fraktur", "mathsf" => "sans-serif",
+	"mathtt" => "monospace", "boldsymbol" => "bold-italic", "bm" => "bold-italic",
+};
+
+static SPACES: phf::Map<&str, &str> = phf_map! {
+	"," => "0.167em", ":" => "0.222em", ">" => "0.222em", ";" => "0.278em", " " => "0.333em",
+	"quad" => "1em", "qquad" => "2em", "enspace" => "0.5em",
+};
+
+/// Commands that only affect the typesetting and so are ignored
+static IGNORED: phf::Set<&str> = phf_set! {
+	"displaystyle", "textstyle", "scriptstyle", "scriptscriptstyle", "nolimits", "!",
+	"big", "Big", "bigg", "Bigg", "bigl", "bigr", "Bigl", "Bigr", "biggl", "biggr", "Biggl", "Biggr", "nonumber", "notag",
+};
+
+/// Environments that are tables (the value is the open and close fences, "" for none)
+static TABLE_ENVIRONMENTS: phf::Map<&str, (&str, &str)> = phf_map! {
+	"matrix" => ("", ""), "smallmatrix" => ("", ""), "array" => ("", ""),
+	"pmatrix" => ("(", ")"), "bmatrix" => ("[", "]"), "Bmatrix" => ("{", "}"), "vmatrix" => ("|", "|"), "Vmatrix" => ("‖", "‖"),
+	"cases" => ("{", ""), "dcases" => ("{", ""), "rcases" => ("", "}"),
+	"align" => ("", ""), "align*" => ("", ""), "aligned" => ("", ""), "alignat" => ("", ""), "alignat*" => ("", ""),
+	"gather" => ("", ""), "gather*" => ("", ""), "gathered" => ("", ""), "split" => ("", ""),
+	"eqnarray" => ("", ""), "eqnarray*" => ("", ""), "equation" => ("", ""), "equation*" => ("", ""),
+};
+
+/// Convert 'latex' to a MathML string.
+/// The math delimiters `$...$`, `$$...$$`, `\(...\)`, and `\[...\]` are optional.
+pub fn latex_to_mathml(latex: &str) -> Result<String> {
+	let trimmed = latex.trim();
+	let n_leading_spaces = latex.chars().count() - latex.trim_start().chars().count();
+	let n_trailing_spaces = latex.chars().count() - latex.trim_end().chars().count();
+	let (start, end, display) = [("$$", "$$", true), ("\\[", "\\]", true), ("$", "$", false), ("\\(", "\\)", false)].iter()
+		.find(|&&(start, end, _)| trimmed.len() >= start.len() + end.len() && trimmed.starts_with(start) && trimmed.ends_with(end))
+		.map_or((0, 0, false), |&(start, end, display)| (start.len(), end.len(), display));
+	let mut parser = LaTeXParser::new(latex, n_leading_spaces + start, latex.chars().count() - n_trailing_spaces - end);
+	let items = parser.parse_list(Stop::EndOfInput)?;
+	let display = if display {" display='block'"} else {""};
+	return Ok( format!("<math{}>{}</math>", display, mrow(items)) );
+}
+
+/// What ends a list of items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+	EndOfInput,
+	CloseBrace,
+	CloseBracket,		// optional argument (e.g., `\sqrt[3]{x}`)
+	Right,				// `\right`
+	Cell,				// `&`, `\\`, or `\end`
+}
+
+/// Result of parsing an atom: the MathML and whether scripts should be written as limits
+struct Atom {
+	mathml: String,
+	limits: bool,
+}
+
+struct LaTeXParser<'s> {
+	source: &'s str,
+	chars: Vec<char>,
+	i: usize,
+	mathvariant: Option<&'static str>,
+}
+
+impl<'s> LaTeXParser<'s> {
+	/// Parse the chars in 'source' from 'start' up to 'end' (positions in errors are relative to all of 'source')
+	fn new(source: &'s str, start: usize, end: usize) -> LaTeXParser<'s> {
+		let chars = source.chars().take(end).collect();
+		return LaTeXParser { source, chars, i: start, mathvariant: None };
+	}
+
+	/// An error that points to position 'at' in the source
+	fn error(&self, at: usize, message: &str) -> anyhow::Error {
+		let line: String = self.source.chars().map(|ch| if ch == '\n' || ch == '\r' {' '} else {ch}).collect();
+		return anyhow!("LaTeX error at position {}: {}\n  {}\n  {}^", at + 1, message, line, " ".repeat(at));
+	}
+
+	fn peek(&self) -> Option<char> {
+		return self.chars.get(self.i).copied();
+	}
+
+	fn skip_white_space(&mut self) {
+		while self.peek().is_some_and(|ch| ch.is_whitespace()) {
+			self.i += 1;
+		}
+	}
+
+	/// If the next thing is a command, return its name without consuming it
+	fn peek_command(&self) -> Option<String> {
+		if self.peek() != Some('\\') {
+			return None;
+		}
+		return Some( self.command_at(self.i + 1).0 );
+	}
+
+	/// Return the command name that starts at 'start' (after the '\') and the position after it
+	fn command_at(&self, start: usize) -> (String, usize) {
+		let mut end = start;
+		while self.chars.get(end).is_some_and(|ch| ch.is_ascii_alphabetic()) {
+			end += 1;
+		}
+		if end == start && end < self.chars.len() {
+			end += 1;			// a single non-letter (e.g., `\,` or `\{`)
+		} else if self.chars.get(end) == Some(&'*') && matches!(self.chars[start..end].iter().collect::<String>().as_str(), "operatorname") {
+			end += 1;
+		}
+		return (self.chars[start..end].iter().collect(), end);
+	}
+
+	fn read_command(&mut self) -> String {
+		let (command, end) = self.command_at(self.i + 1);
+		self.i = end;
+		return command;
+	}
+
+	fn expect(&mut self, ch: char) -> Result<()> {
+		self.skip_white_space();
+		if self.peek() != Some(ch) {
+			return Err( self.error(self.i, &format!("expected '{ch}'")) );
+		}
+		self.i += 1;
+		return Ok( () );
+	}
+
+	/// Return the raw text inside of `{...}` (used for `\text`, environment names, ...)
+	fn read_raw_group(&mut self) -> Result<String> {
+		self.expect('{')?;
+		let start = self.i;
+		let mut depth = 0;
+		while let Some(ch) = self.peek() {
+			match ch {
+				'{' => depth += 1,
+				'}' if depth == 0 => {
+					let text = self.chars[start..self.i].iter().collect();
+					self.i += 1;
+					return Ok(text);
+				},
+				'}' => depth -= 1,
+				'\\' => self.i += 1,		// skip escaped char
+				_ => (),
+			}
+			self.i += 1;
+		}
+		return Err( self.error(start - 1, "missing '}'") );
+	}
+
+	/// Parse items until 'stop' is found. The stopping token is not consumed (except for '}' and ']').
+	fn parse_list(&mut self, stop: Stop) -> Result<Vec<String>> {
+		let start = self.i;
+		let mut items = vec![];
+		loop {
+			self.skip_white_space();
+			let Some(ch) = self.peek() else {
+				return match stop {
+					Stop::EndOfInput => Ok(items),
+					Stop::CloseBrace => Err( self.error(start.saturating_sub(1), "missing '}'") ),
+					Stop::CloseBracket => Err( self.error(start.saturating_sub(1), "missing ']'") ),
+					Stop::Right => Err( self.error(start, "'\\left' without a matching '\\right'") ),
+					Stop::Cell => Err( self.error(start, "missing '\\end'") ),
+				};
+			};
+			match ch {
+				'}' => {
+					if stop == Stop::CloseBrace {
+						self.i += 1;
+						return Ok(items);
+					}
+					return Err( self.error(self.i, "unexpected '}'") );
+				},
+				']' if stop == Stop::CloseBracket => {
+					self.i += 1;
+					return Ok(items);
+				},
+				'&' => {
+					if stop == Stop::Cell {
+						return Ok(items);
+					}
+					return Err( self.error(self.i, "'&' is only allowed in an environment such as 'matrix'") );
+				},
+				'\\' => {
+					let command = self.peek_command().unwrap_or_default();
+					match command.as_str() {
+						"\\" | "cr" | "end" => {
+							if stop == Stop::Cell {
+								return Ok(items);
+							}
+							return Err( self.error(self.i, &format!("'\\{command}' is only allowed in an environment")) );
+						},
+						"right" => {
+							if stop == Stop::Right {
+								return Ok(items);
+							}
+							return Err( self.error(self.i, "'\\right' without a matching '\\left'") );
+						},
+						_ => (),
+					}
+				},
+				_ => (),
+			}
+			if let Some(item) = self.parse_scripted()? {
+				items.push(item);
+			}
+		}
+	}
+
+	/// Parse an atom and any scripts (and primes) that follow it
+	fn parse_scripted(&mut self) -> Result<Option<String>> {
+		let start = self.i;
+		let base = match self.peek() {
+			Some('^' | '_') => Some( Atom { mathml: "<mrow></mrow>".to_string(), limits: false } ),
+			_ => self.parse_atom()?,
+		};
+		let Some(mut base) = base else {
+			return Ok(None);
+		};
+		let mut sub = None;
+		let mut sup = None;
+		loop {
+			self.skip_white_space();
+			match self.peek() {
+				Some('_') => {
+					if sub.is_some() {
+						return Err( self.error(self.i, "double subscript") );
+					}
+					self.i += 1;
+					sub = Some( self.parse_argument()? );
+				},
+				Some('^') => {
+					if sup.is_some() {
+						return Err( self.error(self.i, "double superscript") );
+					}
+					self.i += 1;
+					sup = Some( self.parse_argument()? );
+				},
+				Some('\'') => {
+					let mut primes = String::new();
+					while self.peek() == Some('\'') {
+						primes.push('′');
+						self.i += 1;
+					}
+					let primes = primes.replace("′′′", "‴").replace("′′", "″");
+					sup = Some( format!("<mo>{primes}</mo>") );
+				},
+				Some('\\') if self.peek_command().as_deref() == Some("limits") => {
+					self.read_command();
+					base.limits = true;
+				},
+				Some('\\') if self.peek_command().as_deref() == Some("nolimits") => {
+					self.read_command();
+					base.limits = false;
+				},
+				_ => break,
+			}
+		}
+		if start == self.i {
+			return Err( self.error(start, "internal error -- no progress") );
+		}
+		let (both, only_sub, only_sup) = if base.limits {("munderover", "munder", "mover")} else {("msubsup", "msub", "msup")};
+		let mathml = match (sub, sup) {
+			(Some(sub), Some(sup)) => format!("<{both}>{}{sub}{sup}</{both}>", base.mathml),
+			(Some(sub), None) => format!("<{only_sub}>{}{sub}</{only_sub}>", base.mathml),
+			(None, Some(sup)) => format!("<{only_sup}>{}{sup}</{only_sup}>", base.mathml),
+			(None, None) => base.mathml,
+		};
+		return Ok( Some(mathml) );
+	}
+
+	/// Parse a required argument: a `{...}` group, a single character, or a command
+	fn parse_argument(&mut self) -> Result<String> {
+		self.skip_white_space();
+		let start = self.i;
+		return match self.peek() {
+			None => Err( self.error(start, "missing argument") ),
+			Some('{') => {
+				self.i += 1;
+				Ok( mrow(self.parse_list(Stop::CloseBrace)?) )
+			},
+			Some('}' | '&' | '^' | '_') => Err( self.error(start, "missing argument") ),
+			Some('\\') => match self.parse_atom()? {
+				Some(atom) => Ok(atom.mathml),
+				None => Err( self.error(start, "missing argument") ),
+			},
+			Some(ch) => {
+				self.i += 1;
+				Ok( self.char_to_mathml(ch) )
+			},
+		};
+	}
+
+	/// Parse the next atom (without scripts). Returns None for something that produces no output (e.g., `\displaystyle`).
+	fn parse_atom(&mut self) -> Result<Option<Atom>> {
+		let start = self.i;
+		let Some(ch) = self.peek() else {
+			return Ok(None);
+		};
+		let mathml = match ch {
+			'{' => {
+				self.i += 1;
+				mrow(self.parse_list(Stop::CloseBrace)?)
+			},
+			'\\' => return self.parse_command(),
+			'0'..='9' | '.' if self.mathvariant.is_none() || ch != '.' => {
+				let mut number = String::new();
+				while let Some(ch) = self.peek() {
+					let next_is_digit = self.chars.get(self.i + 1).is_some_and(|ch| ch.is_ascii_digit());
+					if ch.is_ascii_digit() || (ch == '.' && next_is_digit) {
+						number.push(ch);
+						self.i += 1;
+					} else {
+						break;
+					}
+				}
+				if number.is_empty() {
+					self.i += 1;
+					"<mo>.</mo>".to_string()
+				} else {
+					self.token("mn", &number)
+				}
+			},
+			'a'..='z' | 'A'..='Z' if self.mathvariant == Some("normal") => {
+				// \mathrm{d} or \operatorname{foo} -- keep the letters together
+				let mut name = String::new();
+				while let Some(ch) = self.peek().filter(|ch| ch.is_ascii_alphabetic()) {
+					name.push(ch);
+					self.i += 1;
+				}
+				self.token("mi", &name)
+			},
+			'$' => return Err( self.error(start, "'$' is not allowed in math") ),
+			'#' => return Err( self.error(start, "macro parameters are not supported") ),
+			'%' => {
+				// comment -- skip to the end of the line
+				while self.peek().is_some_and(|ch| ch != '\n') {
+					self.i += 1;
+				}
+				return Ok(None);
+			},
+			'~' => {
+				self.i += 1;
+				"<mspace width='0.333em'></mspace>".to_string()
+			},
+			_ => {
+				self.i += 1;
+				self.char_to_mathml(ch)
+			},
+		};
+		return Ok( Some( Atom { mathml, limits: false } ) );
+	}
+
+	/// MathML for a single character (not a command)
+	fn char_to_mathml(&self, ch: char) -> String {
+		return match ch {
+			'0'..='9' => self.token("mn", &ch.to_string()),
+			_ if ch.is_alphabetic() => self.token("mi", &ch.to_string()),
+			'-' => "<mo>−</mo>".to_string(),
+			'*' => "<mo>∗</mo>".to_string(),
+			'\'' => "<mo>′</mo>".to_string(),
+			_ => format!("<mo>{}</mo>", escape(&ch.to_string())),
+		};
+	}
+
+	/// A token element with the current mathvariant (if any)
+	fn token(&self, element: &str, text: &str) -> String {
+		return match self.mathvariant {
+			Some(mathvariant) => format!("<{element} mathvariant='{mathvariant}'>{}</{element}>", escape(text)),
+			None => format!("<{element}>{}</{element}>", escape(text)),
+		};
+	}
+
+	fn parse_command(&mut self) -> Result<Option<Atom>> {
+		let start = self.i;
+		let command = self.read_command();
+		let command = command.as_str();
+		if command.is_empty() {
+			return Err( self.error(start, "'\\' at end of input") );
+		}
+		let mut limits = false;
+		let mathml = if let Some(&ch) = IDENTIFIERS.get(command) {
+			self.token("mi", ch)
+		} else if let Some(&op) = OPERATORS.get(command) {
+			format!("<mo>{}</mo>", escape(op))
+		} else if let Some(&(op, op_limits)) = LARGE_OPERATORS.get(command) {
+			limits = op_limits;
+			format!("<mo>{op}</mo>")
+		} else if let Some(&function_limits) = FUNCTIONS.get(command) {
+			limits = function_limits;
+			format!("<mi>{command}</mi>")
+		} else if let Some(&(accent, is_over)) = ACCENTS.get(command) {
+			let base = self.parse_argument()?;
+			if is_over {
+				format!("<mover accent='true'>{base}<mo>{}</mo></mover>", escape(accent))
+			} else {
+				format!("<munder accentunder='true'>{base}<mo>{accent}</mo></munder>")
+			}
+		} else if let Some(&mathvariant) = FONTS.get(command) {
+			let saved_mathvariant = self.mathvariant.replace(mathvariant);
+			let result = self.parse_argument();
+			self.mathvariant = saved_mathvariant;
+			result?
+		} else if let Some(&width) = SPACES.get(command) {
+			format!("<mspace width='{width}'></mspace>")
+		} else if IGNORED.contains(command) {
+			return Ok(None);
+		} else {
+			match command {
+				"frac" | "dfrac" | "tfrac" | "cfrac" => {
+					let numerator = self.parse_argument()?;
+					let denominator = self.parse_argument()?;
+					format!("<mfrac>{numerator}{denominator}</mfrac>")
+				},
+				"binom" | "dbinom" | "tbinom" => {
+					let n = self.parse_argument()?;
+					let k = self.parse_argument()?;
+					format!("<mrow><mo>(</mo><mfrac linethickness='0'>{n}{k}</mfrac><mo>)</mo></mrow>")
+				},
+				"sqrt" => {
+					self.skip_white_space();
+					let index = if self.peek() == Some('[') {
+						self.i += 1;
+						Some( mrow(self.parse_list(Stop::CloseBracket)?) )
+					} else {
+						None
+					};
+					let radicand = self.parse_argument()?;
+					match index {
+						Some(index) => format!("<mroot>{radicand}{index}</mroot>"),
+						None => format!("<msqrt>{radicand}</msqrt>"),
+					}
+				},
+				"left" => {
+					let open = self.parse_delimiter()?;
+					let contents = self.parse_list(Stop::Right)?;
+					self.read_command();		// \right
+					let close = self.parse_delimiter()?;
+					format!("<mrow>{open}{}{close}</mrow>", mrow(contents))
+				},
+				"middle" => self.parse_delimiter()?,
+				"operatorname" | "operatorname*" => {
+					limits = command == "operatorname*";
+					let name = self.read_raw_group()?;
+					let name: String = name.chars().filter(|ch| !ch.is_whitespace()).collect();
+					format!("<mi>{}</mi>", escape(&name))
+				},
+				"text" | "textrm" | "textnormal" | "mbox" | "textit" | "textbf" => {
+					let text = self.read_raw_group()?;
+					format!("<mtext>{}</mtext>", escape(&text.replace('\\', "")))
+				},
+				"overset" | "stackrel" | "underset" => {
+					let script = self.parse_argument()?;
+					let base = self.parse_argument()?;
+					if command == "underset" {
+						format!("<munder>{base}{script}</munder>")
+					} else {
+						format!("<mover>{base}{script}</mover>")
+					}
+				},
+				"not" => {
+					let negated = self.parse_argument()?;
+					match negated.as_str() {
+						"<mo>=</mo>" => "<mo>≠</mo>".to_string(),
+						"<mo>∈</mo>" => "<mo>∉</mo>".to_string(),
+						"<mo>⊂</mo>" => "<mo>⊄</mo>".to_string(),
+						"<mo>⊆</mo>" => "<mo>⊈</mo>".to_string(),
+						_ => format!("<mrow>{negated}<mo>\u{0338}</mo></mrow>"),
+					}
+				},
+				"pmod" => {
+					let modulus = self.parse_argument()?;
+					format!("<mrow><mo>(</mo><mo>mod</mo>{modulus}<mo>)</mo></mrow>")
+				},
+				"color" | "textcolor" => {
+					self.read_raw_group()?;		// the color
+					if command == "color" {
+						return Ok(None);
+					}
+					self.parse_argument()?
+				},
+				"begin" => self.parse_environment(start)?,
+				_ => return Err( self.error(start, &format!("unknown command '\\{command}'")) ),
+			}
+		};
+		return Ok( Some( Atom { mathml, limits } ) );
+	}
+
+	/// Parse the delimiter after `\left`, `\right`, or `\middle` ('.' means no delimiter)
+	fn parse_delimiter(&mut self) -> Result<String> {
+		self.skip_white_space();
+		let start = self.i;
+		return match self.peek() {
+			Some('.') => {
+				self.i += 1;
+				Ok( String::new() )
+			},
+			Some('\\') => {
+				let command = self.read_command();
+				match OPERATORS.get(command.as_str()) {
+					Some(&delimiter) => Ok( format!("<mo>{delimiter}</mo>") ),
+					None => Err( self.error(start, &format!("'\\{command}' is not a delimiter")) ),
+				}
+			},
+			Some(ch) if "()[]|/<>".contains(ch) => {
+				self.i += 1;
+				let ch = match ch {'<' => '⟨', '>' => '⟩', _ => ch};
+				Ok( format!("<mo>{ch}</mo>") )
+			},
+			_ => Err( self.error(start, "missing delimiter") ),
+		};
+	}
+
+	/// Parse `\begin{name}...\end{name}` ('start' is the position of `\begin`)
+	fn parse_environment(&mut self, start: usize) -> Result<String> {
+		let name = self.read_raw_group()?;
+		let Some(&(open, close)) = TABLE_ENVIRONMENTS.get(name.as_str()) else {
+			return Err( self.error(start, &format!("unknown environment '{name}'")) );
+		};
+		if name == "array" || name.starts_with("alignat") {
+			self.read_raw_group()?;		// column spec or number of columns
+		}
+		let mut rows = vec![];
+		let mut cells = vec![];
+		loop {
+			let items = self.parse_list(Stop::Cell)?;
+			cells.push(format!("<mtd>{}</mtd>", mrow(items)));
+			if self.peek() == Some('&') {
+				self.i += 1;
+				continue;
+			}
+			let command = self.read_command();
+			if command != "end" {
+				// '\\' -- skip any spacing such as '\\[2pt]'
+				self.skip_white_space();
+				if self.peek() == Some('[') {
+					while self.peek().is_some_and(|ch| ch != ']') {
+						self.i += 1;
+					}
+					self.i += 1;
+				}
+			}
+			// a trailing '\\' doesn't start a row
+			if !(command == "end" && cells.len() == 1 && cells[0] == "<mtd><mrow></mrow></mtd>" && !rows.is_empty()) {
+				rows.push(format!("<mtr>{}</mtr>", cells.join("")));
+			}
+			cells.clear();
+			if command == "end" {
+				let end_start = self.i;
+				let end_name = self.read_raw_group()?;
+				if end_name != name {
+					return Err( self.error(end_start, &format!("'\\begin{{{name}}}' ended by '\\end{{{end_name}}}'")) );
+				}
+				break;
+			}
+		}
+		let table = format!("<mtable>{}</mtable>", rows.join(""));
+		if open.is_empty() && close.is_empty() {
+			return Ok(table);
+		}
+		let open = if open.is_empty() {String::new()} else {format!("<mo>{}</mo>", escape(open))};
+		let close = if close.is_empty() {String::new()} else {format!("<mo>{}</mo>", escape(close))};
+		return Ok( format!("<mrow>{open}{table}{close}</mrow>") );
+	}
+}
+
+/******** MathML to LaTeX ********/
+
+/// The LaTeX command for a character (the inverse of the tables used for parsing).
+/// If a character has more than one command, the shortest one is used.
+static LATEX_COMMANDS: LazyLock<HashMap<&'static str, String>> = LazyLock::new(|| {
+	let mut commands: HashMap<&str, &str> = HashMap::new();
+	let entries = IDENTIFIERS.entries().chain(OPERATORS.entries())
+		.chain(LARGE_OPERATORS.entries().map(|(command, (ch, _))| (command, ch)));
+	for (&command, &ch) in entries {
+		if ch.is_ascii() {
+			continue;		// e.g., "mod" and "|" are written as they are
+		}
+		match commands.get(ch) {
+			Some(&old) if (old.len(), old) <= (command.len(), command) => (),
+			_ => {commands.insert(ch, command);},
+		}
+	}
+	return commands.into_iter().map(|(ch, command)| (ch, format!("\\{command}"))).collect();
+});
+
+/// Convert (canonicalized) MathML into LaTeX.
+/// This is used to copy math as text, so the LaTeX is written the way a person would write it (e.g., `\frac{x+1}{2}`).
+pub fn mathml_to_latex(mathml: Element) -> Result<String> {
+	let mut writer = LaTeXWriter::default();
+	writer.write(mathml)?;
+	return Ok( writer.latex.trim().to_string() );
+}
+
+#[derive(Default)]
+struct LaTeXWriter {
+	latex: String,
+	after_script: bool,			// a script is separated from a number or letter that follows it (e.g., "x^2 y")
+}
+
+impl LaTeXWriter {
+	/// Add `text` to the LaTeX, separating a command name from a letter that follows it (e.g., `\alpha x`)
+	fn push(&mut self, text: &str) {
+		if std::mem::take(&mut self.after_script) && text.starts_with(|ch: char| ch.is_ascii_alphanumeric()) {
+			self.latex.push(' ');
+		} else if text.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+			let name_start = self.latex.trim_end_matches(|ch: char| ch.is_ascii_alphabetic());
+			if name_start.len() < self.latex.len() && name_start.ends_with('\\') {
+				self.latex.push(' ');
+			}
+		}
+		self.latex.push_str(text);
+	}
+
+	/// The LaTeX for `mathml` (without adding it)
+	fn to_latex(mathml: Element) -> Result<String> {
+		let mut writer = LaTeXWriter::default();
+		writer.write(mathml)?;
+		return Ok(writer.latex);
+	}
+
+	/// Write `mathml` as the argument of a command (`{...}`)
+	fn write_arg(&mut self, mathml: Element) -> Result<()> {
+		self.push("{");
+		self.write(mathml)?;
+		self.push("}");
+		return Ok( () );
+	}
+
+	/// Write `mathml` as a script -- braces are only used if the script is more than one character or command
+	fn write_script(&mut self, mathml: Element) -> Result<()> {
+		let script = LaTeXWriter::to_latex(mathml)?;
+		let is_command = script.len() > 1 && script.starts_with('\\') && script[1..].chars().all(|ch| ch.is_ascii_alphabetic());
+		if script.chars().count() == 1 || is_command {
+			self.push(&script);
+		} else {
+			self.push(&format!("{{{script}}}"));
+		}
+		self.after_script = true;
+		return Ok( () );
+	}
+
+	fn write_children(&mut self, mathml: Element) -> Result<()> {
+		let children = mathml.children();
+		for (i, &child) in children.iter().enumerate() {
+			let child = as_element(child);
+			if name(child) == "mo" && as_text(child) == "\u{2062}" &&
+			   children.get(i+1).is_some_and(|&next| is_differential(as_element(next))) {
+				self.push("\\,");		// thin space before the 'dx' of an integral
+			}
+			self.write(child)?;
+		}
+		return Ok( () );
+	}
+
+	fn write(&mut self, mathml: Element) -> Result<()> {
+		let element_name = name(mathml);
+		match element_name {
+			"math" | "mrow" | "mstyle" | "mpadded" | "merror" | "mtd" => {
+				if element_name != "mrow" || !self.write_fenced_table(mathml)? {
+					self.write_children(mathml)?;
+				}
+			},
+			"semantics" => {
+				if let Some(&child) = mathml.children().first() {
+					self.write(as_element(child))?;
+				}
+			},
+			"mi" => self.write_mi(mathml),
+			"mn" => self.push(&plain_text(as_text(mathml))),
+			"mo" => self.write_mo(mathml),
+			"mtext" | "ms" => {
+				let text = as_text(mathml).replace('\u{00A0}', " ");
+				if text.trim().is_empty() {
+					self.push("\\ ");
+				} else {
+					let text = escape_text(&text);
+					self.push(&if element_name == "ms" {format!("\\text{{\"{text}\"}}")} else {format!("\\text{{{text}}}")});
+				}
+			},
+			"mspace" => {
+				let width = mathml.attribute_value("width").unwrap_or_default();
+				let is_wide = width.strip_suffix("em").and_then(|width| width.trim().parse::<f64>().ok()).is_some_and(|width| width >= 1.0);
+				self.push(if is_wide {"\\quad"} else {"\\ "});
+			},
+			"mfrac" => {
+				let children = mathml.children();
+				let thickness = mathml.attribute_value("linethickness").unwrap_or_default();
+				let is_zero = !thickness.is_empty() && thickness.trim_start_matches(['0', '.']).chars().all(|ch| ch.is_ascii_alphabetic());
+				self.push(if is_zero {"\\genfrac{}{}{0pt}{}"} else {"\\frac"});
+				self.write_arg(as_element(children[0]))?;
+				self.write_arg(as_element(children[1]))?;
+			},
+			"msqrt" => {
+				self.push("\\sqrt{");
+				self.write_children(mathml)?;
+				self.push("}");
+			},
+			"mroot" => {
+				let children = mathml.children();
+				self.push("\\sqrt[");
+				self.write(as_element(children[1]))?;
+				self.push("]");
+				self.write_arg(as_element(children[0]))?;
+			},
+			"msub" | "msup" | "msubsup" => {
+				let children = mathml.children();
+				self.write_base(as_element(children[0]))?;
+				let (sub, sup) = match element_name {
+					"msub" => (Some(children[1]), None),
+					"msup" => (None, Some(children[1])),
+					_ => (Some(children[1]), Some(children[2])),
+				};
+				if let Some(sub) = sub {
+					self.push("_");
+					self.write_script(as_element(sub))?;
+				}
+				if let Some(sup) = sup {
+					let sup = as_element(sup);
+					let text = if name(sup) == "mo" {as_text(sup)} else {""};
+					if !text.is_empty() && text.chars().all(|ch| ch == '′') {
+						self.push(&"'".repeat(text.chars().count()));
+					} else {
+						self.push("^");
+						self.write_script(sup)?;
+					}
+				}
+			},
+			"munder" | "mover" | "munderover" => self.write_under_over(mathml)?,
+			"mmultiscripts" => {
+				let children = mathml.children();
+				let mut scripts: Vec<Element> = children[1..].iter().map(|&child| as_element(child)).collect();
+				let prescripts = match scripts.iter().position(|&child| name(child) == "mprescripts") {
+					Some(i) => scripts.split_off(i)[1..].to_vec(),
+					None => vec![],
+				};
+				if !prescripts.is_empty() {
+					self.push("{}");
+					self.write_script_pairs(&prescripts)?;
+				}
+				self.write_base(as_element(children[0]))?;
+				self.write_script_pairs(&scripts)?;
+			},
+			"none" | "mprescripts" => (),
+			"mtable" => self.write_table(mathml, "matrix")?,
+			"menclose" => {
+				let notation = mathml.attribute_value("notation").unwrap_or("longdiv");
+				let command = if notation.split_whitespace().any(|notation| notation == "box" || notation == "roundedbox") {
+					Some("\\boxed{")
+				} else if notation.split_whitespace().any(|notation| notation.ends_with("strike")) {
+					Some("\\cancel{")
+				} else {
+					None
+				};
+				if let Some(command) = command {
+					self.push(command);
+				}
+				self.write_children(mathml)?;
+				if command.is_some() {
+					self.push("}");
+				}
+			},
+			"mphantom" => {
+				self.push("\\phantom{");
+				self.write_children(mathml)?;
+				self.push("}");
+			},
+			"mglyph" => self.push(&escape_text(mathml.attribute_value("alt").unwrap_or_default())),
+			_ => bail!("LaTeX can't be generated for the MathML element '{}'", element_name),
+		};
+		return Ok( () );
+	}
+
+	fn write_mi(&mut self, mi: Element) {
+		let text = as_text(mi);
+		if text.chars().count() > 1 {
+			if FUNCTIONS.contains_key(text) {
+				self.push(&format!("\\{text}"));
+			} else {
+				self.push(&format!("\\mathrm{{{}}}", escape_text(text)));
+			}
+			return;
+		}
+		let font = match mi.attribute_value("mathvariant").unwrap_or_default() {
+			"double-struck" => "mathbb", "bold" => "mathbf", "script" => "mathcal", "fraktur" => "mathfrak",
+			"sans-serif" => "mathsf", "monospace" => "mathtt", "bold-italic" => "boldsymbol",
+			_ => "",
+		};
+		let plain = plain_text(text);
+		if !font.is_empty() && plain != text {
+			self.push(&format!("\\{font}{{{plain}}}"));
+		} else {
+			self.push(LATEX_COMMANDS.get(text).map_or(text, |command| command.as_str()));
+		}
+	}
+
+	fn write_mo(&mut self, mo: Element) {
+		let text = as_text(mo);
+		let latex = match text {
+			"\u{2061}" | "\u{2062}" | "\u{2063}" | "\u{2064}" => "",
+			"{" => "\\{",
+			"}" => "\\}",
+			"−" => "-",
+			"′" => "'",
+			"#" | "$" | "%" | "&" | "_" => {
+				self.push(&format!("\\{text}"));
+				return;
+			},
+			"mod" => "\\bmod",
+			_ => match LATEX_COMMANDS.get(text) {
+				Some(command) => command.as_str(),
+				None if text.chars().count() > 1 && text.chars().all(|ch| ch.is_alphabetic()) => {
+					self.push(&format!("\\operatorname{{{text}}}"));
+					return;
+				},
+				None => text,
+			},
+		};
+		self.push(latex);
+	}
+
+	/// The base of a script -- nested scripts need braces (e.g., `{x^2}^3`)
+	fn write_base(&mut self, base: Element) -> Result<()> {
+		if matches!(name(base), "msub" | "msup" | "msubsup" | "mmultiscripts") {
+			return self.write_arg(base);
+		}
+		return self.write(base);
+	}
+
+	fn write_script_pairs(&mut self, scripts: &[Element]) -> Result<()> {
+		for pair in scripts.chunks(2) {
+			if name(pair[0]) != "none" {
+				self.push("_");
+				self.write_script(pair[0])?;
+			}
+			if let Some(&sup) = pair.get(1) && name(sup) != "none" {
+				self.push("^");
+				self.write_script(sup)?;
+			}
+		}
+		return Ok( () );
+	}
+
+	fn write_under_over(&mut self, mathml: Element) -> Result<()> {
+		let element_name = name(mathml);
+		let children = mathml.children();
+		let base = as_element(children[0]);
+		let script = as_element(children[1]);
+		if children.len() == 2 && name(script) == "mo" {
+			let accent = match as_text(script) {
+				"^" | "ˆ" | "\u{0302}" => "hat", "¯" | "‾" | "\u{0304}" | "\u{0305}" => "overline",
+				"→" | "\u{20D7}" => "vec", "˙" | "\u{0307}" => "dot", "¨" | "\u{0308}" => "ddot",
+				"~" | "˜" | "\u{0303}" => "tilde", "ˇ" => "check", "˘" => "breve", "⏞" => "overbrace",
+				"_" | "\u{0332}" => "underline", "⏟" => "underbrace",
+				_ => "",
+			};
+			let is_under_accent = matches!(accent, "underline" | "underbrace");
+			if !accent.is_empty() && is_under_accent == (element_name == "munder") {
+				self.push(&format!("\\{accent}"));
+				return self.write_arg(base);
+			}
+		}
+
+		let base_text = if is_leaf(base) {as_text(base)} else {""};
+		let has_limits = LARGE_OPERATORS.values().any(|(op, _)| *op == base_text) || FUNCTIONS.get(base_text) == Some(&true);
+		if has_limits {
+			self.write(base)?;
+			let (under, over) = match element_name {
+				"munder" => (Some(script), None),
+				"mover" => (None, Some(script)),
+				_ => (Some(script), Some(as_element(children[2]))),
+			};
+			if let Some(under) = under {
+				self.push("_");
+				self.write_script(under)?;
+			}
+			if let Some(over) = over {
+				self.push("^");
+				self.write_script(over)?;
+			}
+			return Ok( () );
+		}
+
+		match element_name {
+			"munder" => {
+				self.push("\\underset");
+				self.write_arg(script)?;
+				self.write_arg(base)?;
+			},
+			"mover" => {
+				self.push("\\overset");
+				self.write_arg(script)?;
+				self.write_arg(base)?;
+			},
+			_ => {
+				self.push("\\underset");
+				self.write_arg(script)?;
+				self.push("{\\overset");
+				self.write_arg(as_element(children[2]))?;
+				self.write_arg(base)?;
+				self.push("}");
+			},
+		}
+		return Ok( () );
+	}
+
+	/// If `mrow` is a table inside of fences (e.g., a matrix in parens), write it as the corresponding environment.
+	/// Returns true if it was written.
+	fn write_fenced_table(&mut self, mrow: Element) -> Result<bool> {
+		let children = mrow.children();
+		if !(2..=3).contains(&children.len()) {
+			return Ok(false);
+		}
+		let open = as_element(children[0]);
+		let table = as_element(children[1]);
+		if name(open) != "mo" || name(table) != "mtable" {
+			return Ok(false);
+		}
+		let close = if children.len() == 3 {as_element(children[2])} else {open};
+		if name(close) != "mo" {
+			return Ok(false);
+		}
+		let close = if children.len() == 3 {as_text(close)} else {""};
+		let environment = match (as_text(open), close) {
+			("(", ")") => "pmatrix",
+			("[", "]") => "bmatrix",
+			("{", "}") => "Bmatrix",
+			("|", "|") => "vmatrix",
+			("‖", "‖") => "Vmatrix",
+			("{", "") => "cases",
+			_ => return Ok(false),
+		};
+		self.write_table(table, environment)?;
+		return Ok(true);
+	}
+
+	fn write_table(&mut self, table: Element, environment: &str) -> Result<()> {
+		self.push(&format!("\\begin{{{environment}}}"));
+		for (i, &row) in table.children().iter().enumerate() {
+			if i > 0 {
+				self.push(" \\\\ ");
+			}
+			let row = as_element(row);
+			let cells = row.children();
+			let cells = if name(row) == "mlabeledtr" {&cells[1..]} else {&cells[..]};
+			for (j, &cell) in cells.iter().enumerate() {
+				if j > 0 {
+					self.push(" & ");
+				}
+				self.write(as_element(cell))?;
+			}
+		}
+		self.push(&format!("\\end{{{environment}}}"));
+		return Ok( () );
+	}
+}
+
+/// Returns true if `mathml` is the 'd' of a differential (e.g., 'dx') that is part of an integral
+pub(crate) fn is_differential(mathml: Element) -> bool {
+	if !(matches!(name(mathml), "mi" | "mo") && matches!(as_text(mathml), "d" | "ⅆ")) {
+		return false;
+	}
+	let is_followed_by_variable = mathml.following_siblings().into_iter().filter_map(|child| child.element())
+		.find(|&child| !(name(child) == "mo" && as_text(child) == "\u{2062}"))
+		.is_some_and(|variable| name(variable) == "mi");
+	if !is_followed_by_variable {
+		return false;
+	}
+	// look for an integral sign before the 'd' (at any level)
+	let mut node = mathml;
+	loop {
+		if node.preceding_siblings().into_iter().filter_map(|child| child.element()).any(is_integral_sign) {
+			return true;
+		}
+		match node.parent().and_then(|parent| parent.element()) {
+			Some(parent) => node = parent,
+			None => return false,
+		}
+	}
+
+	fn is_integral_sign(mathml: Element) -> bool {
+		if is_leaf(mathml) {
+			return name(mathml) == "mo" && matches!(as_text(mathml), "∫" | "∬" | "∭" | "∮" | "∯" | "∰");
+		}
+		return matches!(name(mathml), "msub" | "msup" | "msubsup" | "munder" | "mover" | "munderover") &&
+			   is_integral_sign(as_element(mathml.children()[0]));
+	}
+}
+
+/// Map the math alphanumeric letters and digits (e.g., '𝐱' and 'ℝ') to ASCII
+pub(crate) fn plain_text(text: &str) -> String {
+	static LETTERLIKE: phf::Map<char, char> = phf_map! {
+		'ℂ' => 'C', 'ℍ' => 'H', 'ℕ' => 'N', 'ℙ' => 'P', 'ℚ' => 'Q', 'ℝ' => 'R', 'ℤ' => 'Z', 'ℎ' => 'h',
+		'ℬ' => 'B', 'ℰ' => 'E', 'ℱ' => 'F', 'ℋ' => 'H', 'ℐ' => 'I', 'ℒ' => 'L', 'ℳ' => 'M', 'ℛ' => 'R',
+		'ℯ' => 'e', 'ℊ' => 'g', 'ℴ' => 'o', 'ℭ' => 'C', 'ℌ' => 'H', 'ℑ' => 'I', 'ℜ' => 'R', 'ℨ' => 'Z',
+	};
+	return text.chars().map(|ch| {
+		let code = ch as u32;
+		if let Some(&plain) = LETTERLIKE.get(&ch) {
+			plain
+		} else if (0x1D400..=0x1D6A3).contains(&code) {
+			// 52 letters (A-Z, a-z) for each of the alphabets
+			let i = ((code - 0x1D400) % 52) as u8;
+			(if i < 26 {b'A' + i} else {b'a' + i - 26}) as char
+		} else if (0x1D7CE..=0x1D7FF).contains(&code) {
+			(b'0' + ((code - 0x1D7CE) % 10) as u8) as char
+		} else {
+			ch
+		}
+	}).collect();
+}
+
+/// Escape the characters that are special in LaTeX text
+fn escape_text(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+	for ch in text.chars() {
+		match ch {
+			'\\' => result.push_str("\\textbackslash{}"),
+			'{' | '}' | '#' | '$' | '%' | '&' | '_' => {
+				result.push('\\');
+				result.push(ch);
+			},
+			'^' => result.push_str("\\^{}"),
+			'~' => result.push_str("\\~{}"),
+			_ => result.push(ch),
+		}
+	}
+	return result;
+}
+
+/// Wrap the items in an mrow unless there is exactly one
+fn mrow(items: Vec<String>) -> String {
+	if items.len() == 1 {
+		return items.into_iter().next().unwrap();
+	}
+	return format!("<mrow>{}</mrow>", items.join(""));
+}
+
+fn escape(text: &str) -> String {
+	return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('\'', "&apos;").replace('"', "&quot;");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::interface::{get_element, trim_element, is_same_element};
+	use sxd_document::parser;
+	use anyhow::bail;
+
+	fn is_converted_to(latex: &str, target: &str) -> Result<()> {
+		let mathml = latex_to_mathml(latex)?;
+		let test_package = parser::parse(&mathml).expect("Failed to parse generated MathML");
+		let test_mathml = get_element(&test_package);
+		let target_package = parser::parse(target).expect("Failed to parse target input");
+		let target_mathml = get_element(&target_package);
+		trim_element(target_mathml, false);
+		if let Err(e) = is_same_element(test_mathml, target_mathml, &[]) {
+			bail!("{}\nResult:\n{}", e, mathml);
+		}
+		return Ok( () );
+	}
+
+	#[test]
+	fn scripts_and_fractions() -> Result<()> {
+		return is_converted_to(r"$\frac{a+b}{2} = x_1^{10}$",
+			"<math>
+				<mrow>
+					<mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mn>2</mn></mfrac>
+					<mo>=</mo>
+					<msubsup><mi>x</mi><mn>1</mn><mn>10</mn></msubsup>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn roots_fences_and_primes() -> Result<()> {
+		return is_converted_to(r"\[ \sqrt[3]{x} + \left( f'' \right] \]",
+			"<math display='block'>
+				<mrow>
+					<mroot><mi>x</mi><mn>3</mn></mroot>
+					<mo>+</mo>
+					<mrow><mo>(</mo><msup><mi>f</mi><mo>″</mo></msup><mo>]</mo></mrow>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn limits_and_fonts() -> Result<()> {
+		return is_converted_to(r"\sum_{i \in \mathbb{N}} \operatorname{sgn}(i) \int_0^1",
+			"<math>
+				<mrow>
+					<munder><mo>∑</mo><mrow><mi>i</mi><mo>∈</mo><mi mathvariant='double-struck'>N</mi></mrow></munder>
+					<mi>sgn</mi><mo>(</mo><mi>i</mi><mo>)</mo>
+					<msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn environments() -> Result<()> {
+		return is_converted_to(r"\begin{pmatrix} 1 & 2 \\ -x & \text{if} y \\ \end{pmatrix}",
+			"<math>
+				<mrow>
+					<mo>(</mo>
+					<mtable>
+						<mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+						<mtr><mtd><mrow><mo>−</mo><mi>x</mi></mrow></mtd><mtd><mrow><mtext>if</mtext><mi>y</mi></mrow></mtd></mtr>
+					</mtable>
+					<mo>)</mo>
+				</mrow>
+			</math>");
+	}
+
+	#[test]
+	fn error_position() {
+		let error = latex_to_mathml(r"$x + \foo{y}$").unwrap_err().to_string();
+		assert!(error.starts_with("LaTeX error at position 6: unknown command '\\foo'"), "{error}");
+		let error = latex_to_mathml(r"\frac{1}{2").unwrap_err().to_string();
+		assert!(error.starts_with("LaTeX error at position 9: missing '}'"), "{error}");
+		let error = latex_to_mathml(r"\begin{matrix} 1 \end{cases}").unwrap_err().to_string();
+		assert!(error.contains("'\\begin{matrix}' ended by '\\end{cases}'"), "{error}");
+	}
+}
@@ -17,6 +17,13 @@
 //! 
 //! To get the MathML associated with the current navigation node, call [`get_navigation_mathml`].
 //! To just get the `id` and offset from the id of the current navigation node, call [`get_navigation_mathml_id`].
+//!
+//! The functions above work on one expression at a time. To keep several expressions (and their navigation positions)
+//! around at once, create a [`MathDocument`] for each of them; the rule files and preferences are shared.
+//!
+//! The compiled rules (speech and braille rules, unicode tables, and definitions) are built once and shared by all threads.
+//! Only the preferences are per thread (each thread also compiles the XPath expressions in the rules the first time it uses them).
+//! A server that uses a thread pool can use [`with_preferences`] to set preferences for a single request.
 ///
 /// This module re-exports anyhow types. Use `bail!` for early returns and
 /// `context()`/`with_context()` on Result to add context (replacing old `chain_err()`).
@@ -25,6 +32,10 @@ pub mod errors {
 }
 
 pub mod interface;
+pub mod document;
+pub use document::MathDocument;
+pub use tts::SpeechSegment;
+pub use braille::BrailleLines;
 #[cfg(feature = "include-zip")]
 pub use shim_filesystem::ZIPPED_RULE_FILES;
 
@@ -32,6 +43,8 @@ mod canonicalize;
 mod infer_intent;
 pub mod speech;
 mod braille;
+mod braille_encoding;
+mod back_translate;
 mod navigate;
 mod prefs;
 mod tts;
@@ -39,6 +52,14 @@ mod xpath_functions;
 mod definitions;
 pub mod pretty_print;
 mod chemistry;
+mod content_mathml;
+mod latex;
+mod asciimath;
+mod c_interface;
+#[cfg(feature = "wasm")]
+mod wasm_interface;
+#[cfg(feature = "python")]
+mod python_interface;
 
 pub mod shim_filesystem; // really just for override_file_for_debugging_rules, but the config seems to throw it off
 pub use interface::*;
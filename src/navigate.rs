@@ -2,7 +2,7 @@
 //! See preference documentation for more info on navigation preferences.
 #![allow(clippy::needless_return)]
 
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Ref, RefCell};
 use sxd_xpath::context::Evaluation;
 use sxd_xpath::Value;
 use sxd_document::dom::Element;
@@ -36,12 +36,14 @@ pub static NAV_COMMANDS: phf::Set<&str> = phf_set! {
     "ZoomIn", "ZoomOut", "ZoomOutAll", "ZoomInAll", 
     "MoveLastLocation", 
     "ReadPrevious", "ReadNext", "ReadCurrent", "ReadCellCurrent", "ReadStart", "ReadEnd", "ReadLineStart", "ReadLineEnd", 
+    "ReadToStart", "ReadToEnd", "ReadToLineStart", "ReadToLineEnd", 
     "DescribePrevious", "DescribeNext", "DescribeCurrent", 
     "WhereAmI", "WhereAmIAll", 
     "ToggleZoomLockUp", "ToggleZoomLockDown", "ToggleSpeakMode", 
     "Exit", 
     "MoveTo0","MoveTo1","MoveTo2","MoveTo3","MoveTo4","MoveTo5","MoveTo6","MoveTo7","MoveTo8","MoveTo9",
     "Read0","Read1","Read2","Read3","Read4","Read5","Read6","Read7","Read8","Read9",
+    "ReadTo0","ReadTo1","ReadTo2","ReadTo3","ReadTo4","ReadTo5","ReadTo6","ReadTo7","ReadTo8","ReadTo9",
     "Describe0","Describe1","Describe2","Describe3","Describe4","Describe5","Describe6","Describe7","Describe8","Describe9",
     "SetPlacemarker0","SetPlacemarker1","SetPlacemarker2","SetPlacemarker3","SetPlacemarker4","SetPlacemarker5","SetPlacemarker6","SetPlacemarker7","SetPlacemarker8","SetPlacemarker9",
 };
@@ -107,7 +109,7 @@ impl fmt::Display for NavigationState {
 }
 
 impl NavigationState {
-    fn new() -> NavigationState {
+    pub fn new() -> NavigationState {
         return NavigationState {
             position_stack: Vec::with_capacity(1024),
             command_stack: Vec::with_capacity(1024),
@@ -227,6 +229,8 @@ impl NavigationState {
         context.set_variable("SayCommand", "" );
         context.set_variable("Move2D", "" );
         context.set_variable("SpeakExpression", true );    // default is to speak the expr after navigation
+        context.set_variable("ReadToNode", "" );            // set by the ReadTo rules to the node where reading stops
+        context.set_variable("ReadToNodeOffset", 0 as f64);
         return;
 
         fn convert_last_char_to_number(str: &str) -> usize {
@@ -266,19 +270,21 @@ fn get_node_by_id<'a>(mathml: Element<'a>, pos: &NavigationPosition) -> Option<E
 /// Search the mathml for the id and set the navigation node to that id
 /// Resets the navigation stack
 pub fn set_navigation_node_from_id(mathml: Element, id: &str, offset: usize) -> Result<()> {
+    return NAVIGATION_STATE.with(|nav_state| set_navigation_node_from_id_with_state(&mut nav_state.borrow_mut(), mathml, id, offset));
+}
+
+/// Same as [`set_navigation_node_from_id`], but uses `nav_state` rather than the thread's navigation state
+pub fn set_navigation_node_from_id_with_state(nav_state: &mut NavigationState, mathml: Element, id: &str, offset: usize) -> Result<()> {
     let current_node = id.to_string();
     let pos = NavigationPosition { current_node: current_node.clone(), current_node_offset: offset };
     let node = get_node_by_id(mathml, &pos);
     if node.is_some() {
-        return NAVIGATION_STATE.with(|nav_state| {
-            let mut nav_state = nav_state.borrow_mut();
-            nav_state.reset();
-            nav_state.push(NavigationPosition{
-                current_node,
-                current_node_offset: offset
-            }, "None");
-            return Ok( () );
-        })
+        nav_state.reset();
+        nav_state.push(NavigationPosition{
+            current_node,
+            current_node_offset: offset
+        }, "None");
+        return Ok( () );
     } else {
         bail!("Id {} not found in MathML {}", id, mml_to_string(mathml));
     }
@@ -356,15 +362,29 @@ fn context_get_int_variable<'c>(context: &sxd_xpath::Context<'c>, var_name: &str
 /// The spoken text for the new current node is returned.
 pub fn do_mathml_navigate_key_press(mathml: Element,
             key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool) -> Result<String> {
+    return NAVIGATION_STATE.with(|nav_state|
+        do_mathml_navigate_key_press_with_state(&mut nav_state.borrow_mut(), mathml, key, shift_key, control_key, alt_key, meta_key)
+    );
+}
+
+/// Same as [`do_mathml_navigate_key_press`], but uses `nav_state` rather than the thread's navigation state
+pub fn do_mathml_navigate_key_press_with_state(nav_state: &mut NavigationState, mathml: Element,
+            key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool) -> Result<String> {
     let (command, param) = key_press_to_command_and_param(key, shift_key, control_key, alt_key, meta_key)?;
-    return do_navigate_command_and_param(mathml, command, param);
+    return do_navigate_command_string_with_state(nav_state, mathml, navigation_command_string(command, param));
 }
 
+#[cfg(test)]
 fn do_navigate_command_and_param(mathml: Element, command: NavigationCommand, param: NavigationParam) -> Result<String> {
     return do_navigate_command_string(mathml, navigation_command_string(command, param));
 }
 
-pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) -> Result<String> {   
+pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) -> Result<String> {
+    return NAVIGATION_STATE.with(|nav_state| do_navigate_command_string_with_state(&mut nav_state.borrow_mut(), mathml, nav_command));
+}
+
+/// Same as [`do_navigate_command_string`], but uses `nav_state` rather than the thread's navigation state
+pub fn do_navigate_command_string_with_state(nav_state: &mut NavigationState, mathml: Element, nav_command: &'static str) -> Result<String> {   
     // first check to see if nav file has been changed -- don't bother checking in loop below
     NAVIGATION_RULES.with(|rules| {
         rules.borrow_mut().read_files()
@@ -374,82 +394,68 @@ pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) ->
         bail!("MathML has not been set -- can't navigate");
     };
 
-    return NAVIGATION_STATE.with(|nav_state| {
-        let mut nav_state = nav_state.borrow_mut();
-        // debug!("MathML: {}", mml_to_string(mathml));
-        if nav_state.position_stack.is_empty() {
-            // initialize to root node
-            nav_state.push(NavigationPosition{
-                current_node: mathml.attribute_value("id").unwrap().to_string(),
-                current_node_offset: 0
-            }, "None")
-        };
+    // debug!("MathML: {}", mml_to_string(mathml));
+    if nav_state.position_stack.is_empty() {
+        // initialize to root node
+        nav_state.push(NavigationPosition{
+            current_node: mathml.attribute_value("id").unwrap().to_string(),
+            current_node_offset: 0
+        }, "None")
+    };
 
-        return NAVIGATION_RULES.with(|rules| {
-            let rules = rules.borrow();
-            let new_package = Package::new();
-            let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), "", 0);
-            
-            nav_state.mode = rules.pref_manager.as_ref().borrow().pref_to_string("NavMode");
-            nav_state.speak_overview = rules.pref_manager.as_ref().borrow().pref_to_string("Overview") == "true";
+    return NAVIGATION_RULES.with(|rules| {
+        let rules = rules.borrow();
+        let new_package = Package::new();
+        let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), "", 0);
+        
+        nav_state.mode = rules.pref_manager.as_ref().borrow().pref_to_string("NavMode");
+        nav_state.speak_overview = rules.pref_manager.as_ref().borrow().pref_to_string("Overview") == "true";
 
-            nav_state.init_navigation_context(rules_with_context.get_context(), nav_command, nav_state.top());
-            
-            // start navigation off at the right node
-            if nav_command == "MoveLastLocation" {
-                nav_state.pop();
-            }
+        nav_state.init_navigation_context(rules_with_context.get_context(), nav_command, nav_state.top());
+        
+        // start navigation off at the right node
+        if nav_command == "MoveLastLocation" {
+            nav_state.pop();
+        }
 
-            // If no speech happened for some calls, we try the call again (e.g, no speech for invisible times).
-            // To prevent to infinite loop, we limit the number of tries
-            const LOOP_LIMIT: usize = 3;
-            let mut cumulative_speech = String::with_capacity(120);
-            for loop_count in 0..LOOP_LIMIT {
-                match apply_navigation_rules(mathml, nav_command, &rules, &mut rules_with_context, &mut nav_state, loop_count) {
-                    Ok( (speech, done)) => {
-                        cumulative_speech = cumulative_speech + if loop_count==0 {""} else {" "} + speech.trim();
-                        if done {
-                            let (tts, rate) = {
-                                let prefs = rules.pref_manager.borrow();
-                                (prefs.pref_to_string("TTS"), prefs.pref_to_string("MathRate"))
-                            };
-                            if rate != "100" {
-                                match tts.as_str() {
-                                    "SSML"
-                                        if !cumulative_speech.starts_with("<prosody rate") => {
-                                            cumulative_speech = format!("<prosody rate='{}%'>{}</prosody>", &rate, &cumulative_speech);
-                                        }
-                                    "SAPI5"
-                                        if !cumulative_speech.starts_with("<rate speed") => {
-                                            cumulative_speech = format!(
-                                                "<rate speed='{:.1}'>{}</rate>",
-                                                10.0 * (0.01 * rate.parse::<f32>().unwrap_or(100.0)).log(3.0),
-                                                cumulative_speech
-                                            );
-                                        }
-                                    _ => (),  // do nothing
+        // If no speech happened for some calls, we try the call again (e.g, no speech for invisible times).
+        // To prevent to infinite loop, we limit the number of tries
+        const LOOP_LIMIT: usize = 3;
+        let mut cumulative_speech = String::with_capacity(120);
+        for loop_count in 0..LOOP_LIMIT {
+            match apply_navigation_rules(mathml, nav_command, &rules, &mut rules_with_context, nav_state, loop_count) {
+                Ok( (speech, done)) => {
+                    cumulative_speech = cumulative_speech + if loop_count==0 {""} else {" "} + speech.trim();
+                    if done {
+                        {
+                            let prefs = rules.pref_manager.borrow();
+                            let rate = prefs.pref_to_string("MathRate").parse::<f64>().unwrap_or(100.0);
+                            if rate != 100.0 {
+                                let (start_tag, end_tag) = prefs.get_tts().rate_tags(&prefs, rate);
+                                if !start_tag.is_empty() && !cumulative_speech.starts_with(start_tag.trim_end()) {
+                                    cumulative_speech = format!("{start_tag}{cumulative_speech}{end_tag}");
                                 }
                             }
-                                                return Ok( rules.pref_manager.borrow().get_tts()
-                                            .merge_pauses(crate::speech::remove_optional_indicators(
-                                                &cumulative_speech.replace(CONCAT_STRING, "")
-                                                                    .replace(CONCAT_INDICATOR, "")                            
-                                                            )
-                                            .trim_start().trim_end_matches([' ', ',', ';'])) );
                         }
-                    },
-                    Err(e) => {
-                        return Err(e);
+                                            return Ok( rules.pref_manager.borrow().get_tts()
+                                        .merge_pauses(crate::speech::remove_optional_indicators(
+                                            &cumulative_speech.replace(CONCAT_STRING, "")
+                                                                .replace(CONCAT_INDICATOR, "")                            
+                                                        )
+                                        .trim_start().trim_end_matches([' ', ',', ';'])) );
                     }
+                },
+                Err(e) => {
+                    return Err(e);
                 }
             }
-            bail!("Internal error: Navigation exceeded limit of number of times no speech generated
-                   when attempting to {} in {} mode start at id={} in this MathML:\n{}.",
-                   nav_command, nav_state.mode, nav_state.top().unwrap().0.current_node, mml_to_string(mathml));
-        });
+        }
+        bail!("Internal error: Navigation exceeded limit of number of times no speech generated
+               when attempting to {} in {} mode start at id={} in this MathML:\n{}.",
+               nav_command, nav_state.mode, nav_state.top().unwrap().0.current_node, mml_to_string(mathml));
     });
 
-    fn get_start_node<'m>(mathml: Element<'m>, nav_state: &RefMut<NavigationState>) -> Result<Element<'m>>  {
+    fn get_start_node<'m>(mathml: Element<'m>, nav_state: &NavigationState) -> Result<Element<'m>>  {
         let element = match nav_state.top() {
             None => {
                 let nav_position = NavigationPosition { current_node: mathml.attribute_value("id").unwrap().to_string(), current_node_offset: 0 };
@@ -470,7 +476,7 @@ pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) ->
 
 
     fn apply_navigation_rules<'c, 'm:'c>(mathml: Element<'m>, nav_command: &'static str,
-            rules: &Ref<SpeechRules>, rules_with_context: &mut SpeechRulesWithContext<'c, '_, 'm>, nav_state: &mut RefMut<NavigationState>,
+            rules: &Ref<SpeechRules>, rules_with_context: &mut SpeechRulesWithContext<'c, '_, 'm>, nav_state: &mut NavigationState,
             loop_count: usize) -> Result<(String, bool)> {
         {
             let context = rules_with_context.get_context();
@@ -614,6 +620,16 @@ pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) ->
                 // debug!("returning: '{}'", speech.clone() + " " + &node_speech);
                 return Ok( (speech + " " + &node_speech, true) );
             }
+        } else if nav_command.starts_with("ReadTo") {
+            // the nav node doesn't change -- speak from it to the node the rules found
+            let read_to_position = NavigationPosition {
+                current_node: context_get_variable(context, "ReadToNode", intent)?,
+                current_node_offset: context_get_int_variable(context, "ReadToNodeOffset", intent)?,
+            };
+            let range_speech_result = speak_range(mathml, intent, &nav_position, &read_to_position, nav_state.mode == "Character", nav_command);
+            remove_literal_property(mathml, add_literal, properties);
+            pop_stack(nav_state, loop_count, nav_command);
+            return Ok( (speech + " " + &range_speech_result?, true) );
         } else {
             remove_literal_property(mathml, add_literal, properties);
             pop_stack(nav_state, loop_count, nav_command);
@@ -660,6 +676,15 @@ pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) ->
     }
 }
 
+/// Get the overview of the current navigation node (the whole expression if there hasn't been any navigation).
+pub(crate) fn overview_navigation_node(nav_state: &NavigationState, mathml: Element) -> Result<String> {
+    let (current_node, current_node_offset) = nav_state.get_navigation_mathml_id(mathml);
+    let nav_position = NavigationPosition { current_node, current_node_offset };
+    let new_package = Package::new();
+    let intent = crate::speech::intent_from_mathml(mathml, new_package.as_document())?;
+    return speak(mathml, intent, &nav_position, nav_state.mode == "Character", false);
+}
+
 /// Speak the intent tree at the nav_node_id if that id exists in the intent tree; otherwise use the mathml tree.
 /// If full_read is true, we speak the tree, otherwise we use the overview rules.
 /// If literal_speak is true, we use the literal speak rules (and use the mathml tree).
@@ -688,10 +713,66 @@ fn speak(mathml: Element, intent: Element, nav_position: &NavigationPosition, li
         // debug!("speech from speak: {:?}", speech);
         return speech;
     } else {
-        return crate::speech::overview_mathml(mathml, &nav_position.current_node, nav_position.current_node_offset);
+        // the overview is of the nav node, so there is no need to find it in the speech of the whole tree
+        let nav_node = if literal_speak {None} else {get_node_by_id(intent, nav_position)};
+        return match nav_node.or_else(|| get_node_by_id(mathml, nav_position)) {
+            Some(nav_node) => crate::speech::overview_mathml(nav_node),
+            None => bail!(crate::speech::NAV_NODE_SPEECH_NOT_FOUND),
+        };
     }
 }
 
+/// Speak from the node at `nav_position` to the node at `read_to_position` (used by the `ReadTo` commands).
+/// If the `read_to_position` node is an ancestor of the nav node (e.g., `math`), the speech goes from the nav node
+///   to the end of that node or from the start of that node to the nav node (for the `...Start` commands).
+/// The intent tree is tried first unless `literal_speak` is true; the MathML tree is used if that fails.
+fn speak_range(mathml: Element, intent: Element, nav_position: &NavigationPosition, read_to_position: &NavigationPosition,
+               literal_speak: bool, nav_command: &str) -> Result<String> {
+    if read_to_position.current_node.is_empty() || *read_to_position == NavigationPosition::default() {
+        bail!("'{}' failed: the placemarker has not been set", nav_command);
+    }
+    if !literal_speak {
+        match speak_range_in_tree(intent, nav_position, read_to_position, nav_command) {
+            Ok(speech) => return Ok(speech),
+            Err(e) => {
+                if e.to_string() != crate::speech::NAV_NODE_SPEECH_NOT_FOUND {
+                    return Err(e);
+                }
+                // else the nav node might not be in the intent tree (e.g., '3' in 'x^3') -- try the MathML tree
+            },
+        }
+    }
+    return speak_range_in_tree(mathml, nav_position, read_to_position, nav_command);
+
+    fn speak_range_in_tree(tree: Element, nav_position: &NavigationPosition, read_to_position: &NavigationPosition, nav_command: &str) -> Result<String> {
+        let (Some(nav_node), Some(read_to_node)) = (get_node_by_id(tree, nav_position), get_node_by_id(tree, read_to_position)) else {
+            bail!(crate::speech::NAV_NODE_SPEECH_NOT_FOUND);
+        };
+        let nav = Some( (nav_position.current_node.as_str(), nav_position.current_node_offset) );
+        if is_ancestor_or_self(read_to_node, nav_node) {
+            if nav_command.ends_with("Start") {
+                return crate::speech::speak_mathml_range(read_to_node, None, nav);
+            } else {
+                return crate::speech::speak_mathml_range(read_to_node, nav, None);
+            }
+        }
+        let read_to = Some( (read_to_position.current_node.as_str(), read_to_position.current_node_offset) );
+        return crate::speech::speak_mathml_range(tree, nav, read_to);
+    }
+
+    fn is_ancestor_or_self(ancestor: Element, node: Element) -> bool {
+        let mut node = node;
+        loop {
+            if node == ancestor {
+                return true;
+            }
+            match node.parent().and_then(|parent| parent.element()) {
+                Some(parent) => node = parent,
+                None => return false,
+            }
+        }
+    }
+}
 
 // MathPlayer's interface mentions these, so we keep them.
 // These (KeyboardEvent.keyCode) are consistent across platforms (mostly?) but are deprecated.
@@ -952,7 +1033,19 @@ fn navigation_command_string(command: NavigationCommand, param: NavigationParam)
             }
         },
         NavigationCommand::ReadTo => {
-            todo!("ReadTo navigation command")
+            return match param {
+                NavigationParam::Start => "ReadToStart",
+                NavigationParam::End => "ReadToEnd",
+                NavigationParam::LineStart => "ReadToLineStart",
+                NavigationParam::LineEnd => "ReadToLineEnd",
+                _ => {
+                    if param < NavigationParam::Placemarker0 || param > NavigationParam::Placemarker9 {
+                        panic!("Internal Error: Found illegal value for param of NavigationCommand::ReadTo");
+                    }
+                    static READ_TO_PLACE_MARKERS: &[&str] = &["ReadTo0","ReadTo1","ReadTo2","ReadTo3","ReadTo4","ReadTo5","ReadTo6","ReadTo7","ReadTo8","ReadTo9"];
+                    return READ_TO_PLACE_MARKERS[(param as usize) - (NavigationParam::Placemarker0 as usize)];
+                }
+            }
         },
         NavigationCommand::Locate => {
             if param ==NavigationParam::Previous {
@@ -1008,7 +1101,7 @@ mod tests {
                                         start_id, command, &crate::interface::errors_to_string(&e))
                 },
                 Ok(nav_speech) => {
-                    let nav_speech = nav_speech.trim_end_matches(&[' ', ',', ';']);
+                    let nav_speech = nav_speech.trim_end_matches([' ', ',', ';']);
                     // debug!("Full speech: {}", nav_speech);
                     if !result_id.is_empty() {
                         let (id, _) = nav_stack.borrow().get_navigation_mathml_id(mathml);
@@ -1383,6 +1476,128 @@ mod tests {
         });
     }
     
+    #[test]
+    fn read_to_end_and_start() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'>
+                <mi id='x'>x</mi><mo id='plus'>+</mo><mi id='y'>y</mi><mo id='minus'>-</mo><mi id='z'>z</mi>
+            </mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            test_command("ZoomInAll", mathml, "x");
+            test_command("MoveNext", mathml, "plus");
+            test_command("MoveNext", mathml, "y");
+            assert_eq!(test_command("ReadToEnd", mathml, "y"), "read to end of math; y minus z");
+            assert_eq!(test_command("ReadToStart", mathml, "y"), "read from start of math; x plus y");
+            let speech = do_navigate_command_and_param(mathml, NavigationCommand::ReadTo, NavigationParam::End)?;
+            assert_eq!(speech, "read to end of math; y minus z");
+            // the nav node didn't move
+            test_command("MoveNext", mathml, "minus");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn chemistry_as_compound() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'>
+                <msub id='msub'><mi id='H'>H</mi><mn id='two'>2</mn></msub><mi id='O'>O</mi>
+            </mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("Chemistry", "AsCompound").unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            assert_eq!(test_command("ReadCurrent", mathml, "math"), "read current; water");
+            assert_eq!(test_command("ZoomIn", mathml, "msub"), "zoom in; cap h sub 2");
+            test_command("ZoomOutAll", mathml, "mrow");
+
+            // spelled out when navigating by character
+            set_preference("NavMode", "Character").unwrap();
+            assert_eq!(test_command("ReadCurrent", mathml, "mrow"), "read current; cap h sub 2  cap o");
+            assert_eq!(test_command("ZoomInAll", mathml, "H"), "zoomed in all of the way; in base; cap h");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn read_to_line_end() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'><mfrac id='mfrac'>
+                <mrow id='num'><mi id='x'>x</mi><mo id='plus'>+</mo><mi id='y'>y</mi><mo id='minus'>-</mo><mi id='z'>z</mi></mrow>
+                <mi id='denom'>d</mi>
+            </mfrac><mo id='equals'>=</mo><mn id='one'>1</mn></mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            set_navigation_node_from_id(mathml, "y", 0)?;
+            assert_eq!(test_command("ReadToLineEnd", mathml, "y"), "read to end of line; y minus z");
+            assert_eq!(test_command("ReadToLineStart", mathml, "y"), "read from start of line; x plus y");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn mac_tts() -> Result<()> {
+        // the Mac TTS commands (e.g., "[[slnc 256]]") shouldn't be confused with the markers for the nav node
+        let mathml_str = "<math id='math'><mrow id='mrow'><mfrac id='mfrac'>
+                <mrow id='num'><mi id='x'>x</mi><mo id='plus'>+</mo><mi id='y'>y</mi></mrow>
+                <mi id='denom'>d</mi>
+            </mfrac><mo id='equals'>=</mo><mn id='one'>1</mn></mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("TTS", "Mac").unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            let fraction = "fraction [[slnc 200]] [[char LTRL]]x[[char NORM]]  plus  [[char LTRL]]y[[char NORM]] [[slnc 200]] over [[char LTRL]]d[[char NORM]] [[slnc 200]] end fraction [[slnc 400]]";
+            assert_eq!(test_command("ZoomIn", mathml, "mfrac"), format!("zoom in [[slnc 400]] {fraction}"));
+            assert_eq!(test_command("ReadCurrent", mathml, "mfrac"), format!("read current [[slnc 800]] {fraction}"));
+            assert_eq!(test_command("ReadToEnd", mathml, "mfrac"), format!("read to end of math [[slnc 400]] {fraction}is equal to  1"));
+            assert_eq!(test_command("ReadToStart", mathml, "mfrac"), format!("read from start of math [[slnc 400]] {fraction}"));
+            test_command("MoveNext", mathml, "equals");
+            assert_eq!(test_command("ReadToStart", mathml, "equals"), format!("read from start of math [[slnc 400]] {fraction}is equal to"));
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn read_to_placemarker() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'>
+                <mi id='x'>x</mi><mo id='plus'>+</mo><mi id='y'>y</mi><mo id='minus'>-</mo><mi id='z'>z</mi>
+            </mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            assert!(do_navigate_command_string(mathml, "ReadTo4").is_err(), "placemarker 4 was never set");
+            set_navigation_node_from_id(mathml, "x", 0)?;
+            test_command("SetPlacemarker4", mathml, "x");
+            test_command("MoveNext", mathml, "plus");
+            test_command("MoveNext", mathml, "y");
+            assert_eq!(test_command("ReadTo4", mathml, "y"), "read to placeholder 4; x plus y");
+            set_preference("NavVerbosity", "Terse").unwrap();
+            test_command("MoveNext", mathml, "minus");
+            test_command("MoveNext", mathml, "z");
+            assert_eq!(test_command("ReadTo4", mathml, "z"), "x plus y minus z");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn read_to_end_character_mode() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'>
+                <msup id='msup'><mi id='x'>x</mi><mn id='two'>2</mn></msup><mo id='plus'>+</mo><mn id='one'>1</mn>
+            </mrow></math>";
+        init_default_prefs(mathml_str, "Character");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            set_navigation_node_from_id(mathml, "two", 0)?;
+            assert_eq!(test_command("ReadToEnd", mathml, "two"), "read to end of math; 2 end super, plus 1");
+            return Ok( () );
+        });
+    }
+
     #[test]
     fn move_right_sup() -> Result<()> {
         let mathml_str = "<math display='block' id='id-0'>
@@ -1969,6 +2184,72 @@ mod tests {
         });
     }
     
+    #[test]
+    fn move_cell_mstack() -> Result<()> {
+        // the numbers get split into digits, so the ids of the digits are generated -- test the speech instead
+        let mathml_str = "<math id='nav-0'>
+          <mstack id='nav-1'>
+            <mscarries id='nav-2'><mscarry id='nav-3'><mn id='nav-4'>1</mn></mscarry><none id='nav-5'/><none id='nav-6'/></mscarries>
+            <mn id='nav-7'>478</mn>
+            <msrow id='nav-8'><mo id='nav-9'>+</mo><mn id='nav-10'>52</mn></msrow>
+            <msline id='nav-11'/>
+            <mn id='nav-12'>530</mn>
+          </mstack>
+        </math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            test_command("ZoomInAll", mathml, "nav-4");
+            assert_eq!(test_command("MoveCellDown", mathml, ""), "move down, 4");
+            assert_eq!(test_command("MoveCellNext", mathml, ""), "move right, 7");
+            assert_eq!(test_command("MoveCellDown", mathml, ""), "move down, 5");
+            assert_eq!(test_command("MoveCellDown", mathml, ""), "move down, 3");
+            assert_eq!(test_command("MoveCellDown", mathml, ""), "no next row");
+            assert_eq!(test_command("MoveCellNext", mathml, ""), "move right, 0");
+            assert_eq!(test_command("MoveCellNext", mathml, ""), "no next column");
+            assert_eq!(test_command("MoveColumnStart", mathml, ""), "blank");
+            assert_eq!(test_command("MoveCellPrevious", mathml, ""), "move left, blank");
+            test_command("MoveCellPrevious", mathml, "nav-3");
+            assert_eq!(test_command("MoveColumnEnd", mathml, ""), "5");
+
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn move_cell_mstack_in_other_languages() -> Result<()> {
+        let mathml_str = "<math id='nav-0'>
+          <mstack id='nav-1'>
+            <mscarries id='nav-2'><mscarry id='nav-3'><mn id='nav-4'>1</mn></mscarry><none id='nav-5'/><none id='nav-6'/></mscarries>
+            <mn id='nav-7'>478</mn>
+            <msrow id='nav-8'><mo id='nav-9'>+</mo><mn id='nav-10'>52</mn></msrow>
+            <msline id='nav-11'/>
+            <mn id='nav-12'>530</mn>
+          </mstack>
+        </math>";
+        for (language, expected) in [
+            ("de", ["gehe nach unten, 4", "rechts, 7", "keine vorherige spalte", "nach links, leer"]),
+            ("es", ["bajar, 4", "desplazarse a la derecha, 7", "sin columna anterior", "desplazarse a la izquierda, vacío"]),
+        ] {
+            init_prefs(mathml_str, "Enhanced", language);
+            MATHML_INSTANCE.with(|package_instance| {
+                let package_instance = package_instance.borrow();
+                let mathml = get_element(&package_instance);
+                test_command("ZoomInAll", mathml, "nav-4");
+                assert_eq!(test_command("MoveCellDown", mathml, ""), expected[0]);
+                assert_eq!(test_command("MoveCellNext", mathml, ""), expected[1]);
+                test_command("MoveCellPrevious", mathml, "");
+                assert_eq!(test_command("MoveCellPrevious", mathml, ""), expected[2]);
+                test_command("MoveColumnStart", mathml, "nav-3");
+                test_command("MoveCellNext", mathml, "nav-5");
+                test_command("MoveCellNext", mathml, "nav-6");
+                assert_eq!(test_command("MoveCellPrevious", mathml, ""), expected[3]);
+            });
+        }
+        return Ok( () );
+    }
+    
     #[test]
     fn move_cell_char_mode() -> Result<()> {
         let mathml_str = "<math id='nav-0'>
@@ -2567,7 +2848,7 @@ mod tests {
             let speech = test_command("ReadCurrent", mathml, "frac");
             assert_eq!(speech, "read current; fraction, b plus 1, over 3, end fraction");
             let speech = test_command("DescribeCurrent", mathml, "frac");
-            assert_eq!(speech, "describe current; fraction");
+            assert_eq!(speech, "describe current; a fraction with numerator b plus 1 and denominator 3");
             return Ok( () );
         });
     }
@@ -2601,7 +2882,7 @@ mod tests {
             assert_eq!("переход внутрь; в числитель; икс  плюс, квадратный корень из 1 разделить на игрек, конец корня", speech);
             match do_navigate_command_string(mathml, "DescribeCurrent") {
                 Ok(speech) => {
-                    let speech = speech.trim_end_matches(&[' ', ',', ';']).to_string();
+                    let speech = speech.trim_end_matches([' ', ',', ';']).to_string();
                     assert_eq!("описать текущее; икс  плюс, квадратный корень из 1 разделить на игрек", speech);
                 },
                 Err(e) => {
@@ -2636,12 +2917,82 @@ mod tests {
             let speech = test_command("ReadCurrent", mathml, "mrow");
             assert_eq!(speech, "read current; 1 plus 2 plus 3 plus 4 plus 5 plus 6 plus 7");
             let speech = test_command("DescribeCurrent", mathml, "mrow");
-            assert_eq!(speech, "describe current; 1 plus 2 plus 3 and so on");
+            assert_eq!(speech, "describe current; a sum of 7 terms");
             return Ok( () );
         });
     }
 
 
+    #[test]
+    fn describe_equation() -> Result<()> {
+        let mathml_str = "<math id='math'>
+            <mrow id='mrow'>
+                <mfrac id='frac'>
+                    <mrow id='numerator'><mi>x</mi><mo>+</mo><mn>1</mn></mrow>
+                    <mn id='denom'>2</mn>
+                </mfrac>
+                <mo id='equal'>=</mo>
+                <mrow id='rhs'>
+                    <msup><mi>a</mi><mn>2</mn></msup><mo>+</mo>
+                    <mi>b</mi><mo>-</mo>
+                    <mi>c</mi><mo>+</mo>
+                    <mn>3</mn>
+                </mrow>
+            </mrow>
+        </math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            assert_eq!(crate::interface::get_overview_text()?,
+                       "an equation with a fraction on the left and a sum of 4 terms on the right");
+            assert_eq!(crate::interface::get_navigation_overview_text()?,
+                       "an equation with a fraction on the left and a sum of 4 terms on the right");
+            test_command("ZoomIn", mathml, "frac");
+            assert_eq!(crate::interface::get_navigation_overview_text()?, "a fraction with numerator x plus 1 and denominator 2");
+            test_command("MoveNext", mathml, "equal");
+            let speech = test_command("DescribeNext", mathml, "equal");
+            assert_eq!(speech, "describe right; a sum of 4 terms");
+
+            set_preference("OverviewDepth", "0").unwrap();
+            assert_eq!(crate::interface::get_overview_text()?, "an equation");
+            set_preference("OverviewDepth", "2").unwrap();
+            set_preference("OverviewLength", "30").unwrap();
+            assert_eq!(crate::interface::get_overview_text()?,
+                       "an equation with a fraction with numerator x plus 1 and denominator 2 on the left and a sum of 4 terms on the right");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn describe_in_other_languages() -> Result<()> {
+        let matrix = "<math><mrow><mo>(</mo><mtable>
+                <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+                <mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd><mtd><mn>3</mn></mtd></mtr>
+                <mtr><mtd><mn>4</mn></mtd><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr>
+            </mtable><mo>)</mo></mrow></math>";
+        let system = "<math><mrow><mo>{</mo><mtable>
+                <mtr><mtd><mi>x</mi><mo>+</mo><mi>y</mi></mtd><mtd><mo>=</mo></mtd><mtd><mn>3</mn></mtd></mtr>
+                <mtr><mtd><mi>x</mi><mo>-</mo><mi>y</mi></mtd><mtd><mo>=</mo></mtd><mtd><mn>1</mn></mtd></mtr>
+            </mtable></mrow></math>";
+        let equation = "<math><mfrac><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></mfrac><mo>=</mo>
+                <mrow><msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><mi>b</mi><mo>-</mo><mi>c</mi><mo>+</mo><mn>3</mn></mrow></math>";
+        init_prefs(matrix, "Enhanced", "de");
+        assert_eq!(crate::interface::get_overview_text()?, "eine 3 mal 3 matrix");
+        set_mathml(system).unwrap();
+        assert_eq!(crate::interface::get_overview_text()?, "ein gleichungssystem mit 2 gleichungen");
+        set_mathml(equation).unwrap();
+        assert_eq!(crate::interface::get_overview_text()?, "eine gleichung links ein bruch mit zähler x plus 1 und nenner 2 und rechts eine summe aus 4 termen");
+
+        init_prefs(matrix, "Enhanced", "es");
+        assert_eq!(crate::interface::get_overview_text()?, "una matriz de 3 por 3");
+        set_mathml(system).unwrap();
+        assert_eq!(crate::interface::get_overview_text()?, "un sistema de 2 ecuaciones");
+        set_mathml(equation).unwrap();
+        assert_eq!(crate::interface::get_overview_text()?, "una ecuación con una fracción a la izquierda y una suma de 4 términos a la derecha");
+        return Ok( () );
+    }
+
     #[test]
     fn read_next_invisible_char() -> Result<()> {
         let mathml_str = "<math id='id-0'>
@@ -36,6 +36,11 @@ use crate::errors::*;
 /// Use to indicate preference not found with Preference::to_string()
 pub static NO_PREFERENCE: &str = "\u{FFFF}";
 
+/// Prefix of the preferences that override the speech for a character or an intent name
+///   (e.g., "SpeechOverrides_Symbols_(" or "SpeechOverrides_Symbols_plus-or-minus")
+/// These come from the `SpeechOverrides: Symbols:` dictionary in prefs.yaml or can be set with `set_preference`.
+static SYMBOL_OVERRIDES_PREFIX: &str = "SpeechOverrides_Symbols_";
+
 static DEFAULT_LANG: LazyLock<Yaml> = LazyLock::new(|| Yaml::String("en".to_string()));
 
 
@@ -68,16 +73,25 @@ impl Preferences{
         prefs.insert("SpeechStyle".to_string(), Yaml::String("ClearSpeak".to_string()));
         prefs.insert("Verbosity".to_string(), Yaml::String("Medium".to_string()));
         prefs.insert("SpeechOverrides_CapitalLetters".to_string(), Yaml::String("".to_string())); // important for testing
+        prefs.insert("SpeechOverrides_LeftParen".to_string(), Yaml::String("".to_string()));
+        prefs.insert("SpeechOverrides_RightParen".to_string(), Yaml::String("".to_string()));
+        prefs.insert("SpeechSound".to_string(), Yaml::String("None".to_string()));
+        prefs.insert("Earcons_Capital".to_string(), Yaml::String("beep.mp4".to_string()));
+        prefs.insert("ElementaryMath".to_string(), Yaml::String("Rows".to_string()));
         prefs.insert("Blind".to_string(), Yaml::Boolean(true));
         prefs.insert("MathRate".to_string(), Yaml::Real("100.0".to_string()));
         prefs.insert("PauseFactor".to_string(), Yaml::Real("100.0".to_string()));
         prefs.insert("NavMode".to_string(), Yaml::String("Enhanced".to_string()));
         prefs.insert("Overview".to_string(), Yaml::Boolean(false));
         prefs.insert("ResetOverView".to_string(), Yaml::Boolean(true));
+        prefs.insert("OverviewDepth".to_string(), Yaml::Real("2.0".to_string()));
+        prefs.insert("OverviewLength".to_string(), Yaml::Real("20.0".to_string()));
         prefs.insert("NavVerbosity".to_string(), Yaml::String("Verbose".to_string()));
         prefs.insert("AutoZoomOut".to_string(), Yaml::Boolean(true));
         prefs.insert("BrailleCode".to_string(), Yaml::String("Nemeth".to_string()));
         prefs.insert("BrailleNavHighlight".to_string(), Yaml::String("EndPoints".to_string()));
+        prefs.insert("BrailleLineLength".to_string(), Yaml::Real("0.0".to_string()));
+        prefs.insert("BrailleEncoding".to_string(), Yaml::String("Unicode".to_string()));
         prefs.insert("UEB_START_MODE".to_string(), Yaml::String("Grade2".to_string()));
         prefs.insert("DecimalSeparators".to_string(), Yaml::String(".".to_string()));
         prefs.insert("BlockSeparators".to_string(), Yaml::String(", \u{00A0}\u{202F}".to_string()));
@@ -166,7 +180,7 @@ impl Preferences{
                         format!("name '{}' is not a string in file {}", yaml_to_string(yaml_name, 0), file_name)));
                 } else {
                     match yaml_value {
-                        Yaml::Hash(_) => add_prefs(map, yaml_value, &(name.unwrap().to_string() + "_"), file_name),
+                        Yaml::Hash(_) => add_prefs(map, yaml_value, &(name_prefix.to_string() + name.unwrap().trim() + "_"), file_name),
                         Yaml::Array(_) => error!("name '{}' has illegal array value {} in file '{}'",
                                                  yaml_to_string(yaml_name, 0), yaml_to_string(yaml_value, 0), file_name),
                         Yaml::String(_) | Yaml::Boolean(_) | Yaml::Integer(_) | Yaml::Real(_) => {
@@ -717,6 +731,9 @@ impl PreferenceManager {
             "none" => TTS::None,
             "ssml" => TTS::SSML,
             "sapi5" => TTS::SAPI5,
+            "eloquence" => TTS::Eloquence,
+            "mac" => TTS::Mac,
+            "segments" => TTS::Segments,
             _ => {
                 warn!("found unknown value for TTS: '{}'", self.pref_to_string("TTS").as_str());
                 TTS::None
@@ -752,9 +769,9 @@ impl PreferenceManager {
             if pref_value.as_str().unwrap() != value {
                 self.reset_files_from_preference_change(key, value)?;
             }
-        } else {
+        } else if !key.starts_with(SYMBOL_OVERRIDES_PREFIX) || key.len() == SYMBOL_OVERRIDES_PREFIX.len() {
             bail!("{} is an unknown MathCAT preference!", key);
-        }
+        }   // else a new symbol override (these are user prefs)
 
         // debug!("Setting ({}) {} to '{}'", if is_user_pref {"user"} else {"sys"}, key, value);
         if is_user_pref {
@@ -825,6 +842,30 @@ impl PreferenceManager {
         self.api_prefs.prefs.insert(key.to_string(), Yaml::Boolean(value));
     }
 
+    /// Returns the word(s) the user wants spoken for `ch` (from the `SpeechOverrides` preferences) if there is an override.
+    ///
+    /// `LeftParen`/`RightParen` are used for '(' and ')'; other characters use the `SpeechOverrides_Symbols_` + `ch` preference.
+    /// An empty value means there is no override.
+    pub fn get_speech_override(&self, ch: char) -> Option<String> {
+        let named_override = match ch {
+            '(' => self.pref_to_string("SpeechOverrides_LeftParen"),
+            ')' => self.pref_to_string("SpeechOverrides_RightParen"),
+            _ => String::new(),
+        };
+        if named_override.is_empty() || named_override == NO_PREFERENCE {
+            return self.get_intent_speech_override(&ch.to_string());
+        }
+        return Some(named_override);
+    }
+
+    /// Returns the word(s) the user wants spoken for the intent `intent_name` (e.g., "plus-or-minus") if there is an override.
+    ///
+    /// These share the `SpeechOverrides_Symbols_` preferences with the character overrides. An empty value means there is no override.
+    pub fn get_intent_speech_override(&self, intent_name: &str) -> Option<String> {
+        let word = self.pref_to_string(&format!("{SYMBOL_OVERRIDES_PREFIX}{intent_name}"));
+        return if word.is_empty() || word == NO_PREFERENCE {None} else {Some(word)};
+    }
+
     /// Return the current speech rate.
     pub fn get_rate(&self) -> f64 {
         if !self.error.is_empty() {
@@ -1216,6 +1257,29 @@ cfg_if::cfg_if! {if #[cfg(not(feature = "include-zip"))] {
         });
     }
 
+    #[test]
+    fn test_symbol_overrides() {
+        let file = std::env::temp_dir().join("mathcat_test_symbol_overrides_prefs.yaml");
+        std::fs::write(&file, "Speech:\n  SpeechOverrides:\n    LeftParen: 'left paren'\n    Symbols:\n      '-': 'take away'\nNavigation: {}\nBraille: {}\nOther: {}\n").unwrap();
+        let prefs = Preferences::read_prefs_file(&file, Preferences::user_defaults()).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(prefs.prefs.get("SpeechOverrides_Symbols_-"), Some(&Yaml::String("take away".to_string())));
+
+        PREF_MANAGER.with(|pref_manager| {
+            let mut pref_manager = pref_manager.borrow_mut();
+            pref_manager.initialize(abs_rules_dir_path()).unwrap();
+            pref_manager.user_prefs.prefs.extend(prefs.prefs);
+            assert_eq!(pref_manager.get_speech_override('(').unwrap(), "left paren");
+            assert_eq!(pref_manager.get_speech_override('-').unwrap(), "take away");
+            assert!(pref_manager.get_speech_override(')').is_none());
+            pref_manager.set_string_pref("SpeechOverrides_Symbols_)", "right paren").unwrap();
+            assert_eq!(pref_manager.get_speech_override(')').unwrap(), "right paren");
+            pref_manager.set_string_pref("SpeechOverrides_Symbols_plus-or-minus", "give or take").unwrap();
+            assert_eq!(pref_manager.get_intent_speech_override("plus-or-minus").unwrap(), "give or take");
+            assert!(pref_manager.get_intent_speech_override("minus-or-plus").is_none());
+        });
+    }
+
     #[test]
     #[ignore]   // this is an ugly test for #262 -- it changes the prefs file and so is a bad thing in general
     fn test_up_to_date() {
@@ -1238,7 +1302,7 @@ cfg_if::cfg_if! {if #[cfg(not(feature = "include-zip"))] {
             let pref_manager = pref_manager.borrow();
             if let Some(file_name) = pref_manager.user_prefs_file.as_ref().unwrap().debug_get_file() {
                 file_path = PathBuf::from(file_name);
-                contents = fs::read(&file_path).expect(&format!("Failed to write file {} during test", file_name));
+                contents = fs::read(&file_path).unwrap_or_else(|_| panic!("Failed to write file {} during test", file_name));
                 let changed_contents = String::from_utf8(contents.clone()).unwrap()
                                 .replace("SpeechStyle: ClearSpeak", "SpeechStyle: SimpleSpeak");
                 fs::write(&file_path, changed_contents).unwrap();
@@ -0,0 +1,363 @@
+//! The Python API for MathCAT (built with the `python` feature). It mirrors the functions in [`crate::interface`]
+//! and uses the same (snake_case) names.
+//!
+//! Build and install the module with [maturin](https://github.com/PyO3/maturin) (e.g., `maturin develop --features python`),
+//! then `import libmathcat`. The tests in this file are run with `cargo test --features python` (they need Python installed).
+//!
+//! Conventions:
+//! * Errors are raised as `libmathcat.MathCATError` whose message is the full MathCAT error (see [`errors_to_string`]).
+//! * Rust tuples are returned as Python tuples. The navigation functions return `(speech, id, offset)`
+//!   so that the caller knows the new navigation node without another call.
+//!
+//! MathCAT's state (rules dir, preferences, MathML, navigation) is thread local in Rust, but Python programs expect a module's state
+//! to be shared by all their threads. All calls are therefore run on a single MathCAT thread;
+//! the GIL is released while waiting for the result so other Python threads can run.
+#![allow(clippy::needless_return)]
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::sync::mpsc::{channel, Sender};
+use pyo3::prelude::*;
+use pyo3::create_exception;
+use crate::errors::*;
+use crate::interface::{self, errors_to_string};
+
+create_exception!(libmathcat, MathCATError, pyo3::exceptions::PyException, "An error reported by MathCAT.");
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A speech segment as `(text, id, pause, pitch, rate)` (see [`crate::SpeechSegment`])
+type SpeechSegment = (String, String, f64, f64, f64);
+/// Braille lines as `(lines, cells)` (see [`crate::BrailleLines`])
+type BrailleLines = (Vec<String>, Vec<Vec<(String, usize)>>);
+
+/// The thread that all the MathCAT calls are run on (see the module comment)
+static MATHCAT_THREAD: OnceLock<Sender<Job>> = OnceLock::new();
+
+fn mathcat_thread() -> &'static Sender<Job> {
+    return MATHCAT_THREAD.get_or_init(|| {
+        let (sender, receiver) = channel::<Job>();
+        std::thread::Builder::new()
+            .name("MathCAT".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    // 'interface' catches panics, but a panic here shouldn't take down the thread for the following calls
+                    let _ignore_result = catch_unwind(AssertUnwindSafe(job));
+                }
+            })
+            .expect("Couldn't start the MathCAT thread");
+        sender
+    });
+}
+
+/// Run 'f' on the MathCAT thread and convert any error to a `MathCATError`
+fn run<T: Send + 'static>(py: Python<'_>, f: impl FnOnce() -> Result<T> + Send + 'static) -> PyResult<T> {
+    let (result_sender, result_receiver) = channel();
+    let job: Job = Box::new(move || {
+        let _ignore_result = result_sender.send(f());
+    });
+    let result = py.detach(move || {
+        if mathcat_thread().send(job).is_err() {
+            return None;
+        }
+        return result_receiver.recv().ok();
+    });
+    return match result {
+        Some(Ok(answer)) => Ok(answer),
+        Some(Err(e)) => Err(MathCATError::new_err(errors_to_string(&e))),
+        None => Err(MathCATError::new_err("MathCAT crashed -- this is a bug in MathCAT")),
+    };
+}
+
+/// The speech for a navigation move along with the `id` and offset of the new navigation node
+fn with_navigation_node(speech: String) -> Result<(String, String, usize)> {
+    let (id, offset) = interface::get_navigation_mathml_id()?;
+    return Ok( (speech, id, offset) );
+}
+
+/// Set the directory that contains the Rules (e.g., ".../Rules")
+#[pyfunction]
+fn set_rules_dir(py: Python<'_>, dir: String) -> PyResult<()> {
+    return run(py, move || interface::set_rules_dir(dir));
+}
+
+/// The version number of MathCAT
+#[pyfunction]
+fn get_version() -> String {
+    return interface::get_version();
+}
+
+/// Set the MathML to speak/braille/navigate; returns the canonical MathML
+#[pyfunction]
+fn set_mathml(py: Python<'_>, mathml: String) -> PyResult<String> {
+    return run(py, move || interface::set_mathml(mathml));
+}
+
+/// Set the math using LaTeX; returns the canonical MathML
+#[pyfunction]
+fn set_latex(py: Python<'_>, latex: String) -> PyResult<String> {
+    return run(py, move || interface::set_latex(latex));
+}
+
+/// Set the math using ASCIIMath; returns the canonical MathML
+#[pyfunction]
+fn set_asciimath(py: Python<'_>, asciimath: String) -> PyResult<String> {
+    return run(py, move || interface::set_asciimath(asciimath));
+}
+
+/// The speech for the MathML that was set
+#[pyfunction]
+fn get_spoken_text(py: Python<'_>) -> PyResult<String> {
+    return run(py, interface::get_spoken_text);
+}
+
+/// The speech for the MathML that was set as a list of `(text, id, pause, pitch, rate)`
+#[pyfunction]
+fn get_spoken_segments(py: Python<'_>) -> PyResult<Vec<SpeechSegment>> {
+    return run(py, || {
+        let segments = interface::get_spoken_segments()?;
+        return Ok( segments.into_iter()
+            .map(|segment| (segment.text, segment.id, segment.pause, segment.pitch, segment.rate))
+            .collect() );
+    });
+}
+
+/// An overview of the MathML that was set
+#[pyfunction]
+fn get_overview_text(py: Python<'_>) -> PyResult<String> {
+    return run(py, interface::get_overview_text);
+}
+
+/// An overview of the current navigation node
+#[pyfunction]
+fn get_navigation_overview_text(py: Python<'_>) -> PyResult<String> {
+    return run(py, interface::get_navigation_overview_text);
+}
+
+/// The value of the preference 'name'
+#[pyfunction]
+fn get_preference(py: Python<'_>, name: String) -> PyResult<String> {
+    return run(py, move || interface::get_preference(name));
+}
+
+/// Set the preference 'name' to 'value'
+#[pyfunction]
+fn set_preference(py: Python<'_>, name: String, value: String) -> PyResult<()> {
+    return run(py, move || interface::set_preference(name, value));
+}
+
+/// The braille for the MathML that was set; if 'nav_node_id' is given, that node is highlighted
+#[pyfunction]
+#[pyo3(signature = (nav_node_id = String::new()))]
+fn get_braille(py: Python<'_>, nav_node_id: String) -> PyResult<String> {
+    return run(py, move || interface::get_braille(nav_node_id));
+}
+
+/// The braille for the MathML that was set broken into lines of at most 'line_length' cells.
+/// Returns `(lines, cells)` where `cells[i]` is a list of the `(id, offset)` for each cell in `lines[i]`.
+#[pyfunction]
+#[pyo3(signature = (line_length, nav_node_id = String::new()))]
+fn get_braille_lines(py: Python<'_>, line_length: usize, nav_node_id: String) -> PyResult<BrailleLines> {
+    return run(py, move || {
+        let braille_lines = interface::get_braille_lines(nav_node_id, line_length)?;
+        return Ok( (braille_lines.lines, braille_lines.cells) );
+    });
+}
+
+/// Convert braille in the `BrailleEncoding` to Unicode braille
+#[pyfunction]
+fn braille_to_unicode(py: Python<'_>, braille: String) -> PyResult<String> {
+    return run(py, move || interface::braille_to_unicode(braille));
+}
+
+/// Convert braille in the `BrailleCode` to MathML
+#[pyfunction]
+fn braille_to_mathml(py: Python<'_>, braille: String) -> PyResult<String> {
+    return run(py, move || interface::braille_to_mathml(braille));
+}
+
+/// The braille for the MathML with the current navigation node highlighted
+#[pyfunction]
+fn get_navigation_braille(py: Python<'_>) -> PyResult<String> {
+    return run(py, interface::get_navigation_braille);
+}
+
+/// The MathML for the current navigation node (for copying)
+#[pyfunction]
+fn get_navigation_copy(py: Python<'_>) -> PyResult<String> {
+    return run(py, interface::get_navigation_copy);
+}
+
+/// Navigate using a key (a JavaScript-style key code) and the modifier keys; returns `(speech, id, offset)` for the new navigation node
+#[pyfunction]
+#[pyo3(signature = (key, shift_key = false, control_key = false, alt_key = false, meta_key = false))]
+fn do_navigate_keypress(py: Python<'_>, key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool) -> PyResult<(String, String, usize)> {
+    return run(py, move || {
+        let speech = interface::do_navigate_keypress(key, shift_key, control_key, alt_key, meta_key)?;
+        return with_navigation_node(speech);
+    });
+}
+
+/// Navigate using a command (e.g., "ZoomIn"); returns `(speech, id, offset)` for the new navigation node
+#[pyfunction]
+fn do_navigate_command(py: Python<'_>, command: String) -> PyResult<(String, String, usize)> {
+    return run(py, move || {
+        let speech = interface::do_navigate_command(command)?;
+        return with_navigation_node(speech);
+    });
+}
+
+/// Set the current navigation node
+#[pyfunction]
+#[pyo3(signature = (id, offset = 0))]
+fn set_navigation_node(py: Python<'_>, id: String, offset: usize) -> PyResult<()> {
+    return run(py, move || interface::set_navigation_node(id, offset));
+}
+
+/// The `(mathml, offset)` of the current navigation node
+#[pyfunction]
+fn get_navigation_mathml(py: Python<'_>) -> PyResult<(String, usize)> {
+    return run(py, interface::get_navigation_mathml);
+}
+
+/// The `(id, offset)` of the current navigation node
+#[pyfunction]
+fn get_navigation_mathml_id(py: Python<'_>) -> PyResult<(String, usize)> {
+    return run(py, interface::get_navigation_mathml_id);
+}
+
+/// The `(start, end)` of the braille cells for the current navigation node
+#[pyfunction]
+fn get_braille_position(py: Python<'_>) -> PyResult<(usize, usize)> {
+    return run(py, interface::get_braille_position);
+}
+
+/// The `(id, offset)` of the navigation node for the braille cell at 'position'
+#[pyfunction]
+fn get_navigation_node_from_braille_position(py: Python<'_>, position: usize) -> PyResult<(String, usize)> {
+    return run(py, move || interface::get_navigation_node_from_braille_position(position));
+}
+
+/// The supported braille codes
+#[pyfunction]
+fn get_supported_braille_codes(py: Python<'_>) -> PyResult<Vec<String>> {
+    return run(py, interface::get_supported_braille_codes);
+}
+
+/// The supported languages (e.g., "en", "en-gb")
+#[pyfunction]
+fn get_supported_languages(py: Python<'_>) -> PyResult<Vec<String>> {
+    return run(py, interface::get_supported_languages);
+}
+
+/// The supported speech styles for 'lang'
+#[pyfunction]
+fn get_supported_speech_styles(py: Python<'_>, lang: String) -> PyResult<Vec<String>> {
+    return run(py, move || interface::get_supported_speech_styles(lang));
+}
+
+/// MathCAT: speech, braille, and navigation of MathML
+#[pymodule]
+#[pyo3(name = "libmathcat")]
+fn libmathcat_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("MathCATError", m.py().get_type::<MathCATError>())?;
+    m.add_function(wrap_pyfunction!(set_rules_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(get_version, m)?)?;
+    m.add_function(wrap_pyfunction!(set_mathml, m)?)?;
+    m.add_function(wrap_pyfunction!(set_latex, m)?)?;
+    m.add_function(wrap_pyfunction!(set_asciimath, m)?)?;
+    m.add_function(wrap_pyfunction!(get_spoken_text, m)?)?;
+    m.add_function(wrap_pyfunction!(get_spoken_segments, m)?)?;
+    m.add_function(wrap_pyfunction!(get_overview_text, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_overview_text, m)?)?;
+    m.add_function(wrap_pyfunction!(get_preference, m)?)?;
+    m.add_function(wrap_pyfunction!(set_preference, m)?)?;
+    m.add_function(wrap_pyfunction!(get_braille, m)?)?;
+    m.add_function(wrap_pyfunction!(get_braille_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(braille_to_unicode, m)?)?;
+    m.add_function(wrap_pyfunction!(braille_to_mathml, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_braille, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_copy, m)?)?;
+    m.add_function(wrap_pyfunction!(do_navigate_keypress, m)?)?;
+    m.add_function(wrap_pyfunction!(do_navigate_command, m)?)?;
+    m.add_function(wrap_pyfunction!(set_navigation_node, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_mathml, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_mathml_id, m)?)?;
+    m.add_function(wrap_pyfunction!(get_braille_position, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation_node_from_braille_position, m)?)?;
+    m.add_function(wrap_pyfunction!(get_supported_braille_codes, m)?)?;
+    m.add_function(wrap_pyfunction!(get_supported_languages, m)?)?;
+    m.add_function(wrap_pyfunction!(get_supported_speech_styles, m)?)?;
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyList;
+
+    /// Call 'f' with the `libmathcat` module (its rules dir is set to MathCAT's Rules dir)
+    fn with_module(f: impl FnOnce(&Bound<'_, PyModule>) -> PyResult<()>) {
+        // all the calls share the one MathCAT thread, so the tests can't run at the same time
+        static ONE_AT_A_TIME: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _lock = ONE_AT_A_TIME.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "libmathcat")?;
+            libmathcat_module(&module)?;
+            module.getattr("set_rules_dir")?.call1((crate::abs_rules_dir_path(),))?;
+            module.getattr("set_preference")?.call1(("Language", "en"))?;
+            module.getattr("set_preference")?.call1(("SpeechStyle", "ClearSpeak"))?;
+            return f(&module);
+        }).unwrap();
+    }
+
+    #[test]
+    fn speech() {
+        with_module(|module| {
+            let mathml: String = module.getattr("set_mathml")?.call1(("<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>",))?.extract()?;
+            assert!(mathml.contains("<mfrac"));
+            let speech: String = module.getattr("get_spoken_text")?.call0()?.extract()?;
+            assert_eq!(speech, "1 half");
+            let segments = module.getattr("get_spoken_segments")?.call0()?;
+            let segments = segments.cast::<PyList>()?;
+            let (text, ..): SpeechSegment = segments.get_item(0)?.extract()?;
+            assert_eq!(text, "1 half");
+            return Ok(());
+        });
+    }
+
+    #[test]
+    fn navigation_returns_the_new_node() {
+        with_module(|module| {
+            module.getattr("set_mathml")?.call1(("<math><mi>x</mi><mo>+</mo><mn>2</mn></math>",))?;
+            let (speech, id, offset): (String, String, usize) = module.getattr("do_navigate_command")?.call1(("ZoomIn",))?.extract()?;
+            assert!(!speech.is_empty());
+            let (current_id, current_offset): (String, usize) = module.getattr("get_navigation_mathml_id")?.call0()?.extract()?;
+            assert_eq!((id, offset), (current_id, current_offset));
+            return Ok(());
+        });
+    }
+
+    #[test]
+    fn errors_are_mathcat_errors() {
+        with_module(|module| {
+            let py = module.py();
+            let err = module.getattr("set_mathml")?.call1(("<math><mi>x</mi>",)).unwrap_err();
+            assert!(err.is_instance_of::<MathCATError>(py));
+            let err = module.getattr("set_preference")?.call1(("NotAPreference", "1")).unwrap_err();
+            assert!(err.is_instance_of::<MathCATError>(py));
+            assert!(err.value(py).to_string().contains("NotAPreference"));
+            let err = module.getattr("do_navigate_command")?.call1(("NotACommand",)).unwrap_err();
+            assert!(err.is_instance_of::<MathCATError>(py));
+            // the wrong type for an argument is a Python error, not a MathCAT error
+            let err = module.getattr("set_mathml")?.call1((1,)).unwrap_err();
+            assert!(!err.is_instance_of::<MathCATError>(py));
+            // MathCAT still works after an error
+            module.getattr("set_mathml")?.call1(("<math><mi>x</mi></math>",))?;
+            let speech: String = module.getattr("get_spoken_text")?.call0()?.extract()?;
+            assert_eq!(speech, "x");
+            return Ok(());
+        });
+    }
+}
@@ -30,6 +30,7 @@ cfg_if! {
         }
 
         use std::cell::RefCell;
+        use std::rc::Rc;
         use std::io::Cursor;
         use std::io::Read;
         use std::collections::{HashMap, HashSet};
@@ -56,32 +57,65 @@ cfg_if! {
             return Ok(archive);
         }
 
+        thread_local! {
+            // The embedded zip files (e.g., "Rules/Languages/en/en.zip") are only unzipped when they are needed.
+            // This maps the index of an embedded zip file in ZIPPED_RULE_FILES to its (uncompressed) bytes once it has been read.
+            static EMBEDDED_ZIP_FILES: RefCell<HashMap<usize, Rc<Vec<u8>>>> = RefCell::new(HashMap::with_capacity(31));
+        }
+
         /// Read ZIPPED_RULE_FILES and build up the FILES and DIRECTORIES static variables.
         /// This is called lazily when the first file or directory check is done.
+        /// Only the top-level zip file is read -- the embedded (language and braille code) zip files are read on demand
+        /// by `expand_embedded_zip_files` so that startup only pays for the languages/braille codes that are used.
         fn initialize_static_vars() -> Result<()> {
             let mut archive = get_zip_archive(ZIPPED_RULE_FILES)?;
-            read_zip_file("", &mut archive, None)?;
+            return read_zip_file("", &mut archive, None);
+        }
+
+        /// Make sure the static vars are initialized
+        fn assure_static_vars_initialized() {
+            if FILES.with(|files| files.borrow().is_empty()) {
+                let _ignore_result = initialize_static_vars();
+            }
+        }
 
+        /// Unzip all the embedded zip files that haven't yet been unzipped and whose containing dir satisfies 'is_needed'.
+        /// The files and dirs in them are added to FILES and DIRECTORIES.
+        /// Returns true if any zip file was unzipped.
+        fn expand_embedded_zip_files(is_needed: impl Fn(&Path) -> bool) -> Result<bool> {
             // Because of Rust's borrow checker, we can't recursively unzip contained zip files (FILES, etc., are borrowed mut)
-            // Here we gather up the zip files that were found and iterate over them non-recursively.
+            // Here we gather up the zip files that are needed and iterate over them non-recursively.
             // Note: there shouldn't be embedded zip files in these files (if there are, they won't be unzipped)
             let zip_files = FILES.with(|files| files.borrow().iter()
-                .filter_map(|(name, archive_path)| if name.ends_with(".zip") { Some((name.clone(), *archive_path)) } else { None } )
+                .filter_map(|(name, archive_path)| {
+                    let containing_dir = name.rsplit_once("/").map(|(before, _)| before).unwrap_or("");
+                    if name.ends_with(".zip") && archive_path.zipped.is_none() && is_needed(Path::new(containing_dir)) &&
+                       !EMBEDDED_ZIP_FILES.with(|zip_files| zip_files.borrow().contains_key(&archive_path.main)) {
+                        Some((containing_dir.to_string(), archive_path.main))
+                    } else {
+                        None
+                    }
+                })
                 .collect::<Vec<_>>()
             );
-            // debug!("Found {:?} embedded zip files", zip_files);
-            for (zip_file_name, archive_path) in zip_files.iter() {
-                let bytes = get_bytes_from_index(&mut archive, archive_path.main)?;
+            if zip_files.is_empty() {
+                return Ok(false);
+            }
+            let mut archive = get_zip_archive(ZIPPED_RULE_FILES)?;
+            for (containing_dir, main) in zip_files {
+                let bytes = Rc::new(get_bytes_from_index(&mut archive, main)?);
                 let mut inner_archive = get_zip_archive(bytes.as_slice())?;
-                // debug!("  internal zip file {} has {} files", zip_file_name, inner_archive.len());
-                let new_containing_dir = zip_file_name.rsplit_once("/").map(|(before, _)| before).unwrap_or("");
-                read_zip_file(new_containing_dir, &mut inner_archive, Some(archive_path.main))?;
+                // debug!("  internal zip file in {} has {} files", containing_dir, inner_archive.len());
+                read_zip_file(&containing_dir, &mut inner_archive, Some(main))?;
+                EMBEDDED_ZIP_FILES.with(|zip_files| zip_files.borrow_mut().insert(main, bytes));
             }
-            // FILES.with(|files| {
-            //     let files = files.borrow();
-            //     debug!("{} files={:?}", files.len(), files);
-            // });
-            return Ok(());
+            return Ok(true);
+        }
+
+        /// Unzip any embedded zip files that could contain 'path' (i.e., they are in 'path' or in a dir containing it).
+        /// Returns true if any zip file was unzipped.
+        fn expand_embedded_zip_files_for(path: &Path) -> bool {
+            return expand_embedded_zip_files(|containing_dir| path.starts_with(containing_dir)).unwrap_or(false);
         }
 
         /// Get the bytes for a file in the zip archive (intended for embedded zip files)
@@ -141,23 +175,23 @@ cfg_if! {
         }
 
         pub fn is_file_shim(path: &Path) -> bool {
-            if FILES.with(|files| files.borrow().is_empty()) {
-                let _ignore_result = initialize_static_vars();
-            }
-            return FILES.with(|files| files.borrow().contains_key(&canonicalize_path_separators(path)) );
+            assure_static_vars_initialized();
+            let is_file = |path: &Path| FILES.with(|files| files.borrow().contains_key(&canonicalize_path_separators(path)) );
+            return is_file(path) || (expand_embedded_zip_files_for(path) && is_file(path));
         }
         
         pub fn is_dir_shim(path: &Path) -> bool {
-            if FILES.with(|files| files.borrow().is_empty()) {
-                let _ignore_result = initialize_static_vars();
-            }
-            return DIRECTORIES.with(|dirs| dirs.borrow().contains(&canonicalize_path_separators(path)) );
+            assure_static_vars_initialized();
+            let is_dir = |path: &Path| DIRECTORIES.with(|dirs| dirs.borrow().contains(&canonicalize_path_separators(path)) );
+            return is_dir(path) || (expand_embedded_zip_files_for(path) && is_dir(path));
         }
 
         /// Find files in 'dir' that end with 'ending' (e.g., "_Rules.yaml")
         pub fn find_files_in_dir_that_ends_with_shim(dir: &Path, ending: &str) -> Vec<String> {
             // FIX: this is very inefficient because it looks through all the files -- maybe dirs should list the files in them?
             // look for files that have 'path' as a prefix
+            assure_static_vars_initialized();
+            let _ignore_result = expand_embedded_zip_files(|containing_dir| dir.starts_with(containing_dir) || containing_dir.starts_with(dir));
             return FILES.with(|files| {
                 let files = files.borrow();
                 let mut answer = Vec::new();
@@ -179,6 +213,9 @@ cfg_if! {
         
 
         pub fn find_all_dirs_shim(dir: &Path, found_dirs: &mut Vec<PathBuf> ) {
+            // regional dirs (e.g., "zh/tw") are inside the zip files, so the zip files in 'dir' need to be unzipped
+            assure_static_vars_initialized();
+            let _ignore_result = expand_embedded_zip_files(|containing_dir| containing_dir.starts_with(dir));
             return DIRECTORIES.with(|dirs| {
                 let dirs = dirs.borrow();
 
@@ -233,8 +270,11 @@ cfg_if! {
                             None => (ZIPPED_RULE_FILES, archive_path.main),
                             Some(i) => {
                                 // debug!("read_to_string_shim: reading embedded zip file {} at index {}", file_name, *i);
-                                let mut archive = get_zip_archive(ZIPPED_RULE_FILES)?;
-                                inner_bytes = get_bytes_from_index(&mut archive, archive_path.main)?;  // need to hold temp value
+                                // the embedded zip file was unzipped when the file was added to FILES, so it is cached
+                                inner_bytes = match EMBEDDED_ZIP_FILES.with(|zip_files| zip_files.borrow().get(&archive_path.main).cloned()) {
+                                    Some(bytes) => bytes,
+                                    None => bail!("read_to_string_shim: embedded zip file for {} was not unzipped", file_name),
+                                };
                                 (inner_bytes.as_slice(), *i)
                             }
                         }
@@ -263,8 +303,12 @@ cfg_if! {
         pub fn zip_extract_shim(dir: &Path, zip_file_name: &str) -> Result<bool> {
             let zip_file_path = dir.join(zip_file_name);
             let full_zip_file_name = canonicalize_path_separators(&zip_file_path);
+            assure_static_vars_initialized();
             match FILES.with(|files| files.borrow().contains_key(full_zip_file_name.as_str()) ) {
-                true => Ok(true),
+                true => {
+                    expand_embedded_zip_files(|containing_dir| containing_dir == dir)?;
+                    Ok(true)
+                },
                 false => bail!("zip_extract_shim: didn't find {} in zip archive", full_zip_file_name),
             }
         }
@@ -278,6 +322,7 @@ cfg_if! {
             // file_name should be path name starting at Rules dir: e.g, "Rules/en/navigate.yaml"
             OVERRIDE_FILE_NAME.with(|name| *name.borrow_mut() = file_name.to_string().replace("/", "\\"));
             OVERRIDE_FILE_CONTENTS.with(|contents| *contents.borrow_mut() = file_contents.to_string());
+            crate::speech::clear_parsed_rule_files();                  // the (zipped) file's time doesn't change, so force it to be reparsed
             crate::interface::set_rules_dir("Rules").unwrap();       // force reinitialization after the change
         }
     } else {
@@ -378,3 +423,52 @@ cfg_if! {
         }
     }
 }
+
+#[cfg(all(test, any(target_family = "wasm", feature = "include-zip")))]
+mod tests {
+    use super::*;
+
+    fn n_embedded_zip_files() -> usize {
+        return EMBEDDED_ZIP_FILES.with(|zip_files| zip_files.borrow().len());
+    }
+
+    #[test]
+    fn embedded_zip_files_are_unzipped_when_needed() -> Result<()> {
+        // each test runs in its own thread, so the (thread local) file system starts out empty
+        assert!(is_file_shim(Path::new("Rules/prefs.yaml")));
+        assert_eq!(n_embedded_zip_files(), 0, "only the top-level zip file should have been read");
+        assert!(read_to_string_shim(Path::new("Rules/prefs.yaml"))?.contains("Language"));
+
+        assert!(is_file_shim(Path::new("Rules/Languages/en/navigate.yaml")));
+        assert_eq!(n_embedded_zip_files(), 1, "only en.zip should have been unzipped");
+        assert!(is_dir_shim(Path::new("Rules/Languages/en/SharedRules")));
+        assert!(!read_to_string_shim(Path::new("Rules/Languages/en/SharedRules/general.yaml"))?.is_empty());
+        assert_eq!(n_embedded_zip_files(), 1, "reading a file in an unzipped dir shouldn't unzip anything");
+
+        assert!(is_file_shim(Path::new("Rules/Braille/Nemeth/unicode.yaml")));
+        assert_eq!(n_embedded_zip_files(), 2);
+        assert!(!is_file_shim(Path::new("Rules/Braille/Nemeth/not-a-file.yaml")));
+        assert!(read_to_string_shim(Path::new("Rules/Languages/xx/navigate.yaml")).is_err());
+        return Ok(());
+    }
+
+    #[test]
+    fn finding_dirs_unzips_the_regional_dirs() {
+        let mut dirs = vec![];
+        find_all_dirs_shim(Path::new("Rules/Languages"), &mut dirs);
+        assert!(dirs.contains(&PathBuf::from("Rules/Languages/zh/tw")));
+        assert!(!dirs.iter().any(|dir| dir.to_string_lossy().contains("SharedRules")));
+        assert!(n_embedded_zip_files() > 1);
+        assert!(!find_files_in_dir_that_ends_with_shim(Path::new("Rules/Languages/en"), "_Rules.yaml").is_empty());
+    }
+
+    #[test]
+    fn zip_extract() -> Result<()> {
+        assert!(zip_extract_shim(Path::new("Rules/Languages/es"), "es.zip")?);
+        assert_eq!(n_embedded_zip_files(), 1);
+        assert!(is_file_shim(Path::new("Rules/Languages/es/ClearSpeak_Rules.yaml")));
+        assert_eq!(n_embedded_zip_files(), 1);
+        assert!(zip_extract_shim(Path::new("Rules/Languages/es"), "xx.zip").is_err());
+        return Ok(());
+    }
+}
@@ -5,8 +5,8 @@
 #![allow(clippy::needless_return)]
 use std::path::PathBuf;
 use std::collections::HashMap;
-use std::cell::{RefCell, RefMut};
-use std::sync::LazyLock;
+use std::cell::RefCell;
+use std::sync::{Arc, LazyLock, RwLock};
 use sxd_document::dom::{ChildOfElement, Document, Element};
 use sxd_document::{Package, QName};
 use sxd_xpath::context::Evaluation;
@@ -32,6 +32,11 @@ use log::{debug, error, info};
 
 pub const NAV_NODE_SPEECH_NOT_FOUND: &str = "NAV_NODE_NOT_FOUND";
 
+/// The speech for the navigation node is surrounded by these markers so that it can be found in the speech for the whole expression.
+/// They are private use chars because the TTS markup can't contain them (e.g., the Mac commands look like "[[slnc 200]]")
+const NAV_NODE_START: &str = "\u{effc}";
+const NAV_NODE_END: &str = "\u{effd}";
+
 /// Like lisp's ' (quote foo), this is used to block "replace_chars" being called.
 ///   Unlike lisp, this appended to the end of a string (more efficient)
 /// At the moment, the only use is BrailleChars(...) -- internally, it calls replace_chars and we don't want it called again.
@@ -82,8 +87,125 @@ pub fn speak_mathml(mathml: Element, nav_node_id: &str, nav_node_offset: usize)
     return speak_rules(&SPEECH_RULES, mathml, nav_node_id, nav_node_offset);
 }
 
-pub fn overview_mathml(mathml: Element, nav_node_id: &str, nav_node_offset: usize) -> Result<String> {
-    return speak_rules(&OVERVIEW_RULES, mathml, nav_node_id, nav_node_offset);
+/// Speak an overview of `mathml` using the overview rules (`mathml` can be any node in the tree, such as the navigation node).
+///
+/// The overview rules describe `$OverviewDepth` levels of the structure of the expression.
+/// The depth starts at the `OverviewDepth` preference and is lowered until the overview is at most
+///   `OverviewLength` words long (or the depth is 0).
+pub fn overview_mathml(mathml: Element) -> Result<String> {
+    return OVERVIEW_RULES.with(|rules| {
+        rules.borrow_mut().read_files()?;
+        let rules = rules.borrow();
+        let (mut depth, max_words) = {
+            let pref_manager = rules.pref_manager.borrow();
+            (pref_manager.pref_to_string("OverviewDepth").parse::<f64>().unwrap_or(2.0).max(0.0) as usize,
+             pref_manager.pref_to_string("OverviewLength").parse::<f64>().unwrap_or(20.0).max(1.0) as usize)
+        };
+        loop {
+            let new_package = Package::new();
+            let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), "", 0);
+            rules_with_context.get_context().set_variable("OverviewDepth", depth as f64);
+            let speech = rules_with_context.match_pattern::<String>(mathml)
+                        .context("Pattern match/replacement failure!")?;
+            let speech = clean_speech(&rules, &speech);
+            if depth == 0 || count_words(&speech) <= max_words {
+                return Ok(speech);
+            }
+            depth -= 1;
+        }
+    });
+
+    /// Count the words in `speech`, ignoring any TTS markup (e.g., "<break time='300ms'/>")
+    fn count_words(speech: &str) -> usize {
+        let mut n_words = 0;
+        let mut in_markup = false;
+        for word in speech.split_whitespace() {
+            if word.starts_with('<') {
+                in_markup = true;
+            }
+            if !in_markup && word.chars().any(|ch| ch.is_alphanumeric()) {
+                n_words += 1;
+            }
+            if word.ends_with('>') {
+                in_markup = false;
+            }
+        }
+        return n_words;
+    }
+}
+
+/// Speak the part of `mathml` that covers the nodes `first` and `last` (given as (id, offset) pairs) along with everything between them.
+/// If `first` is `None`, the speech starts at the beginning of `mathml`; if `last` is `None`, the speech goes to the end of `mathml`.
+/// The nodes can be in either document order. This is used for the navigation "ReadTo" commands.
+pub fn speak_mathml_range(mathml: Element, first: Option<(&str, usize)>, last: Option<(&str, usize)>) -> Result<String> {
+    return SPEECH_RULES.with(|rules| {
+        rules.borrow_mut().read_files()?;
+        let rules = rules.borrow();
+        let mut speech = None;      // speech without the nav node markers
+        let mut start = usize::MAX;
+        let mut end = 0;
+        for (id, offset) in [first, last].into_iter().flatten() {
+            // the nav node markers are the only difference between the strings, so the positions can be compared
+            // Note: the concat indicators can differ around the markers, so they are removed first
+            let marked = remove_concat_indicators(&marked_speech(&rules, mathml, id, offset)?);
+            let (Some(node_start), Some(node_end)) = (marked.find(NAV_NODE_START), marked.find(NAV_NODE_END)) else {
+                bail!(NAV_NODE_SPEECH_NOT_FOUND);
+            };
+            start = start.min(node_start);
+            end = end.max(node_end - NAV_NODE_START.len());
+            let unmarked = marked.replacen(NAV_NODE_START, "", 1).replacen(NAV_NODE_END, "", 1);
+            if speech.as_ref().is_some_and(|speech| speech != &unmarked) {
+                bail!(NAV_NODE_SPEECH_NOT_FOUND);    // one of the nodes was only found with LiteralSpeak
+            }
+            speech = Some(unmarked);
+        }
+        let speech = match speech {
+            Some(speech) => speech,
+            None => remove_concat_indicators(&marked_speech(&rules, mathml, "", 0)?),
+        };
+        if first.is_none() {
+            start = 0;
+        }
+        if last.is_none() {
+            end = speech.len();
+        }
+        return Ok( clean_speech(&rules, &speech[start..end]) );
+    });
+
+    /// Returns the speech for `mathml` with the node `id` surrounded by the nav node markers (if `id` is non-empty).
+    /// Some parts might have been elided in some SpeechStyles, so if the node isn't found, LiteralSpeak is tried.
+    fn marked_speech(rules: &SpeechRules, mathml: Element, id: &str, offset: usize) -> Result<String> {
+        let new_package = Package::new();
+        let mut rules_with_context = SpeechRulesWithContext::new(rules, new_package.as_document(), id, offset);
+        let speech = rules_with_context.match_pattern::<String>(mathml)
+                    .context("Pattern match/replacement failure!")?;
+        let intent_attr = mathml.attribute_value("data-intent-property").unwrap_or_default();
+        if id.is_empty() || speech.contains(NAV_NODE_START) || intent_attr.contains(":literal:") {
+            return Ok(speech);
+        }
+        mathml.set_attribute_value("data-intent-property", (":literal:".to_string() + intent_attr).as_str());
+        let speech = marked_speech(rules, mathml, id, offset);
+        if intent_attr.is_empty() {
+            mathml.remove_attribute("data-intent-property");
+        } else {
+            mathml.set_attribute_value("data-intent-property", intent_attr);
+        }
+        return speech;
+    }
+}
+
+/// Remove the internal markers from the speech and clean up the pauses and ends of the string
+fn clean_speech(rules: &SpeechRules, speech: &str) -> String {
+    return rules.pref_manager.borrow().get_tts()
+        .merge_pauses(remove_optional_indicators(&remove_concat_indicators(speech))
+        .trim_start().trim_end_matches([' ', ',', ';']));
+}
+
+fn remove_concat_indicators(speech: &str) -> String {
+    return speech.replace(CONCAT_STRING, "")
+                 .replace(CONCAT_INDICATOR, "")
+                 .replace(POSTFIX_CONCAT_STRING, "")
+                 .replace(POSTFIX_CONCAT_INDICATOR, "");
 }
 
 
@@ -123,7 +245,7 @@ fn intent_rules<'m>(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>,
 }
 
 /// Speak the MathML
-/// If 'nav_node_id' is not an empty string, then the element with that id will have [[...]] around it
+/// If 'nav_node_id' is not an empty string, then the element with that id will have the nav node markers around it
 fn speak_rules(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>, mathml: Element, nav_node_id: &str, nav_node_offset: usize) -> Result<String> {
     return rules.with(|rules| {
         rules.borrow_mut().read_files()?;
@@ -132,29 +254,22 @@ fn speak_rules(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>, math
         let new_package = Package::new();
         let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), nav_node_id, nav_node_offset);
         let speech_string = nestable_speak_rules(& mut rules_with_context, mathml)?;
-        
-        return Ok( rules.pref_manager.borrow().get_tts()
-            .merge_pauses(remove_optional_indicators(
-                &speech_string.replace(CONCAT_STRING, "")
-                                   .replace(CONCAT_INDICATOR, "") 
-                                   .replace(POSTFIX_CONCAT_STRING, "")
-                                   .replace(POSTFIX_CONCAT_INDICATOR, "")                           
-                            )
-            .trim_start().trim_end_matches([' ', ',', ';'])) );
+        return Ok( clean_speech(&rules, &speech_string) );
     });
 
     fn nestable_speak_rules<'c, 's:'c, 'm:'c>(rules_with_context: &mut SpeechRulesWithContext<'c, 's, 'm>, mathml: Element<'c>) -> Result<String> {
         let mut speech_string = rules_with_context.match_pattern::<String>(mathml)
                     .context("Pattern match/replacement failure!")?;
         // debug!("Speech string: {}", speech_string);
-        // Note: [[...]] is added around a matching child, but if the "id" is on 'mathml', the whole string is used
+        // Note: the nav node markers are added around a matching child, but if the "id" is on 'mathml', the whole string is used
         if !rules_with_context.nav_node_id.is_empty() {
             // See https://github.com/NSoiffer/MathCAT/issues/174 for why we can just start the speech at the nav node
             let intent_attr = mathml.attribute_value("data-intent-property").unwrap_or_default();
-            if let Some(start) = speech_string.find("[[") {
-                match speech_string[start+2..].find("]]") {
-                    None => bail!("Internal error: looking for '[[...]]' during navigation -- only found '[[' in '{}'", speech_string),
-                    Some(end) => speech_string = speech_string[start+2..start+2+end].to_string(),
+            if let Some(start) = speech_string.find(NAV_NODE_START) {
+                let start = start + NAV_NODE_START.len();
+                match speech_string[start..].find(NAV_NODE_END) {
+                    None => bail!("Internal error: looking for the nav node markers during navigation -- only found the start in '{}'", speech_string),
+                    Some(end) => speech_string = speech_string[start..start+end].to_string(),
                 }
             } else if !intent_attr.contains(":literal:") {
                 // try again with LiteralSpeak -- some parts might have been elided in other SpeechStyles
@@ -266,6 +381,10 @@ pub fn remove_optional_indicators(str: &str) -> String {
 /// The returned value should be a Vector containing the paths of all the files that were included.
 pub fn compile_rule<F>(str: &str, mut build_fn: F) -> Result<Vec<PathBuf>> where
             F: FnMut(&Yaml) -> Result<Vec<PathBuf>> {
+    return build_fn(&parse_rule_yaml(str)?);
+}
+
+fn parse_rule_yaml(str: &str) -> Result<Yaml> {
     let docs = YamlLoader::load_from_str(str);
     match docs {
         Err(e) => {
@@ -275,11 +394,55 @@ pub fn compile_rule<F>(str: &str, mut build_fn: F) -> Result<Vec<PathBuf>> where
             if docs.len() != 1 {
                 bail!("Didn't find rules!");
             }
-            return build_fn(&docs[0]);
+            return Ok(docs.into_iter().next().unwrap());
         }
     }
 }
 
+/// The parsed rule files are shared by all threads so that a thread pool only reads and parses each rule file once.
+/// The value is the modification time of the file when it was read along with the parsed YAML.
+type ParsedRuleFiles = HashMap<PathBuf, (SystemTime, Arc<Yaml>)>;
+static PARSED_RULE_FILES: LazyLock<RwLock<ParsedRuleFiles>> =
+        LazyLock::new(|| RwLock::new(HashMap::with_capacity(63)));
+
+/// Like [`compile_rule`], but the YAML comes from the file `path`.
+/// If the file has already been read (by any thread) and hasn't changed since, the already parsed YAML is used.
+pub fn compile_rule_file<F>(path: &Path, mut build_fn: F) -> Result<Vec<PathBuf>> where
+            F: FnMut(&Yaml) -> Result<Vec<PathBuf>> {
+    let file_time = FileAndTime::get_metadata(path);
+    let cached_yaml = PARSED_RULE_FILES.read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(path)
+            .filter(|(time, _)| *time == file_time)
+            .map(|(_, yaml)| yaml.clone());
+    let yaml = match cached_yaml {
+        Some(yaml) => yaml,
+        None => {
+            let file_contents = read_to_string_shim(path).with_context(|| format!("cannot read file '{}'", path.to_str().unwrap()))?;
+            let yaml = Arc::new(parse_rule_yaml(&file_contents)?);
+            PARSED_RULE_FILES.write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .insert(path.to_path_buf(), (file_time, yaml.clone()));
+            yaml
+        }
+    };
+    return build_fn(&yaml);
+}
+
+/// Forget all the parsed rule files and the tables built from them so that they are read again the next time they are needed.
+pub fn clear_parsed_rule_files() {
+    PARSED_RULE_FILES.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+    SHARED_RULE_TABLES.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+    SHARED_UNICODE_TABLES.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+    crate::definitions::clear_shared_definitions();
+}
+
 pub fn process_include<F>(current_file: &Path, new_file_name: &str, mut read_new_file: F) -> Result<Vec<PathBuf>>
                     where F: FnMut(&Path) -> Result<Vec<PathBuf>> {
     let parent_path = current_file.parent();
@@ -374,7 +537,9 @@ impl<'c, 'm:'c> TreeOrString<'c, 'm, Element<'m>> for Element<'m> {
 
     fn from_string(s: String, doc: Document<'m>) -> Result<Element<'m>> {
         // FIX: is 'mi' really ok?  Don't want to use TEMP_NAME because this name needs to move to the outside world
-        let leaf = create_mathml_element(&doc, "mi");
+        // numbers (e.g., the implied order '1' of a derivative) are 'mn's
+        let is_number = !s.is_empty() && s.chars().all(|ch| ch.is_ascii_digit());
+        let leaf = create_mathml_element(&doc, if is_number {"mn"} else {"mi"});
         leaf.set_text(&s);
         return Ok(leaf);
 }
@@ -964,8 +1129,8 @@ impl ReplacementArray {
             }
 
             // should be exactly one match -- ignore more than one for now
-            let i_start = next.find(OPTIONAL_INDICATOR)?;
-            let start_repeat_word_in_next = &next[i_start + OPTIONAL_INDICATOR_LEN..];
+            // the optional text must start 'next' -- an optional text in the middle is not repetitive of 'prev'
+            let start_repeat_word_in_next = next.trim_start().strip_prefix(OPTIONAL_INDICATOR)?;
             let i_end = start_repeat_word_in_next.find(OPTIONAL_INDICATOR)
                 .unwrap_or_else(|| panic!("Internal error: missing end optional char -- text handling is corrupted!"));
             let repeat_word = &start_repeat_word_in_next[..i_end];
@@ -1031,15 +1196,17 @@ impl ReplacementArray {
 // MyXPath is a wrapper around an 'XPath' that keeps around the original xpath expr (as a string) so it can be used in error reporting.
 // Because we want to be able to clone them and XPath doesn't support clone(), this is a wrapper around an internal MyXPath.
 // It supports the standard SpeechRule functionality of building and replacing.
+// Compiled XPaths can't be shared across threads, so the compiled rules only store the string and an id.
+// Each thread compiles an XPath the first time it evaluates it (see `COMPILED_XPATHS`).
 #[derive(Debug)]
 struct RCMyXPath {
-    xpath: XPath,
-    string: String,        // store for error reporting
+    id: usize,             // index into COMPILED_XPATHS
+    string: String,        // the xpath (also used for error reporting)
 }
 
 #[derive(Debug, Clone)]
 pub struct MyXPath {
-    rc: Rc<RCMyXPath>        // rather than putting Rc around both 'xpath' and 'string', just use one and indirect to internal RCMyXPath
+    rc: Arc<RCMyXPath>        // rather than putting Arc around both 'id' and 'string', just use one and indirect to internal RCMyXPath
 }
 
 
@@ -1049,35 +1216,51 @@ impl fmt::Display for MyXPath {
     }
 }
 
-// pub fn xpath_count() -> (usize, usize) {
-//     return (XPATH_CACHE.with( |cache| cache.borrow().len()), unsafe{XPATH_CACHE_HITS} );
-// }
+/// All the xpaths used by any thread -- the same string always gets the same id
+static XPATH_CACHE: LazyLock<RwLock<HashMap<String, MyXPath>>> = LazyLock::new(|| RwLock::new( HashMap::with_capacity(4095) ));
+
 thread_local!{
-    static XPATH_CACHE: RefCell<HashMap<String, MyXPath>> = RefCell::new( HashMap::with_capacity(2047) );
+    /// The XPaths compiled by this thread, indexed by the id of the MyXPath
+    static COMPILED_XPATHS: RefCell<Vec<Option<Rc<XPath>>>> = RefCell::new( Vec::with_capacity(4095) );
 }
-// static mut XPATH_CACHE_HITS: usize = 0;
 
 impl MyXPath {
     fn new(xpath: String) -> Result<MyXPath> {
-        return XPATH_CACHE.with( |cache|  {
-            let mut cache = cache.borrow_mut();
-            return Ok(
-                match cache.get(&xpath) {
-                    Some(compiled_xpath) => {
-                        // unsafe{ XPATH_CACHE_HITS += 1;};
-                        compiled_xpath.clone()
-                    },
-                    None => {
-                        let new_xpath = MyXPath {
-                            rc: Rc::new( RCMyXPath {
-                                xpath: MyXPath::compile_xpath(&xpath)?,
-                                string: xpath.clone()
-                            })};
-                        cache.insert(xpath.clone(), new_xpath.clone());
-                        new_xpath
-                    },
-                }
-            )
+        if let Some(my_xpath) = XPATH_CACHE.read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(&xpath) {
+            return Ok( my_xpath.clone() );
+        }
+
+        // compile it now so that errors are reported when the rules are built
+        let compiled_xpath = Rc::new( MyXPath::compile_xpath(&xpath)? );
+        let mut cache = XPATH_CACHE.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let id = cache.len();
+        let my_xpath = cache.entry(xpath)
+                .or_insert_with_key(|xpath| MyXPath{ rc: Arc::new( RCMyXPath{ id, string: xpath.clone() }) })
+                .clone();
+        drop(cache);
+        if my_xpath.rc.id == id {      // not added by another thread in the meantime
+            MyXPath::set_compiled_xpath(id, compiled_xpath);
+        }
+        return Ok(my_xpath);
+    }
+
+    /// Returns the compiled XPath for this thread (compiling it if this thread hasn't used it before)
+    fn compiled_xpath(&self) -> Result<Rc<XPath>> {
+        let id = self.rc.id;
+        if let Some(Some(xpath)) = COMPILED_XPATHS.with_borrow(|compiled| compiled.get(id).cloned()) {
+            return Ok(xpath);
+        }
+        let xpath = Rc::new( MyXPath::compile_xpath(&self.rc.string)? );
+        MyXPath::set_compiled_xpath(id, xpath.clone());
+        return Ok(xpath);
+    }
+
+    fn set_compiled_xpath(id: usize, xpath: Rc<XPath>) {
+        COMPILED_XPATHS.with_borrow_mut(|compiled| {
+            if compiled.len() <= id {
+                compiled.resize(id + 1, None);
+            }
+            compiled[id] = Some(xpath);
         });
     }
 
@@ -1263,7 +1446,7 @@ impl MyXPath {
     
     pub fn evaluate<'c>(&self, context: &sxd_xpath::Context<'c>, mathml: Element<'c>) -> Result<Value<'c>> {
         // debug!("evaluate: {}", self);
-        let result = self.rc.xpath.evaluate(context, mathml);
+        let result = self.compiled_xpath()?.evaluate(context, mathml);
         return match result {
             Ok(val) => Ok( val ),
             Err(e) => {
@@ -1284,7 +1467,7 @@ impl MyXPath {
 // Some info is not needed beyond converting the Yaml to the SpeechPattern, but is useful for error reporting.
 // The two main parts are the pattern to be matched and the replacements to do if there is a match.
 // Any variables/prefs that are defined/set are also stored.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct SpeechPattern {
     pattern_name: String,
     tag_name: String,
@@ -1390,7 +1573,7 @@ impl SpeechPattern  {
                     })?
                 } );
             // get the array of rules for the tag name
-            let rule_value = rules.rules.entry(tag_name).or_default();
+            let rule_value = Arc::make_mut(&mut rules.rules).entry(tag_name).or_default();
 
             // if the name exists, replace it. Otherwise add the new rule
             match rule_value.iter().enumerate().find(|&pattern| pattern.1.pattern_name == speech_pattern.pattern_name) {
@@ -1750,6 +1933,8 @@ impl<'c, 'r> ContextStack<'c> {
         // These can't be set on the <math> tag because of the "translate" command which starts speech at an 'id'
         context_stack.base.set_variable("MatchingPause", Value::Boolean(false));
         context_stack.base.set_variable("IsColumnSilent", Value::Boolean(false));
+        context_stack.base.set_variable("ElementaryMathMode", Value::String("".to_string()));
+        context_stack.base.set_variable("MathSpeakScriptLevel", Value::String("".to_string()));
 
 
         return context_stack;
@@ -1875,6 +2060,7 @@ impl UnicodeDef {
         } else {
             speech_rules.unicode_full.borrow_mut()
         };
+        let unicode_table = Arc::make_mut(&mut unicode_table);
         if let Some(str) = ch.as_str() {
             if str.is_empty() {
                 bail!("Empty character definition. Replacement is {}", replacements.as_str().unwrap());
@@ -1905,7 +2091,7 @@ impl UnicodeDef {
         }
         return Ok(None);
 
-        fn process_range(def_range: &str, replacements: &Yaml, mut unicode_table: RefMut<HashMap<u32,Vec<Replacement>>>) -> Result<Option<Vec<PathBuf>>> {
+        fn process_range(def_range: &str, replacements: &Yaml, unicode_table: &mut UnicodeMap) -> Result<Option<Vec<PathBuf>>> {
             // should be a character range (e.g., "A-Z")
             // iterate over that range and also substitute the char for '.' in the 
             let mut range = def_range.split('-');
@@ -1964,17 +2150,35 @@ impl UnicodeDef {
     }    
 }
 
-// Fix: there should be a cache so subsequent library calls don't have to read in the same speech rules
-//   likely a cache of size 1 is fine
-// Fix: all statics should be gathered together into one structure that is a Mutex
-//   for each library call, we should grab a lock on the Mutex in case others try to call
-//   at the same time.
-//   If this turns out to be something that others actually do, then a cache > 1 would be good
-
  type RuleTable = HashMap<String, Vec<Box<SpeechPattern>>>;
- type UnicodeTable = Rc<RefCell<HashMap<u32,Vec<Replacement>>>>;
+ type UnicodeMap = HashMap<u32,Vec<Replacement>>;
+ type UnicodeTable = Rc<RefCell<Arc<UnicodeMap>>>;
  type FilesAndTimesShared = Rc<RefCell<FilesAndTimes>>;
 
+/// The compiled rule tables (speech rules, unicode tables, and definitions) don't change once they are built,
+///   so they are shared by all threads. The key is the main file the table was built from.
+/// The files and times are used to check whether the table is still up to date.
+pub type SharedTables<T> = LazyLock<RwLock<HashMap<PathBuf, (FilesAndTimes, Arc<T>)>>>;
+static SHARED_RULE_TABLES: SharedTables<RuleTable> = LazyLock::new(|| RwLock::new( HashMap::with_capacity(31) ));
+static SHARED_UNICODE_TABLES: SharedTables<UnicodeMap> = LazyLock::new(|| RwLock::new( HashMap::with_capacity(31) ));
+
+/// Returns the table built from the file `path` along with the files that were read to build it.
+/// If no thread has built an up to date table for the file, `build_fn` is called to build it and the table is shared.
+pub fn get_shared_table<T>(tables: &SharedTables<T>, path: &Path, should_ignore_file_time: bool,
+                           build_fn: impl FnOnce() -> Result<(Arc<T>, Vec<PathBuf>)>) -> Result<(Arc<T>, FilesAndTimes)> {
+    if let Some((files, table)) = tables.read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(path) &&
+       files.is_file_up_to_date(path, should_ignore_file_time) {
+        return Ok( (table.clone(), files.clone()) );
+    }
+    let (table, files_read) = build_fn()?;
+    let mut files = FilesAndTimes::default();
+    files.set_files_and_times(files_read);
+    tables.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(path.to_path_buf(), (files.clone(), table.clone()));
+    return Ok( (table, files) );
+}
+
  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
  pub enum RulesFor {
      Intent,
@@ -2042,7 +2246,7 @@ impl FileAndTime {
     }
 
 }
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct FilesAndTimes {
     // ft[0] is the main file -- other files are included by it (or recursively)
     // We could be a little smarter about invalidation by tracking what file is the parent (including file),
@@ -2119,7 +2323,7 @@ pub struct SpeechRules {
     error: String,
     name: RulesFor,
     pub pref_manager: Rc<RefCell<PreferenceManager>>,
-    rules: RuleTable,                              // the speech rules used (partitioned into MathML tags in hashmap, then linearly searched)
+    rules: Arc<RuleTable>,                         // the speech rules used (partitioned into MathML tags in hashmap, then linearly searched)
     rule_files: FilesAndTimes,                     // files that were read
     translate_single_chars_only: bool,             // strings like "half" don't want 'a's translated, but braille does
     unicode_short: UnicodeTable,                   // the short list of rules used for Unicode characters
@@ -2165,19 +2369,19 @@ impl<'c, 's:'c, 'm:'c> fmt::Display for SpeechRulesWithContext<'c, 's,'m> {
 thread_local!{
     /// SPEECH_UNICODE_SHORT is shared among several rules, so "RC" is used
     static SPEECH_UNICODE_SHORT: UnicodeTable =
-        Rc::new( RefCell::new( HashMap::with_capacity(500) ) );
+        Rc::new( RefCell::new( Arc::new( HashMap::with_capacity(500) ) ) );
         
     /// SPEECH_UNICODE_FULL is shared among several rules, so "RC" is used
     static SPEECH_UNICODE_FULL: UnicodeTable =
-        Rc::new( RefCell::new( HashMap::with_capacity(6500) ) );
+        Rc::new( RefCell::new( Arc::new( HashMap::with_capacity(6500) ) ) );
         
     /// BRAILLE_UNICODE_SHORT is shared among several rules, so "RC" is used
     static BRAILLE_UNICODE_SHORT: UnicodeTable =
-        Rc::new( RefCell::new( HashMap::with_capacity(500) ) );
+        Rc::new( RefCell::new( Arc::new( HashMap::with_capacity(500) ) ) );
         
     /// BRAILLE_UNICODE_FULL is shared among several rules, so "RC" is used
     static BRAILLE_UNICODE_FULL: UnicodeTable =
-        Rc::new( RefCell::new( HashMap::with_capacity(5000) ) );
+        Rc::new( RefCell::new( Arc::new( HashMap::with_capacity(5000) ) ) );
 
     /// SPEECH_DEFINITION_FILES_AND_TIMES is shared among several rules, so "RC" is used
     static SPEECH_DEFINITION_FILES_AND_TIMES: FilesAndTimesShared =
@@ -2280,7 +2484,7 @@ impl SpeechRules {
         return SpeechRules {
             error: Default::default(),
             name,
-            rules: HashMap::with_capacity(if name == RulesFor::Intent || name == RulesFor::Speech {500} else {50}),                       // lazy load them
+            rules: Arc::new( HashMap::new() ),                       // lazy load them
             rule_files: FilesAndTimes::default(),
             unicode_short: globals.0.0,       // lazy load them
             unicode_short_files: globals.0.1,
@@ -2308,17 +2512,26 @@ impl SpeechRules {
         let should_ignore_file_time = self.pref_manager.borrow().pref_to_string("CheckRuleFiles") != "All";     // ignore for "None", "Prefs"
         let rule_file = self.pref_manager.borrow().get_rule_file(&self.name).to_path_buf();     // need to create PathBuf to avoid a move/use problem
         if self.rules.is_empty() || !self.rule_files.is_file_up_to_date(&rule_file, should_ignore_file_time) {
-            self.rules.clear();
-            let files_read = self.read_patterns(&rule_file)?;
-            self.rule_files.set_files_and_times(files_read);
+            let (rules, files) = get_shared_table(&SHARED_RULE_TABLES, &rule_file, should_ignore_file_time, || {
+                self.rules = Arc::new( HashMap::with_capacity(if self.name == RulesFor::Intent || self.name == RulesFor::Speech {500} else {50}) );
+                let files_read = self.read_patterns(&rule_file)?;
+                return Ok( (self.rules.clone(), files_read) );
+            })?;
+            self.rules = rules;
+            self.rule_files = files;
         }
 
         let pref_manager = self.pref_manager.borrow();
         let unicode_pref_files = if self.name == RulesFor::Braille {pref_manager.get_braille_unicode_file()} else {pref_manager.get_speech_unicode_file()};
 
         if !self.unicode_short_files.borrow().is_file_up_to_date(unicode_pref_files.0, should_ignore_file_time) {
-            self.unicode_short.borrow_mut().clear();
-            self.unicode_short_files.borrow_mut().set_files_and_times(self.read_unicode(None, true)?);
+            let (unicode_short, files) = get_shared_table(&SHARED_UNICODE_TABLES, unicode_pref_files.0, should_ignore_file_time, || {
+                *self.unicode_short.borrow_mut() = Arc::new( HashMap::with_capacity(500) );
+                let files_read = self.read_unicode(None, true)?;
+                return Ok( (self.unicode_short.borrow().clone(), files_read) );
+            })?;
+            *self.unicode_short.borrow_mut() = unicode_short;
+            *self.unicode_short_files.borrow_mut() = files;
         }
 
         if self.definitions_files.borrow().ft.is_empty() || !self.definitions_files.borrow().is_file_up_to_date(
@@ -2332,12 +2545,11 @@ impl SpeechRules {
 
     fn read_patterns(&mut self, path: &Path) -> Result<Vec<PathBuf>> {
         // info!("Reading rule file: {}", p.to_str().unwrap());
-        let rule_file_contents = read_to_string_shim(path).with_context(|| format!("cannot read file '{}'", path.to_str().unwrap()))?;
         let rules_build_fn = |pattern: &Yaml| {
             self.build_speech_patterns(pattern, path)
                 .with_context(||format!("in file {:?}", path.to_str().unwrap()))
         };
-        return compile_rule(&rule_file_contents, rules_build_fn)
+        return compile_rule_file(path, rules_build_fn)
                 .with_context(||format!("in file {:?}", path.to_str().unwrap()));
     }
 
@@ -2375,7 +2587,6 @@ impl SpeechRules {
 
         // FIX: should read first (lang), then supplement with second (region)
         // info!("Reading unicode file {}", path.to_str().unwrap());
-        let unicode_file_contents = read_to_string_shim(&path)?;
         let unicode_build_fn = |unicode_def_list: &Yaml| {
             let unicode_defs = unicode_def_list.as_vec();
             if unicode_defs.is_none() {
@@ -2391,7 +2602,7 @@ impl SpeechRules {
             return Ok(files_read)
         };
 
-        return compile_rule(&unicode_file_contents, unicode_build_fn)
+        return compile_rule_file(&path, unicode_build_fn)
                     .with_context(||format!("in file {:?}", path.to_str().unwrap()));
     }
 
@@ -2656,11 +2867,11 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
     }
 
     fn mark_nav_speech(speech: String) -> String {
-        // add unique markers (since speech is mostly ascii letters and digits, most any symbol will do)
+        // add unique markers (they can't be confused with anything in the speech or the TTS markup)
         // it's a bug (but happened during intent generation), we might have identical id's, choose innermost one
-        // debug!("mark_nav_speech: adding [[ {} ]] ", &speech);
-        if !speech.contains("[[") {
-            return "[[".to_string() + &speech + "]]";
+        // debug!("mark_nav_speech: adding markers around '{}'", &speech);
+        if !speech.contains(NAV_NODE_START) {
+            return NAV_NODE_START.to_string() + &speech + NAV_NODE_END;
         } else {
             return speech
         }
@@ -2740,19 +2951,24 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
     fn replace_nodes_string(&'r mut self, nodes: Vec<Node<'c>>, mathml: Element<'c>) -> Result<String> {
         // debug!("replace_nodes: working on {} nodes", nodes.len());
         let mut result = String::with_capacity(3*nodes.len());   // guess (2 chars/node + space)
+        let skip_silent_nodes = is_in_elementary_math(mathml);
         let mut first_time = true;
         for node in nodes {
-            if first_time {
-                first_time = false;
-            } else {
-                result.push(' ');
-            };
             let matched = match node {
                 Node::Element(n) => self.match_pattern::<String>(n)?,
                 Node::Text(t) => self.replace_chars(t.text(), mathml)?,
                 Node::Attribute(attr) => self.replace_chars(attr.value(), mathml)?,
                 _ => bail!("replace_nodes: found unexpected node type!!!"),
             };
+            // silent nodes (the empty cells in an mstack/mlongdiv) shouldn't add extra spaces
+            if matched.is_empty() && skip_silent_nodes {
+                continue;
+            }
+            if first_time {
+                first_time = false;
+            } else {
+                result.push(' ');
+            };
             result += &matched;
         }
         return Ok( result );
@@ -2805,8 +3021,14 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
     fn replace_single_char(&'r mut self, ch: char, mathml: Element<'c>) -> Result<String> {
         let ch_as_u32 = ch as u32;
         let rules =  self.speech_rules;
-        let mut unicode = rules.unicode_short.borrow();
-        let mut replacements = unicode.get( &ch_as_u32 );
+        if rules.name != RulesFor::Braille && rules.name != RulesFor::Intent &&
+           let Some(word) = rules.pref_manager.borrow().get_speech_override(ch) {
+            self.translate_count = 0;
+            return Ok(self.escape_string_for_safety(word));
+        }
+        let unicode_short = rules.unicode_short.borrow().clone();
+        let unicode_full;
+        let mut replacements = unicode_short.get( &ch_as_u32 );
         // debug!("replace_single_char: looking for unicode {} for char '{}'/{:#06x}, found: {:?}", rules.name, ch, ch_as_u32, replacements);
         if replacements.is_none() {
             // see if it in the full unicode table (if it isn't loaded already)
@@ -2815,12 +3037,17 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
             let should_ignore_file_time = pref_manager.pref_to_string("CheckRuleFiles") == "All";
             if rules.unicode_full.borrow().is_empty() || !rules.unicode_full_files.borrow().is_file_up_to_date(unicode_pref_files.1, should_ignore_file_time) {
                 info!("*** Loading full unicode {} for char '{}'/{:#06x}", rules.name, ch, ch_as_u32);
-                rules.unicode_full.borrow_mut().clear();
-                rules.unicode_full_files.borrow_mut().set_files_and_times(rules.read_unicode(None, false)?);
+                let (table, files) = get_shared_table(&SHARED_UNICODE_TABLES, unicode_pref_files.1, should_ignore_file_time, || {
+                    *rules.unicode_full.borrow_mut() = Arc::new( HashMap::with_capacity(if rules.name == RulesFor::Braille {5000} else {6500}) );
+                    let files_read = rules.read_unicode(None, false)?;
+                    return Ok( (rules.unicode_full.borrow().clone(), files_read) );
+                })?;
+                *rules.unicode_full.borrow_mut() = table;
+                *rules.unicode_full_files.borrow_mut() = files;
                 info!("# Unicode defs = {}/{}", rules.unicode_short.borrow().len(), rules.unicode_full.borrow().len());
             }
-            unicode = rules.unicode_full.borrow();
-            replacements = unicode.get( &ch_as_u32 );
+            unicode_full = rules.unicode_full.borrow().clone();
+            replacements = unicode_full.get( &ch_as_u32 );
             if replacements.is_none() {
               self.translate_count = 0;     // not in loop
               // debug!("*** Did not find unicode {} for char '{}'/{:#06x}", rules.name, ch, ch_as_u32);
@@ -2848,6 +3075,21 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
     }
 }
 
+/// Returns true if `mathml` is an `mstack` or `mlongdiv` or is inside of one.
+/// The empty cells in those layouts are silent, so they shouldn't add spaces to the speech.
+fn is_in_elementary_math(mathml: Element) -> bool {
+    let mut element = mathml;
+    loop {
+        if matches!(name(element), "mstack" | "mlongdiv") {
+            return true;
+        }
+        match element.parent().and_then(|parent| parent.element()) {
+            Some(parent) => element = parent,
+            None => return false,
+        }
+    }
+}
+
 /// Hack to allow replacement of `str` with braille chars.
 pub fn braille_replace_chars(str: &str, mathml: Element) -> Result<String> {
     return BRAILLE_RULES.with(|rules| {
@@ -2982,6 +3224,45 @@ mod tests {
         assert_eq!(result.unwrap(), r#"DEBUG(ClearSpeak_Matrix = 'Combinatorics', "ClearSpeak_Matrix = 'Combinatorics'") and IsBracketed(., '(', ')')"#);
     }
 
+    #[test]
+    fn rule_tables_are_shared_by_threads() {
+        // returns the addresses of the speech rule table, the unicode table, and the definitions used by the thread
+        fn tables_used_by_thread() -> (usize, usize, usize) {
+            use crate::interface::*;
+            set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+            set_preference("Language", "en").unwrap();
+            set_preference("SpeechStyle", "SimpleSpeak").unwrap();
+            set_mathml("<math><mfrac><mi>x</mi><mn>2</mn></mfrac></math>").unwrap();
+            assert_eq!(get_spoken_text().unwrap(), "x over 2");
+            let (rules, unicode) = SPEECH_RULES.with_borrow(|rules|
+                (Arc::as_ptr(&rules.rules) as usize, Arc::as_ptr(&rules.unicode_short.borrow()) as usize)
+            );
+            let definitions = crate::definitions::SPEECH_DEFINITIONS.with_borrow(|defs| Arc::as_ptr(&defs.name_to_var_mapping) as usize);
+            return (rules, unicode, definitions);
+        }
+
+        let tables = tables_used_by_thread();
+        let other_thread_tables = std::thread::spawn(tables_used_by_thread).join().unwrap();
+        assert_eq!(tables, other_thread_tables);
+    }
+
+    #[test]
+    fn silent_nodes_only_skipped_in_elementary_math() {
+        let package = sxd_document::parser::parse(
+            "<math><mrow><mi>a</mi><mstack><msrow><none/><mn>1</mn></msrow></mstack><mi>b</mi></mrow></math>"
+        ).expect("failed to parse XML");
+        let math = crate::interface::get_element(&package);
+        let mrow = as_element(math.children()[0]);
+        let mstack = as_element(mrow.children()[1]);
+        let msrow = as_element(mstack.children()[0]);
+        assert!(!is_in_elementary_math(math));
+        assert!(!is_in_elementary_math(mrow));
+        assert!(!is_in_elementary_math(as_element(mrow.children()[0])));
+        assert!(is_in_elementary_math(mstack));
+        assert!(is_in_elementary_math(msrow));
+        assert!(is_in_elementary_math(as_element(msrow.children()[0])));
+    }
+
 
 // zipped files do NOT include "zz", hence we need to exclude this test
 cfg_if::cfg_if! {if #[cfg(not(feature = "include-zip"))] {  
@@ -3012,7 +3293,7 @@ cfg_if::cfg_if! {if #[cfg(not(feature = "include-zip"))] {
                 let start_main_file = rules.borrow().unicode_short_files.borrow().ft[0].clone();
 
                 // open the file, read all the contents, then write them back so the time changes
-                let contents = std::fs::read(&start_main_file.file).expect(&format!("Failed to read file {} during test", &start_main_file.file.to_string_lossy()));
+                let contents = std::fs::read(&start_main_file.file).unwrap_or_else(|_| panic!("Failed to read file {} during test", &start_main_file.file.to_string_lossy()));
                 std::fs::write(start_main_file.file, contents).unwrap();
                 std::thread::sleep(Duration::from_millis(5));       // pause a little to make sure the time changes
 
@@ -23,6 +23,8 @@
 //! Note: other legal values for SSML are not supported, and all numbers are interpreted as relative changes
 //! ### Eloquence: Absolute pitch (relative pitch not supported by Eloquence)
 //! * Range is 0 - 100.  Guess is that 0 ~= 42hz, 100 ~= 422hz based on supported \"sapi\" values
+//! ### Mac: Relative pitch
+//! * `[[pbas +n]]`/`[[pbas -n]]` changes the baseline pitch by 'n' semitones (an octave is 12)
 //! ## Rate (default 180 words/min)
 //! ### SAPI4: Absolute rate
 //! * Number is relative to the default/current rate
@@ -46,6 +48,8 @@
 //!
 //! Note: this means words/min = 4.18 * Eloquence rate + 66
 //! So the relative pause rate is 180/computed value
+//! ### Mac: Relative rate
+//! * `[[rate +n]]`/`[[rate -n]]` changes the rate by 'n' words/min
 //!
 //!
 //! ## Volume (default 100 \[full])
@@ -60,11 +64,28 @@
 //! Note:  other legal values for SSML are not supported, and all numbers are interpreted as relative changes
 //! ### Eloquence: Absolute volume (relative volume not supported by Eloquence)
 //! * Range is 0 - 100
+//! ### Mac: Relative volume
+//! * `[[volm +n]]`/`[[volm -n]]` changes the volume by 'n', where full volume is 1.0
+//!
+//! Note: because Eloquence only supports absolute values, the end of a pitch/rate/volume change resets
+//!   the value to the default (140hz, `Rate`, `Volume`) rather than to any enclosing change.
 //!
 //! ## Pause
-//! * All systems -- pauses are given in milliseconds
+//! * All systems -- pauses are given in milliseconds (Eloquence: `` `pn``, Mac: `[[slnc n]]`)
 //!
 //! Note: Pauses on output are scaled based on the ratio of the current rate to the default rate (180 wpm)
+//!
+//! ## Audio (earcons)
+//! * The value of `audio` in the rule files is the name of a sound (e.g., `Capital`). The `Earcons_<name>` preference
+//!   gives the sound file (or URL) to play; if there is no such preference, the name is used as the file.
+//! * SSML: `<audio src='file'>` -- any replacements are spoken if the sound can't be played
+//! * SAPI5: `<wav src='file'>`
+//! * Eloquence, Mac: not supported
+//!
+//! ## Segments
+//! `Segments` is not a speech engine. It generates private markup that [`speech_segments`] turns into a list of
+//! [`SpeechSegment`]s (text + `id` + prosody) for callers that drive the speech engine themselves (e.g., the Web Speech API).
+//! Pitch, rate, and volume are relative changes using the same values as SSML.
 #![allow(clippy::needless_return)]
 
 use crate::{errors::*, prefs::PreferenceManager, speech::ReplacementArray};
@@ -79,7 +100,7 @@ use strum_macros::{Display, EnumString};
 use regex::Regex;
 use std::sync::LazyLock;
 use sxd_xpath::Value;
-use html_escape::encode_safe;
+use html_escape::{encode_safe, encode_single_quoted_attribute};
 
 const MIN_PAUSE:f64 = 50.0;         // ms -- avoids clutter of putting out pauses that probably can't be heard
 const PAUSE_SHORT:f64 = 200.0;  // ms
@@ -89,6 +110,11 @@ const PAUSE_XLONG:f64 = 1600.0;   // ms
 const PAUSE_AUTO:f64 = 987654321.5;   // ms -- hopefully unique
 pub const PAUSE_AUTO_STR: &str = "\u{F8FA}\u{F8FA}";
 const RATE_FROM_CONTEXT:f64 = 987654321.5;   // hopefully unique
+const DEFAULT_PITCH:f64 = 140.0;    // hz
+
+// TTS::Segments commands are of the form "\u{F8F0}name value\u{F8F1}" (end commands are "\u{F8F0}/name\u{F8F1}")
+const SEGMENT_COMMAND_START: char = '\u{F8F0}';
+const SEGMENT_COMMAND_END: char = '\u{F8F1}';
 
 const MAX_TRANSLATE_RECURSION: usize = 5;   // probably never more than three -- prevents infinite loop/stack overflows bugs
 
@@ -263,22 +289,28 @@ pub enum TTS {
     None,
     SSML,
     SAPI5,
-//    Eloquence,
-//    Mac,
+    Eloquence,
+    Mac,
+    Segments,
 }
 
-/// Escape literal text so user MathML (e.g. `mtext`) cannot inject SSML/SAPI markup.
-/// Applies only when generating speech (not braille) with SSML or SAPI5 output.
+/// Escape literal text so user MathML (e.g. `mtext`) cannot inject SSML/SAPI markup or Eloquence/Mac commands.
+/// Applies only when generating speech (not braille) with TTS output.
 /// Returns `s` unchanged when no escaping is needed (no allocation).
 pub fn escape_string_for_safety(s: String, rules_for: RulesFor, tts: &TTS) -> String {
-    if rules_for == RulesFor::Braille || (*tts != TTS::SSML && *tts != TTS::SAPI5) {
+    if rules_for == RulesFor::Braille {
         return s;
     }
-    if !needs_xml_text_escape(&s) {
-        return s;
+    match tts {
+        TTS::SSML | TTS::SAPI5 if needs_xml_text_escape(&s) => {
+            log::debug!("Escaping string for safety: {}", s);
+            return encode_safe(&s).into_owned();
+        },
+        TTS::Eloquence if s.contains('`') => return s.replace('`', "'"),      // '`' starts an Eloquence command
+        TTS::Mac if s.contains("[[") => return s.replace("[[", "[ ["),         // '[[' starts a Mac command
+        TTS::Segments if s.contains([SEGMENT_COMMAND_START, SEGMENT_COMMAND_END]) => return s.replace([SEGMENT_COMMAND_START, SEGMENT_COMMAND_END], ""),
+        _ => return s,
     }
-    log::debug!("Escaping string for safety: {}", s);
-    return encode_safe(&s).into_owned();
 }
 
 fn needs_xml_text_escape(s: &str) -> bool {
@@ -375,8 +407,10 @@ impl TTS {
             }
             return Ok( match self {
                 TTS::None  => "".to_string(),
-                TTS::SSML => compute_bookmark_element(&command.value, "mark name", rules_with_context, mathml)?,
-                TTS::SAPI5 => compute_bookmark_element(&command.value, "bookmark mark", rules_with_context, mathml)?,
+                TTS::SSML => format!("<mark name='{}'/>", compute_bookmark_id(&command.value, rules_with_context, mathml)?),
+                TTS::SAPI5 => format!("<bookmark mark='{}'/>", compute_bookmark_id(&command.value, rules_with_context, mathml)?),
+                TTS::Eloquence | TTS::Mac => "".to_string(),    // these only support numeric index marks
+                TTS::Segments => format!("{SEGMENT_COMMAND_START}mark {}{SEGMENT_COMMAND_END}", compute_bookmark_id(&command.value, rules_with_context, mathml)?),
             } );
         }
 
@@ -466,6 +500,9 @@ impl TTS {
             TTS::None  => self.get_string_none(&command, prefs, true),
             TTS::SSML  => self.get_string_ssml(&command, prefs, true),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, true),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, true),
+            TTS::Mac   => self.get_string_mac(&command, prefs, true),
+            TTS::Segments => self.get_string_segments(&command, prefs, true),
         };
 
 
@@ -481,6 +518,9 @@ impl TTS {
             TTS::None  => self.get_string_none(&command, prefs, false),
             TTS::SSML  => self.get_string_ssml(&command, prefs, false),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, false),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, false),
+            TTS::Mac   => self.get_string_mac(&command, prefs, false),
+            TTS::Segments => self.get_string_segments(&command, prefs, false),
         };   
 
         if end_tag.is_empty() {
@@ -490,11 +530,13 @@ impl TTS {
         }
 
 
-        fn compute_bookmark_element<'c, 's:'c, 'm, 'r>(value: &TTSCommandValue, tag_and_attr: &str, rules_with_context: &'r mut SpeechRulesWithContext<'c, 's, 'm>, mathml: Element<'c>) -> Result<String> {
+        fn compute_bookmark_id<'c, 's:'c, 'm, 'r>(value: &TTSCommandValue, rules_with_context: &'r mut SpeechRulesWithContext<'c, 's, 'm>, mathml: Element<'c>) -> Result<String> {
             match value {
                 TTSCommandValue::XPath(xpath) => {
-                    let id = xpath.replace::<String>(rules_with_context, mathml)?;
-                    return Ok( format!("<{tag_and_attr}='{id}'/>") );
+                    // evaluate (rather than 'replace') so the id isn't treated as text to speak (e.g., an id of "X" becoming "cap x")
+                    let id = xpath.evaluate(rules_with_context.get_context(), mathml)
+                        .with_context(|| format!("in 'bookmark': can't evaluate xpath \"{xpath}\""))?;
+                    return Ok( id.string() );
                 },
                 _ => bail!("Implementation error: found bookmark value that did not evaluate to a string"),
             }
@@ -557,7 +599,7 @@ impl TTS {
             // rate must be in [-10, 10], but we get relative %s. 300% => 10 (see comments at top of file)
             TTSCommand::Rate =>  if is_start_tag {format!("<rate speed='{:.1}'>", 10.0*(0.01*command.value.get_num()).log(3.0))} else {String::from("</rate>")},
             TTSCommand::Volume =>if is_start_tag {format!("<volume level='{}'>", command.value.get_num())} else {String::from("</volume>")},
-            TTSCommand::Audio => "".to_string(),    // SAPI5 doesn't support audio
+            TTSCommand::Audio => if is_start_tag {format!("<wav src='{}'>", encode_single_quoted_attribute(&TTS::earcon_file(prefs, command.value.get_string())))} else {String::from("</wav>")},
             TTSCommand::Gender =>if is_start_tag {format!("<voice required=\"Gender={}\">", command.value.get_string())} else {String::from("</prosody>")},
             TTSCommand::Voice =>if is_start_tag {format!("<voice required=\"Name={}\">", command.value.get_string())} else {String::from("</prosody>")},
             TTSCommand::Spell =>if is_start_tag {format!("<spell>{}", command.value.get_string())} else {String::from("</spell>")},
@@ -592,7 +634,7 @@ impl TTS {
             TTSCommand::Pitch => if is_start_tag {format!("<prosody pitch='{}%'>", command.value.get_num())} else {String::from("</prosody>")},
             TTSCommand::Rate =>  if is_start_tag {format!("<prosody rate='{}%'>", command.value.get_num())} else {String::from("</prosody>")},
             TTSCommand::Volume =>if is_start_tag {format!("<prosody volume='{}db'>", command.value.get_num())} else {String::from("</prosody>")},
-            TTSCommand::Audio =>if is_start_tag {format!("<audio src='{}'>", command.value.get_string())} else {String::from("</audio>")}, // only 'beep' is supported for now
+            TTSCommand::Audio =>if is_start_tag {format!("<audio src='{}'>", encode_single_quoted_attribute(&TTS::earcon_file(prefs, command.value.get_string())))} else {String::from("</audio>")},
             TTSCommand::Gender =>if is_start_tag {format!("<voice required='gender=\"{}\"'>", command.value.get_string())} else {String::from("</voice>")},
             TTSCommand::Voice =>if is_start_tag {format!("<voice required='{}'>", command.value.get_string())} else {String::from("</voice>")},
             TTSCommand::Spell =>if is_start_tag {format!("<say-as interpret-as='characters'>{}", command.value.get_string())} else {String::from("</say-as>")},
@@ -605,6 +647,155 @@ impl TTS {
         }
     }
 
+    // Eloquence only supports absolute values, so the end tags reset the values to their defaults
+    fn get_string_eloquence(&self, command: &TTSCommandRule, prefs: &PreferenceManager, is_start_tag: bool) -> String  {
+        return match &command.command {
+            TTSCommand::Pause => if is_start_tag {
+                let amount = command.value.get_num();
+                if amount == PAUSE_AUTO {
+                    PAUSE_AUTO_STR.to_string()
+                } else {
+                    let amount = amount * TTS::get_pause_multiplier(prefs);
+                    if amount > MIN_PAUSE {
+                        format!("`p{} ", (amount * 180.0/prefs.get_rate()).round())
+                    } else {
+                        "".to_string()
+                    }
+                }
+            } else {
+                "".to_string()
+            },
+            // pitch is in [0, 100] -- 0 ~= 42hz, 100 ~= 422hz
+            TTSCommand::Pitch => {
+                let pitch = if is_start_tag {DEFAULT_PITCH * (1.0 + command.value.get_num()/100.0)} else {DEFAULT_PITCH};
+                format!("`vb{} ", ((pitch - 42.0)/3.8).round().clamp(0.0, 100.0))
+            },
+            // rate is in [0, 250] -- words/min = 4.18 * Eloquence rate + 66
+            TTSCommand::Rate => {
+                let rate = if is_start_tag {prefs.get_rate() * command.value.get_num()/100.0} else {prefs.get_rate()};
+                format!("`vs{} ", ((rate - 66.0)/4.18).round().clamp(0.0, 250.0))
+            },
+            TTSCommand::Volume => {
+                let volume = prefs.pref_to_string("Volume").parse::<f64>().unwrap_or(100.0);
+                let volume = if is_start_tag {volume * (1.0 + command.value.get_num()/100.0)} else {volume};
+                format!("`vv{} ", volume.round().clamp(0.0, 100.0))
+            },
+            TTSCommand::Audio | TTSCommand::Gender | TTSCommand::Voice => "".to_string(),    // not supported
+            TTSCommand::Spell => if is_start_tag {command.value.get_string().to_string()} else {"".to_string()},
+            TTSCommand::Pronounce => if is_start_tag {
+                    let pronounce = command.value.get_pronounce();
+                    if pronounce.eloquence.is_empty() {
+                        pronounce.text.to_string()
+                    } else {
+                        format!("`[{}] ", pronounce.eloquence)
+                    }
+                } else {
+                    "".to_string()
+                },
+            TTSCommand::Bookmark => panic!("Internal error: bookmarks should have been handled earlier"),
+        };
+    }
+
+    // Mac (Apple speech) embedded commands support relative values, so the end tags undo the change
+    fn get_string_mac(&self, command: &TTSCommandRule, prefs: &PreferenceManager, is_start_tag: bool) -> String  {
+        let sign = if is_start_tag {1.0} else {-1.0};
+        return match &command.command {
+            TTSCommand::Pause => if is_start_tag {
+                let amount = command.value.get_num();
+                if amount == PAUSE_AUTO {
+                    PAUSE_AUTO_STR.to_string()
+                } else {
+                    let amount = amount * TTS::get_pause_multiplier(prefs);
+                    if amount > MIN_PAUSE {
+                        format!("[[slnc {}]]", (amount * 180.0/prefs.get_rate()).round())
+                    } else {
+                        "".to_string()
+                    }
+                }
+            } else {
+                "".to_string()
+            },
+            // pitch change is in semitones (12 per octave)
+            TTSCommand::Pitch => format!("[[pbas {:+.1}]]", sign * 12.0*(1.0+command.value.get_num()/100.0).log2()),
+            // rate change is in words/min
+            TTSCommand::Rate => format!("[[rate {:+}]]", (sign * prefs.get_rate() * (command.value.get_num() - 100.0)/100.0).round()),
+            // volume is in [0, 1]
+            TTSCommand::Volume => format!("[[volm {:+.2}]]", sign * command.value.get_num()/100.0),
+            TTSCommand::Audio | TTSCommand::Gender | TTSCommand::Voice => "".to_string(),    // not supported
+            TTSCommand::Spell => if is_start_tag {format!("[[char LTRL]]{}", command.value.get_string())} else {String::from("[[char NORM]]")},
+            TTSCommand::Pronounce => if is_start_tag {command.value.get_pronounce().text.to_string()} else {"".to_string()},
+            TTSCommand::Bookmark => panic!("Internal error: bookmarks should have been handled earlier"),
+        };
+    }
+
+    // Segments markup holds the (unconverted) values -- `speech_segments` turns the markup into SpeechSegments
+    fn get_string_segments(&self, command: &TTSCommandRule, prefs: &PreferenceManager, is_start_tag: bool) -> String  {
+        if !is_start_tag {
+            return match &command.command {
+                TTSCommand::Pitch | TTSCommand::Rate | TTSCommand::Volume | TTSCommand::Spell | TTSCommand::Pronounce | TTSCommand::Audio =>
+                    format!("{SEGMENT_COMMAND_START}/{}{SEGMENT_COMMAND_END}", command.command),
+                _ => "".to_string(),
+            };
+        }
+        return match &command.command {
+            TTSCommand::Pause => {
+                let amount = command.value.get_num();
+                if amount == PAUSE_AUTO {
+                    PAUSE_AUTO_STR.to_string()
+                } else {
+                    let amount = amount * TTS::get_pause_multiplier(prefs);
+                    if amount > MIN_PAUSE {
+                        format!("{SEGMENT_COMMAND_START}pause {}{SEGMENT_COMMAND_END}", (amount * 180.0/prefs.get_rate()).round())
+                    } else {
+                        "".to_string()
+                    }
+                }
+            },
+            TTSCommand::Pitch | TTSCommand::Rate | TTSCommand::Volume =>
+                format!("{SEGMENT_COMMAND_START}{} {}{SEGMENT_COMMAND_END}", command.command, command.value.get_num()),
+            TTSCommand::Audio => format!("{SEGMENT_COMMAND_START}audio {}{SEGMENT_COMMAND_END}", TTS::earcon_file(prefs, command.value.get_string())),
+            TTSCommand::Gender | TTSCommand::Voice => "".to_string(),    // not supported
+            TTSCommand::Spell => format!("{SEGMENT_COMMAND_START}spell{SEGMENT_COMMAND_END}{}", command.value.get_string()),
+            TTSCommand::Pronounce => {
+                let pronounce = command.value.get_pronounce();
+                format!("{SEGMENT_COMMAND_START}pronounce {}{SEGMENT_COMMAND_END}{}", pronounce.ipa, pronounce.text)
+            },
+            TTSCommand::Bookmark => panic!("Internal error: bookmarks should have been handled earlier"),
+        };
+    }
+
+    /// Returns the start and end markup that changes the rate to `rate` (a percentage of the current rate) for the TTS engine.
+    /// Empty strings are returned if the TTS engine doesn't support changing the rate.
+    pub fn rate_tags(&self, prefs: &PreferenceManager, rate: f64) -> (String, String) {
+        let command = TTSCommandRule::new(TTSCommand::Rate, TTSCommandValue::Number(rate), ReplacementArray::build_empty());
+        return match self {
+            TTS::None  => ("".to_string(), "".to_string()),
+            TTS::SSML  => (self.get_string_ssml(&command, prefs, true), self.get_string_ssml(&command, prefs, false)),
+            TTS::SAPI5 => (self.get_string_sapi5(&command, prefs, true), self.get_string_sapi5(&command, prefs, false)),
+            TTS::Eloquence => (self.get_string_eloquence(&command, prefs, true), self.get_string_eloquence(&command, prefs, false)),
+            TTS::Mac   => (self.get_string_mac(&command, prefs, true), self.get_string_mac(&command, prefs, false)),
+            TTS::Segments => (self.get_string_segments(&command, prefs, true), self.get_string_segments(&command, prefs, false)),
+        };
+    }
+
+    /// Returns the markup that plays the sound `name` (e.g., "MathStart") for the TTS engine.
+    /// An empty string is returned if the TTS engine doesn't support sounds.
+    pub fn earcon(&self, prefs: &PreferenceManager, name: &str) -> String {
+        let command = TTSCommandRule::new(TTSCommand::Audio, TTSCommandValue::String(name.to_string()), ReplacementArray::build_empty());
+        return match self {
+            TTS::SSML  => self.get_string_ssml(&command, prefs, true) + &self.get_string_ssml(&command, prefs, false),
+            TTS::SAPI5 => self.get_string_sapi5(&command, prefs, true) + &self.get_string_sapi5(&command, prefs, false),
+            TTS::Segments => self.get_string_segments(&command, prefs, true) + &self.get_string_segments(&command, prefs, false),
+            TTS::None | TTS::Eloquence | TTS::Mac => "".to_string(),
+        };
+    }
+
+    /// The sound file for the sound `name` -- this is the value of the `Earcons_<name>` preference if there is one, otherwise `name`
+    fn earcon_file(prefs: &PreferenceManager, name: &str) -> String {
+        let file = prefs.pref_to_string(&format!("Earcons_{name}"));
+        return if file.is_empty() || file == crate::prefs::NO_PREFERENCE {name.to_string()} else {file};
+    }
+
     fn get_pause_multiplier(prefs: &PreferenceManager) -> f64 {
         return prefs.pref_to_string("PauseFactor").parse::<f64>().unwrap_or(100.)/100.0;
     }
@@ -615,6 +806,9 @@ impl TTS {
     /// There is a bias towards pausing more _after_ longer strings.
     pub fn compute_auto_pause(&self, prefs: &PreferenceManager, before: &str, after: &str) -> String {
         static REMOVE_XML: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<.+?>").unwrap()); // punctuation ending with a '.'
+        static REMOVE_ELOQUENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`\S+ ?").unwrap());
+        static REMOVE_MAC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\[.+?\]\]").unwrap());
+        static REMOVE_SEGMENTS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x{F8F0}[^\x{F8F1}]*\x{F8F1}").unwrap());
         let before_len;
         let after_len;
         match self {
@@ -622,6 +816,18 @@ impl TTS {
                 before_len = REMOVE_XML.replace_all(before, "").len();
                 after_len = REMOVE_XML.replace_all(after, "").len();
             },
+            TTS::Eloquence => {
+                before_len = REMOVE_ELOQUENCE.replace_all(before, "").len();
+                after_len = REMOVE_ELOQUENCE.replace_all(after, "").len();
+            },
+            TTS::Mac => {
+                before_len = REMOVE_MAC.replace_all(before, "").len();
+                after_len = REMOVE_MAC.replace_all(after, "").len();
+            },
+            TTS::Segments => {
+                before_len = REMOVE_SEGMENTS.replace_all(before, "").len();
+                after_len = REMOVE_SEGMENTS.replace_all(after, "").len();
+            },
             _ => {
                 before_len = before.len();
                 after_len = after.len();
@@ -648,6 +854,9 @@ impl TTS {
             TTS::None  => self.get_string_none(&command, prefs, true),
             TTS::SSML  => self.get_string_ssml(&command, prefs, true),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, true),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, true),
+            TTS::Mac   => self.get_string_mac(&command, prefs, true),
+            TTS::Segments => self.get_string_segments(&command, prefs, true),
         };
 
     }
@@ -665,6 +874,9 @@ impl TTS {
             TTS::None  => self.merge_pauses_none(str),
             TTS::SSML  => self.merge_pauses_ssml(str),
             TTS::SAPI5 => self.merge_pauses_sapi5(str),
+            TTS::Eloquence => self.merge_pauses_eloquence(str),
+            TTS::Mac   => self.merge_pauses_mac(str),
+            TTS::Segments => self.merge_pauses_segments(str),
         };        
     }
 
@@ -701,6 +913,27 @@ impl TTS {
         return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
     }
 
+    fn merge_pauses_eloquence(&self, str: &str) -> String {
+        static CONSECUTIVE_BREAKS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(`p\d+ *){2,}").unwrap()); // two or more pauses
+        static PAUSE_AMOUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`p(\d+)").unwrap()); // amount after '`p'
+        let replacement = |amount: usize| format!("`p{amount} ");
+        return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
+    }
+
+    fn merge_pauses_mac(&self, str: &str) -> String {
+        static CONSECUTIVE_BREAKS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\[\[slnc \d+\]\] *){2,}").unwrap()); // two or more pauses
+        static PAUSE_AMOUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"slnc (\d+)").unwrap()); // amount after 'slnc'
+        let replacement = |amount: usize| format!("[[slnc {amount}]]");
+        return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
+    }
+
+    fn merge_pauses_segments(&self, str: &str) -> String {
+        static CONSECUTIVE_BREAKS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\x{F8F0}pause \d+\x{F8F1} *){2,}").unwrap()); // two or more pauses
+        static PAUSE_AMOUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"pause (\d+)").unwrap()); // amount after 'pause'
+        let replacement = |amount: usize| format!("{SEGMENT_COMMAND_START}pause {amount}{SEGMENT_COMMAND_END}");
+        return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
+    }
+
     fn merge_pauses_ssml(&self, str: &str) -> String {
         static CONSECUTIVE_BREAKS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(<break time=[^>]+?> *){2,}").unwrap()); // two or more pauses
         static PAUSE_AMOUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"time=.*?(\d+)").unwrap()); // amount after 'time'
@@ -709,6 +942,109 @@ impl TTS {
     }
 }
 
+/// A piece of speech along with the `id` of the MathML node it came from and how it should be spoken.
+/// These are generated by [`speech_segments`] (see [`crate::get_spoken_segments`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSegment {
+    /// The text to speak
+    pub text: String,
+    /// The `id` of the MathML node the text came from (empty if not known)
+    pub id: String,
+    /// The length of the pause (in ms) after the text
+    pub pause: f64,
+    /// The relative change in pitch (%) -- 0 is no change
+    pub pitch: f64,
+    /// The rate as a percentage of the current rate -- 100 is no change
+    pub rate: f64,
+    /// The relative change in volume -- 0 is no change
+    pub volume: f64,
+    /// True if the text should be spoken letter by letter
+    pub spell: bool,
+    /// The pronunciation (IPA) of the text if the speech engine may not say it properly
+    pub pronounce: Option<String>,
+    /// A sound (file or URL) to play -- if present, `text` is only spoken if the sound can't be played
+    pub audio: Option<String>,
+}
+
+/// Convert the speech generated with `TTS::Segments` into a list of [`SpeechSegment`]s.
+///
+/// A new segment is started whenever the `id`, pitch, rate, volume, spelling, pronunciation, or sound changes.
+/// Pauses are added to the previous segment (a pause at the start is dropped).
+pub fn speech_segments(speech: &str) -> Vec<SpeechSegment> {
+    let mut segments = Vec::new();
+    let mut state = SegmentState::default();
+    let mut text = String::new();
+    let mut rest = speech;
+    while let Some(i_start) = rest.find(SEGMENT_COMMAND_START) {
+        text += &rest[..i_start];
+        let command = &rest[i_start + SEGMENT_COMMAND_START.len_utf8()..];
+        let i_end = command.find(SEGMENT_COMMAND_END).unwrap_or(command.len());
+        rest = &command[(i_end + SEGMENT_COMMAND_END.len_utf8()).min(command.len())..];
+        let command = &command[..i_end];
+
+        // any change in state ends the current segment
+        state.add_segment(&mut segments, &text);
+        text.clear();
+        let (name, value) = command.split_once(' ').unwrap_or((command, ""));
+        let number = value.parse::<f64>().unwrap_or(0.0);
+        match name {
+            "mark" => state.id = value.to_string(),
+            "pause" => if let Some(segment) = segments.last_mut() {
+                segment.pause = segment.pause.max(number);
+            },
+            "pitch" => state.pitch.push(number),
+            "rate" => state.rate.push(number),
+            "volume" => state.volume.push(number),
+            "spell" => state.spell += 1,
+            "pronounce" => state.pronounce.push(value.to_string()),
+            "audio" => state.audio = Some(value.to_string()),
+            "/pitch" => {state.pitch.pop();},
+            "/rate" => {state.rate.pop();},
+            "/volume" => {state.volume.pop();},
+            "/spell" => state.spell = state.spell.saturating_sub(1),
+            "/pronounce" => {state.pronounce.pop();},
+            "/audio" => state.audio = None,
+            _ => log::error!("Internal error: unknown speech segment command '{}'", command),
+        }
+    }
+    text += rest;
+    state.add_segment(&mut segments, &text);
+    return segments;
+
+    /// The values in effect at some point in the speech -- these are stacks because the commands can be nested
+    #[derive(Default)]
+    struct SegmentState {
+        id: String,
+        pitch: Vec<f64>,
+        rate: Vec<f64>,
+        volume: Vec<f64>,
+        spell: usize,
+        pronounce: Vec<String>,
+        audio: Option<String>,
+    }
+
+    impl SegmentState {
+        fn add_segment(&self, segments: &mut Vec<SpeechSegment>, text: &str) {
+            let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+            if text.is_empty() && self.audio.is_none() {
+                return;
+            }
+            // nested changes are relative to the enclosing change (as in SSML)
+            segments.push(SpeechSegment {
+                text,
+                id: self.id.clone(),
+                pause: 0.0,
+                pitch: 100.0 * self.pitch.iter().fold(1.0, |pitch, change| pitch * (1.0 + change/100.0)) - 100.0,
+                rate: 100.0 * self.rate.iter().fold(1.0, |rate, change| rate * change/100.0),
+                volume: self.volume.iter().fold(0.0, |volume, change| volume + change),
+                spell: self.spell > 0,
+                pronounce: self.pronounce.last().cloned(),
+                audio: self.audio.clone(),
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -777,6 +1113,61 @@ mod tests {
         assert!(output.contains("300ms"));
     }
 
+    #[test]
+    /// Uses the maximum pause when merging consecutive Eloquence pauses.
+    fn merge_pauses_eloquence_keeps_max() {
+        let input = "a `p100 `p300 b";
+        let output = TTS::Eloquence.merge_pauses(input);
+        assert_eq!(output, "a `p300 b");
+    }
+
+    #[test]
+    /// Uses the maximum pause when merging consecutive Mac silences.
+    fn merge_pauses_mac_keeps_max() {
+        let input = "a [[slnc 300]] [[slnc 100]]b";
+        let output = TTS::Mac.merge_pauses(input);
+        assert_eq!(output, "a [[slnc 300]]b");
+    }
+
+    #[test]
+    fn merge_pauses_segments_keeps_max() {
+        let input = "a \u{F8F0}pause 300\u{F8F1} \u{F8F0}pause 100\u{F8F1}b";
+        let output = TTS::Segments.merge_pauses(input);
+        assert_eq!(output, "a \u{F8F0}pause 300\u{F8F1}b");
+    }
+
+    #[test]
+    fn speech_segments_nest_and_pause() {
+        let speech = "\u{F8F0}mark a\u{F8F1} one \u{F8F0}rate 50\u{F8F1}two \u{F8F0}rate 200\u{F8F1}\u{F8F0}pitch 10\u{F8F1}three\u{F8F0}/pitch\u{F8F1}\u{F8F0}/rate\u{F8F1} \
+                      \u{F8F0}pause 250\u{F8F1}\u{F8F0}/rate\u{F8F1}\u{F8F0}mark b\u{F8F1}\u{F8F0}pronounce ˈæ\u{F8F1}a\u{F8F0}/pronounce\u{F8F1}";
+        let segments = speech_segments(speech);
+        let summary = segments.iter()
+                .map(|s| (s.text.as_str(), s.id.as_str(), s.rate, s.pitch, s.pause, s.pronounce.as_deref()))
+                .collect::<Vec<_>>();
+        assert_eq!(summary, vec![
+            ("one", "a", 100.0, 0.0, 0.0, None),
+            ("two", "a", 50.0, 0.0, 0.0, None),
+            ("three", "a", 100.0, 10.000000000000014, 250.0, None),
+            ("a", "b", 100.0, 0.0, 0.0, Some("ˈæ")),
+        ]);
+    }
+
+    #[test]
+    fn escape_string_for_safety_removes_segment_commands() {
+        let output = escape_string_for_safety("a\u{F8F0}pause 5000\u{F8F1}".to_string(), RulesFor::Speech, &TTS::Segments);
+        assert_eq!(output, "apause 5000");
+    }
+
+    #[test]
+    fn escape_string_for_safety_escapes_commands() {
+        let output = escape_string_for_safety("`vs200 x".to_string(), RulesFor::Speech, &TTS::Eloquence);
+        assert_eq!(output, "'vs200 x");
+        let output = escape_string_for_safety("[[rate 500]] x".to_string(), RulesFor::Speech, &TTS::Mac);
+        assert_eq!(output, "[ [rate 500]] x");
+        let output = escape_string_for_safety("[[rate 500]] x".to_string(), RulesFor::Speech, &TTS::Eloquence);
+        assert_eq!(output, "[[rate 500]] x");
+    }
+
     /// Returns the same String allocation when escaping is not needed.
     #[test]
     fn escape_string_for_safety_no_alloc_when_clean() {
@@ -0,0 +1,306 @@
+//! The JavaScript API for MathCAT (built with the `wasm` feature). It mirrors the functions in [`crate::interface`].
+//!
+//! The Rules dir is built into the library (see `shim_filesystem.rs`), so there are no files to serve: the rules dir is `"Rules"`.
+//! Only the language and braille code rules that are used are unzipped.
+//!
+//! Conventions:
+//! * Function names are the camelCase versions of the Rust names (e.g., `getSpokenText` for `get_spoken_text`).
+//! * Errors are thrown as a JavaScript `Error` whose message is the full MathCAT error (see [`errors_to_string`]).
+//! * Rust tuples are returned as small objects (e.g., [`NavigationLocation`] has `id` and `offset` fields).
+//!
+//! A typical use from JavaScript (after `wasm-pack build --target web -- --features wasm`):
+//! ```js
+//! import init, * as MathCAT from "./pkg/mathcat.js";
+//! await init();
+//! MathCAT.setRulesDir("Rules");
+//! MathCAT.setMathML("<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>");
+//! console.log(MathCAT.getSpokenText());   // "1 half"
+//! document.addEventListener("keydown", ev =>
+//!     speak(MathCAT.doNavigateKeypress(ev.keyCode, ev.shiftKey, ev.ctrlKey, ev.altKey, ev.metaKey)));
+//! ```
+#![allow(clippy::needless_return)]
+
+use wasm_bindgen::prelude::*;
+use crate::errors::*;
+use crate::interface::*;
+
+/// Convert a MathCAT error to an error that is thrown in JavaScript
+fn to_js_error(e: Error) -> JsError {
+    return JsError::new(&errors_to_string(&e));
+}
+
+/// A location in the MathML: the `id` of a node and the offset into it
+/// (the offset is the char in a leaf such as `<mn>123</mn>` and is otherwise 0).
+#[wasm_bindgen(getter_with_clone)]
+pub struct NavigationLocation {
+    pub id: String,
+    pub offset: usize,
+}
+
+impl From<(String, usize)> for NavigationLocation {
+    fn from((id, offset): (String, usize)) -> Self {
+        return NavigationLocation { id, offset };
+    }
+}
+
+/// The MathML of a node and the offset into it (see [`NavigationLocation`])
+#[wasm_bindgen(getter_with_clone)]
+pub struct NavigationMathML {
+    pub mathml: String,
+    pub offset: usize,
+}
+
+/// The start and end (exclusive) of a range of braille cells
+#[wasm_bindgen]
+pub struct BrailleRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A piece of speech along with the MathML node it came from and how it should be spoken (see [`crate::SpeechSegment`]).
+#[wasm_bindgen(getter_with_clone)]
+pub struct SpeechSegment {
+    pub text: String,
+    pub id: String,
+    pub pause: f64,
+    pub pitch: f64,
+    pub rate: f64,
+    pub volume: f64,
+    pub spell: bool,
+    pub pronounce: Option<String>,
+    pub audio: Option<String>,
+}
+
+impl From<crate::SpeechSegment> for SpeechSegment {
+    fn from(segment: crate::SpeechSegment) -> Self {
+        let crate::SpeechSegment { text, id, pause, pitch, rate, volume, spell, pronounce, audio } = segment;
+        return SpeechSegment { text, id, pause, pitch, rate, volume, spell, pronounce, audio };
+    }
+}
+
+/// Braille broken into lines along with the MathML node for each braille cell (see [`crate::BrailleLines`]).
+#[wasm_bindgen]
+pub struct BrailleLines(crate::BrailleLines);
+
+#[wasm_bindgen]
+impl BrailleLines {
+    /// The braille for each line
+    #[wasm_bindgen(getter)]
+    pub fn lines(&self) -> Vec<String> {
+        return self.0.lines.clone();
+    }
+
+    /// The MathML node for each cell in line 'line'
+    pub fn cells(&self, line: usize) -> Vec<NavigationLocation> {
+        return match self.0.cells.get(line) {
+            None => vec![],
+            Some(cells) => cells.iter().map(|cell| NavigationLocation::from(cell.clone())).collect(),
+        };
+    }
+}
+
+/// The version number of MathCAT
+#[wasm_bindgen(js_name = getVersion)]
+pub fn get_version_js() -> String {
+    return get_version();
+}
+
+/// Set the rules dir -- for the web build, this should be `"Rules"` (the rules that are built in)
+#[wasm_bindgen(js_name = setRulesDir)]
+pub fn set_rules_dir_js(dir: &str) -> Result<(), JsError> {
+    return set_rules_dir(dir).map_err(to_js_error);
+}
+
+/// Set the MathML to speak/braille/navigate; returns the canonical MathML
+#[wasm_bindgen(js_name = setMathML)]
+pub fn set_mathml_js(mathml: &str) -> Result<String, JsError> {
+    return set_mathml(mathml).map_err(to_js_error);
+}
+
+/// Set the math using LaTeX; returns the canonical MathML
+#[wasm_bindgen(js_name = setLaTeX)]
+pub fn set_latex_js(latex: &str) -> Result<String, JsError> {
+    return set_latex(latex).map_err(to_js_error);
+}
+
+/// Set the math using ASCIIMath; returns the canonical MathML
+#[wasm_bindgen(js_name = setASCIIMath)]
+pub fn set_asciimath_js(asciimath: &str) -> Result<String, JsError> {
+    return set_asciimath(asciimath).map_err(to_js_error);
+}
+
+/// The speech for the MathML that was set
+#[wasm_bindgen(js_name = getSpokenText)]
+pub fn get_spoken_text_js() -> Result<String, JsError> {
+    return get_spoken_text().map_err(to_js_error);
+}
+
+/// The speech for the MathML that was set, broken into segments
+#[wasm_bindgen(js_name = getSpokenSegments)]
+pub fn get_spoken_segments_js() -> Result<Vec<SpeechSegment>, JsError> {
+    let segments = get_spoken_segments().map_err(to_js_error)?;
+    return Ok( segments.into_iter().map(SpeechSegment::from).collect() );
+}
+
+/// An overview of the MathML that was set
+#[wasm_bindgen(js_name = getOverviewText)]
+pub fn get_overview_text_js() -> Result<String, JsError> {
+    return get_overview_text().map_err(to_js_error);
+}
+
+/// An overview of the current navigation node
+#[wasm_bindgen(js_name = getNavigationOverviewText)]
+pub fn get_navigation_overview_text_js() -> Result<String, JsError> {
+    return get_navigation_overview_text().map_err(to_js_error);
+}
+
+/// The value of the preference 'name'
+#[wasm_bindgen(js_name = getPreference)]
+pub fn get_preference_js(name: &str) -> Result<String, JsError> {
+    return get_preference(name).map_err(to_js_error);
+}
+
+/// Set the preference 'name' to 'value'
+#[wasm_bindgen(js_name = setPreference)]
+pub fn set_preference_js(name: &str, value: &str) -> Result<(), JsError> {
+    return set_preference(name, value).map_err(to_js_error);
+}
+
+/// The braille for the MathML that was set; if 'navNodeId' is not empty, that node is highlighted
+#[wasm_bindgen(js_name = getBraille)]
+pub fn get_braille_js(nav_node_id: &str) -> Result<String, JsError> {
+    return get_braille(nav_node_id).map_err(to_js_error);
+}
+
+/// The braille for the MathML that was set broken into lines of at most 'lineLength' cells
+#[wasm_bindgen(js_name = getBrailleLines)]
+pub fn get_braille_lines_js(nav_node_id: &str, line_length: usize) -> Result<BrailleLines, JsError> {
+    return get_braille_lines(nav_node_id, line_length).map(BrailleLines).map_err(to_js_error);
+}
+
+/// Convert braille in the `BrailleEncoding` to Unicode braille
+#[wasm_bindgen(js_name = brailleToUnicode)]
+pub fn braille_to_unicode_js(braille: &str) -> Result<String, JsError> {
+    return braille_to_unicode(braille).map_err(to_js_error);
+}
+
+/// Convert braille in the `BrailleCode` to MathML
+#[wasm_bindgen(js_name = brailleToMathML)]
+pub fn braille_to_mathml_js(braille: &str) -> Result<String, JsError> {
+    return braille_to_mathml(braille).map_err(to_js_error);
+}
+
+/// The braille for the MathML with the current navigation node highlighted
+#[wasm_bindgen(js_name = getNavigationBraille)]
+pub fn get_navigation_braille_js() -> Result<String, JsError> {
+    return get_navigation_braille().map_err(to_js_error);
+}
+
+/// The MathML for the current navigation node (for copying)
+#[wasm_bindgen(js_name = getNavigationCopy)]
+pub fn get_navigation_copy_js() -> Result<String, JsError> {
+    return get_navigation_copy().map_err(to_js_error);
+}
+
+/// Navigate using a key event (e.g., `doNavigateKeypress(ev.keyCode, ev.shiftKey, ev.ctrlKey, ev.altKey, ev.metaKey)`);
+/// returns the speech for the new navigation node
+#[wasm_bindgen(js_name = doNavigateKeypress)]
+pub fn do_navigate_keypress_js(key: usize, shift_key: bool, control_key: bool, alt_key: bool, meta_key: bool) -> Result<String, JsError> {
+    return do_navigate_keypress(key, shift_key, control_key, alt_key, meta_key).map_err(to_js_error);
+}
+
+/// Navigate using a command (e.g., `"ZoomIn"`); returns the speech for the new navigation node
+#[wasm_bindgen(js_name = doNavigateCommand)]
+pub fn do_navigate_command_js(command: &str) -> Result<String, JsError> {
+    return do_navigate_command(command).map_err(to_js_error);
+}
+
+/// Set the current navigation node
+#[wasm_bindgen(js_name = setNavigationNode)]
+pub fn set_navigation_node_js(id: &str, offset: usize) -> Result<(), JsError> {
+    return set_navigation_node(id, offset).map_err(to_js_error);
+}
+
+/// The MathML and offset of the current navigation node
+#[wasm_bindgen(js_name = getNavigationMathML)]
+pub fn get_navigation_mathml_js() -> Result<NavigationMathML, JsError> {
+    return get_navigation_mathml().map(|(mathml, offset)| NavigationMathML { mathml, offset }).map_err(to_js_error);
+}
+
+/// The `id` and offset of the current navigation node
+#[wasm_bindgen(js_name = getNavigationMathMLId)]
+pub fn get_navigation_mathml_id_js() -> Result<NavigationLocation, JsError> {
+    return get_navigation_mathml_id().map(NavigationLocation::from).map_err(to_js_error);
+}
+
+/// The range of braille cells for the current navigation node
+#[wasm_bindgen(js_name = getBraillePosition)]
+pub fn get_braille_position_js() -> Result<BrailleRange, JsError> {
+    return get_braille_position().map(|(start, end)| BrailleRange { start, end }).map_err(to_js_error);
+}
+
+/// The navigation node for the braille cell at 'position'
+#[wasm_bindgen(js_name = getNavigationNodeFromBraillePosition)]
+pub fn get_navigation_node_from_braille_position_js(position: usize) -> Result<NavigationLocation, JsError> {
+    return get_navigation_node_from_braille_position(position).map(NavigationLocation::from).map_err(to_js_error);
+}
+
+/// The supported braille codes
+#[wasm_bindgen(js_name = getSupportedBrailleCodes)]
+pub fn get_supported_braille_codes_js() -> Result<Vec<String>, JsError> {
+    return get_supported_braille_codes().map_err(to_js_error);
+}
+
+/// The supported languages (e.g., `"en"`, `"en-gb"`)
+#[wasm_bindgen(js_name = getSupportedLanguages)]
+pub fn get_supported_languages_js() -> Result<Vec<String>, JsError> {
+    return get_supported_languages().map_err(to_js_error);
+}
+
+/// The supported speech styles for 'lang'
+#[wasm_bindgen(js_name = getSupportedSpeechStyles)]
+pub fn get_supported_speech_styles_js(lang: &str) -> Result<Vec<String>, JsError> {
+    return get_supported_speech_styles(lang).map_err(to_js_error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speech_segments_keep_every_field() -> Result<()> {
+        set_rules_dir("Rules")?;
+        set_preference("Language", "en")?;
+        set_preference("SpeechStyle", "ClearSpeak")?;
+        set_mathml("<math><mi>x</mi><mo>+</mo><mi>y</mi></math>")?;
+        let segments = get_spoken_segments()?;
+        assert!(!segments.is_empty());
+        for segment in segments {
+            let js_segment = SpeechSegment::from(segment.clone());
+            assert_eq!(
+                (js_segment.text, js_segment.id, js_segment.pause, js_segment.pitch, js_segment.rate),
+                (segment.text, segment.id, segment.pause, segment.pitch, segment.rate));
+            assert_eq!(
+                (js_segment.volume, js_segment.spell, js_segment.pronounce, js_segment.audio),
+                (segment.volume, segment.spell, segment.pronounce, segment.audio));
+        }
+        return Ok(());
+    }
+
+    #[test]
+    fn braille_lines_cells() -> Result<()> {
+        set_rules_dir("Rules")?;
+        set_preference("BrailleCode", "Nemeth")?;
+        set_mathml("<math><mi>x</mi><mo>+</mo><mn>1</mn></math>")?;
+        let lines = BrailleLines(crate::interface::get_braille_lines("", 40)?);
+        assert_eq!(lines.lines(), vec!["⠭⠬⠂".to_string()]);
+        let cells = lines.cells(0);
+        assert_eq!(cells.len(), 3);
+        for cell in &cells {
+            set_navigation_node(&cell.id, cell.offset)?;    // each cell is for a node in the MathML
+        }
+        assert!(cells[0].id != cells[1].id && cells[1].id != cells[2].id);
+        assert!(lines.cells(1).is_empty());     // past the last line
+        return Ok(());
+    }
+}
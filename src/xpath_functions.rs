@@ -13,6 +13,8 @@
 //! * `IsLargeOp(node)` -- returns true if the node is a large operator (e.g, integral or sum)
 //! * `IsBracketed(node, left, right, requires_comma)` -- returns true if the first/last element in the mrow match `left`/`right`.
 //!   If the optional `requires_comma` argument is given and is `true`, then there also must be a "," in the mrow (e.g., "f(x,y)")
+//! * `ChemicalFormula(node)` -- returns the formula (e.g., "H2O" or "SO4^2-") if the node is a simple chemical formula, otherwise an empty string.
+//!   This is used to look up the names of compounds.
 //! * `DEBUG(xpath)` -- _Very_ useful function for debugging speech rules.
 //!   This can be used to surround a whole or part of an xpath expression in a match or output.
 //!   The result will be printed to standard output and the result returned so that `DEBUG` does not affect the computation.    
@@ -22,7 +24,7 @@ use sxd_xpath::{Value, Context, context, function::*, nodeset::*};
 use crate::definitions::{Definitions, SPEECH_DEFINITIONS, BRAILLE_DEFINITIONS};
 use regex::Regex;
 use crate::pretty_print::mml_to_string;
-use std::cell::{Ref, RefCell};
+use std::cell::RefCell;
 use log::{debug, error, warn};
 use std::sync::LazyLock;
 use std::thread::LocalKey;
@@ -594,7 +596,7 @@ impl ToOrdinal {
     }
 
 
-    fn hundreds_to_words(number: &[usize], words: &[Ref<Vec<String>>; 3]) -> Option<String> {
+    fn hundreds_to_words(number: &[usize], words: &[&Vec<String>; 3]) -> Option<String> {
         assert!( number.len() == 3 );
         return SPEECH_DEFINITIONS.with(|definitions| {
             let definitions = definitions.borrow();
@@ -1210,6 +1212,7 @@ impl Function for EdgeNode {
 pub struct SpeakIntentName;
 /// SpeakIntentName(intent, verbosity)
 ///   Returns a string corresponding to the intent name with the indicated verbosity
+///   (or the user's `SpeechOverrides` word for the intent name if there is one)
 impl Function for SpeakIntentName {
     fn evaluate<'d>(&self,
                         _context: &context::Evaluation<'_, 'd>,
@@ -1221,6 +1224,9 @@ impl Function for SpeakIntentName {
         let fixity = args.pop_string()?;
         let verbosity = args.pop_string()?;
         let intent_name = args.pop_string()?;
+        if let Some(word) = crate::prefs::PreferenceManager::get().borrow().get_intent_speech_override(&intent_name) {
+            return Ok( Value::String(word) );
+        }
         return Ok( Value::String(crate::infer_intent::intent_speech_for_name(&intent_name, &verbosity, &fixity)) );
     }
 }
@@ -1389,6 +1395,32 @@ impl Function for FontSizeGuess {
     }
 }
 
+struct StackDigit;
+/// StackDigit(node)
+///   'node' is a cell in a row of an mstack/mlongdiv (canonicalization splits numbers into one mn per digit).
+///   Returns the text of 'node'. If it continues a number (the cell to its left is an 'mn'), the text is prefixed with
+///   the concatenation indicator so that the digits are spoken as a number (e.g., "478" and not "4 7 8").
+impl Function for StackDigit {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(1)?;
+        let node = validate_one_node(args.pop_nodeset()?, "StackDigit")?;
+        if let Node::Element(e) = node {
+            let text = get_text_from_element(e);
+            let continues_number = e.preceding_siblings().last()
+                    .and_then(|child| child.element())
+                    .is_some_and(|child| is_tag(child, "mn"));
+            return Ok( Value::String( if continues_number {crate::speech::CONCAT_INDICATOR.to_string() + &text} else {text} ) );
+        } else {
+            return Err( Error::Other("Argument other than a node given to StackDigit".to_string()) );
+        }
+    }
+}
+
 pub struct ReplaceAll;
 /// ReplaceAll(haystack, needle, replacement)
 ///   Returns a string with all occurrences of 'needle' replaced with 'replacement'
@@ -1423,9 +1455,11 @@ pub fn add_builtin_functions(context: &mut Context) {
     context.set_function("IFTHENELSE", IfThenElse);
     context.set_function("DistanceFromLeaf", DistanceFromLeaf);
     context.set_function("EdgeNode", EdgeNode);
+    context.set_function("StackDigit", StackDigit);
     context.set_function("SpeakIntentName", SpeakIntentName);
     context.set_function("GetBracketingIntentName", GetBracketingIntentName);
     context.set_function("GetNavigationPartName", GetNavigationPartName);
+    context.set_function("ChemicalFormula", crate::chemistry::ChemicalFormula);
     context.set_function("DEBUG", Debug);
 
     // Not used: remove??
@@ -217,7 +217,7 @@ fn integral_under() -> Result<()> {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("de", "ClearSpeak", expr, "die integral durch die reellen zahlen von; f von x d x")?;
+    test("de", "ClearSpeak", expr, "das integral über die reellen zahlen, von f von x, d x")?;
     return Ok(());
 
 }
@@ -229,7 +229,7 @@ fn integral() -> Result<()> {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("de", "ClearSpeak", expr, "die integral von f von x d x")?;
+    test("de", "ClearSpeak", expr, "das integral von f von x, d x")?;
     return Ok(());
 
 }
\ No newline at end of file
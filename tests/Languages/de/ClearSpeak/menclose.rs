@@ -6,7 +6,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("de", "ClearSpeak", expr, "versicherungsmathematische symbol, einschließen 3 plus 2 i ende der einschliessung")?;
+    test("de", "ClearSpeak", expr, "versicherungsmathematisch 3 plus 2 i ende versicherungsmathematisch")?;
     return Ok(());
 
 }
@@ -56,7 +56,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("de", "ClearSpeak", expr, "diagonal nach oben, durchstreichen, einschließen 3 hälften ende der einschliessung")?;
+    test("de", "ClearSpeak", expr, "durchgestrichen 3 hälften")?;
     return Ok(());
 
 }
@@ -66,7 +66,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("de", "ClearSpeak", expr, "diagonal nach unten, durchstreichen, einschließen 3 hälften ende der einschliessung")?;
+    test("de", "ClearSpeak", expr, "durchgestrichen 3 hälften")?;
     return Ok(());
 
 }
@@ -76,7 +76,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("de", "ClearSpeak", expr, "x, durchstreichen, einschließen 3 hälften ende der einschliessung")?;
+    test("de", "ClearSpeak", expr, "durchgestrichen 3 hälften")?;
     return Ok(());
 
 }
@@ -166,7 +166,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("de", "ClearSpeak", expr, "phasenwinkel, einschließen 3 hälften ende der einschliessung")?;
+    test("de", "ClearSpeak", expr, "zeiger 3 hälften")?;
     return Ok(());
 
 }
@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("de", "ClearSpeak", expr, "addition; spalte 3, übertrag 1; 424; plus 33; strich; 457; ende addition")?;
+  test("de", "SimpleSpeak", expr, "addition; spalte 3, übertrag 1; 424; plus 33; strich; 457; ende addition")?;
+  test_prefs("de", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "addition; spalte 1; 4, 3, strich; 7; spalte 2; 2, 3, strich; 5; spalte 3; übertrag 1, 4, plus, strich; 4; ende addition")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("de", "ClearSpeak", expr, "subtraktion; spalte 3, streiche 3 und schreibe 2; spalte 2, streiche 2 und schreibe 12; 2327; minus 1156; strich; 1171; ende subtraktion")?;
+  test("de", "SimpleSpeak", expr, "subtraktion; spalte 3, streiche 3 und schreibe 2; spalte 2, streiche 2 und schreibe 12; 2327; minus 1156; strich; 1171; ende subtraktion")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("de", "ClearSpeak", expr, "schriftliche division; 36; geteilt durch 3; ergebnis 12; rechenschritte; 3; strich; 6; 6; strich; 0; ende schriftliche division")?;
+  test("de", "SimpleSpeak", expr, "schriftliche division; 36; geteilt durch 3; ergebnis 12; rechenschritte; 3; strich; 6; 6; strich; 0; ende schriftliche division")?;
+  return Ok(());
+}
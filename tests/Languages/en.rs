@@ -22,13 +22,22 @@ mod SimpleSpeak {
     mod sets;
     mod geometry;
     mod linear_algebra;
+    mod calculus;
     mod multiline;
     mod subscripts;
 }
+
+mod MathSpeak {
+    mod mfrac;
+    mod mroot;
+    mod scripts;
+    mod mtable;
+}
 mod shared;
 mod units;
 mod chemistry;
 mod alphabets;
 mod intent;
 mod mtable;
+mod elementary;
 
@@ -217,7 +217,7 @@ fn integral_under() -> Result<()> {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("en", "ClearSpeak", expr, "the integral over the real numbers of; f of x d x")?;
+    test("en", "ClearSpeak", expr, "the integral over the real numbers, of f of x, d x")?;
     return Ok(());
 
 }
@@ -229,7 +229,7 @@ fn integral() -> Result<()> {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("en", "ClearSpeak", expr, "the integral of f of x d x")?;
+    test("en", "ClearSpeak", expr, "the integral of f of x, d x")?;
     return Ok(());
 
 }
\ No newline at end of file
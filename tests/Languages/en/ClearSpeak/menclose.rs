@@ -6,7 +6,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("en", "ClearSpeak", expr, "actuarial symbol, enclosing 3 plus 2 i end enclosure")?;
+    test("en", "ClearSpeak", expr, "actuarial 3 plus 2 i end actuarial")?;
     return Ok(());
 
 }
@@ -56,7 +56,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("en", "ClearSpeak", expr, "up diagonal, cross out, enclosing 3 halves end enclosure")?;
+    test("en", "ClearSpeak", expr, "cancel 3 halves")?;
     return Ok(());
 
 }
@@ -66,7 +66,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("en", "ClearSpeak", expr, "down diagonal, cross out, enclosing 3 halves end enclosure")?;
+    test("en", "ClearSpeak", expr, "cancel 3 halves")?;
     return Ok(());
 
 }
@@ -76,7 +76,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("en", "ClearSpeak", expr, "x, cross out, enclosing 3 halves end enclosure")?;
+    test("en", "ClearSpeak", expr, "cancel 3 halves")?;
     return Ok(());
 
 }
@@ -166,7 +166,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("en", "ClearSpeak", expr, "phasor angle, enclosing 3 halves end enclosure")?;
+    test("en", "ClearSpeak", expr, "phasor 3 halves")?;
     return Ok(());
 
 }
@@ -231,6 +231,59 @@ fn menclose_radical() -> Result<()> {
 
 }
 
+#[test]
+fn menclose_cancel_in_fraction() -> Result<()> {
+    let expr = "<math>
+                    <mfrac>
+                        <mrow><mn>2</mn><menclose notation='updiagonalstrike'><mn>3</mn></menclose></mrow>
+                        <menclose notation='updiagonalstrike'><mn>3</mn></menclose>
+                    </mfrac>
+                </math>";
+    test("en", "ClearSpeak", expr, "the fraction with numerator; 2 cancel 3; and denominator cancel 3")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_boxed_answer() -> Result<()> {
+    let expr = "<math>
+                    <menclose notation='box'> <mi>x</mi><mo>=</mo><mn>5</mn> </menclose>
+                </math>";
+    test("en", "ClearSpeak", expr, "boxed x is equal to 5 end boxed")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_circled() -> Result<()> {
+    let expr = "<math>
+                    <menclose notation='circle'> <mn>1</mn> </menclose>
+                </math>";
+    test("en", "ClearSpeak", expr, "circled 1")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_madruwb() -> Result<()> {
+    let expr = "<math>
+                    <menclose notation='madruwb'> <mi>n</mi> </menclose>
+                </math>";
+    test("en", "ClearSpeak", expr, "n factorial")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_empty_box() -> Result<()> {
+    let expr = "<math>
+                    <menclose notation='box'> <mtext>&#xA0;</mtext> </menclose>
+                </math>";
+    test("en", "ClearSpeak", expr, "empty box")?;
+    return Ok(());
+
+}
+
 #[test]
 fn simple_speak_menclose_top_bottom() -> Result<()> {
     let expr = "<math>
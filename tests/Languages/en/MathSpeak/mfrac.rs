@@ -0,0 +1,57 @@
+/// Tests for fractions
+///   includes simple fractions and more complex fractions
+///   also tests mixed fractions (implicit and explicit)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn common_fraction() -> Result<()> {
+    let expr = "<math> <mfrac> <mn>3</mn> <mn>4</mn> </mfrac> </math>";
+    test("en", "MathSpeak", expr, "3 fourths")?;
+    return Ok(());
+
+}
+
+#[test]
+fn simple() -> Result<()> {
+    let expr = "<math> <mfrac> <mn>1</mn> <mi>x</mi> </mfrac> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartFraction 1 Over x EndFraction")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartFrac 1 Over x EndFrac")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "Frac 1 Over x EndFrac")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nested() -> Result<()> {
+    let expr = "<math>
+        <mfrac>
+            <mrow><mfrac><mn>1</mn><mi>x</mi></mfrac><mo>+</mo><mn>1</mn></mrow>
+            <mi>y</mi>
+        </mfrac>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+            "StartStartFraction StartFraction 1 Over x EndFraction, plus 1 OverOver y EndEndFraction")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+            "NestFrac Frac 1 Over x EndFrac plus 1 NestOver y NestEndFrac")?;
+    return Ok(());
+
+}
+
+#[test]
+fn binomial() -> Result<()> {
+    let expr = "<math> <mrow><mo>(</mo><mfrac linethickness='0'><mi>n</mi><mi>k</mi></mfrac><mo>)</mo></mrow> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartBinomialOrMatrix n Choose k EndBinomialOrMatrix")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartBinomOrMatrix n Choose k EndBinomOrMatrix")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "BinomOrMatrix n Choose k EndBinomOrMatrix")?;
+    return Ok(());
+
+}
+
+#[test]
+fn zero_linethickness() -> Result<()> {
+    let expr = "<math> <mfrac linethickness='0'><mi>n</mi><mi>k</mi></mfrac> </math>";
+    test("en", "MathSpeak", expr, "StartBinomialOrMatrix n Choose k EndBinomialOrMatrix")?;
+    return Ok(());
+
+}
@@ -0,0 +1,31 @@
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn msqrt() -> Result<()> {
+    let expr = "<math> <msqrt> <mi>x</mi> </msqrt> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartRoot x EndRoot")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "Root x EndRoot")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nested_msqrt() -> Result<()> {
+    let expr = "<math> <msqrt> <mi>x</mi><mo>+</mo><msqrt><mi>y</mi></msqrt> </msqrt> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+            "StartRoot x plus NestedStartRoot y NestedEndRoot EndRoot")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+            "Root x plus NestRoot y NestEndRoot EndRoot")?;
+    return Ok(());
+
+}
+
+#[test]
+fn mroot() -> Result<()> {
+    let expr = "<math> <mroot> <mi>x</mi> <mn>3</mn> </mroot> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "RootIndex 3 StartRoot x EndRoot")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "Index 3 StartRoot x EndRoot")?;
+    return Ok(());
+
+}
@@ -0,0 +1,54 @@
+/// Tests for tables
+///   includes matrices, determinants, and layouts (e.g., systems of equations)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn matrix() -> Result<()> {
+    let expr = "<math>
+        <mrow><mo>(</mo>
+            <mtable>
+                <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+                <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+            </mtable>
+        <mo>)</mo></mrow>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+            "Start 2 By 2 Matrix, 1st Row, 1st Column 1, 2nd Column 2, 2nd Row, 1st Column 3, 2nd Column 4 EndMatrix")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+            "2 By 2 Matrix, 1st Row, 1st Column 1, 2nd Column 2, 2nd Row, 1st Column 3, 2nd Column 4 EndMatrix")?;
+    return Ok(());
+
+}
+
+#[test]
+fn determinant() -> Result<()> {
+    let expr = "<math>
+        <mrow><mo>|</mo>
+            <mtable>
+                <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+                <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+            </mtable>
+        <mo>|</mo></mrow>
+    </math>";
+    test("en", "MathSpeak", expr,
+            "Start 2 By 2 Determinant, 1st Row, 1st Column 1, 2nd Column 2, 2nd Row, 1st Column 3, 2nd Column 4 EndDeterminant")?;
+    return Ok(());
+
+}
+
+#[test]
+fn layout() -> Result<()> {
+    let expr = "<math>
+        <mtable>
+            <mtr><mtd><mi>x</mi></mtd><mtd><mo>=</mo><mn>1</mn></mtd></mtr>
+            <mtr><mtd><mi>y</mi></mtd><mtd><mo>=</mo><mn>2</mn></mtd></mtr>
+        </mtable>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+            "StartLayout, 1st Row x is equal to 1, 2nd Row y is equal to 2 EndLayout")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+            "Layout, 1st Row x is equal to 1, 2nd Row y is equal to 2 EndLayout")?;
+    return Ok(());
+
+}
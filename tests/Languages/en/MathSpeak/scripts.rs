@@ -0,0 +1,125 @@
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn squared() -> Result<()> {
+    let expr = "<math> <msup> <mi>x</mi> <mn>2</mn> </msup> </math>";
+    test("en", "MathSpeak", expr, "x squared")?;
+    return Ok(());
+
+}
+
+#[test]
+fn superscript_baseline() -> Result<()> {
+    let expr = "<math> <msup> <mi>x</mi> <mi>n</mi> </msup> <mo>+</mo> <mn>1</mn> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x Superscript n Baseline, plus 1")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "x Sup n Base plus 1")?;
+    return Ok(());
+
+}
+
+#[test]
+fn superscript_at_end() -> Result<()> {
+    let expr = "<math> <mn>1</mn> <mo>+</mo> <msup> <mi>x</mi> <mi>n</mi> </msup> </math>";
+    test("en", "MathSpeak", expr, "1 plus x Superscript n")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nested_superscripts() -> Result<()> {
+    let expr = "<math>
+        <msup> <mi>x</mi> <mrow><msup><mi>a</mi><mi>b</mi></msup><mo>+</mo><mn>1</mn></mrow> </msup>
+    </math>";
+    test("en", "MathSpeak", expr, "x Superscript eigh Super Super b Superscript, plus 1")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nested_numeric_subscript() -> Result<()> {
+    let expr = "<math> <msup><mi>x</mi><msub><mi>a</mi><mn>2</mn></msub></msup> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x Superscript eigh Super Sub 2 Baseline")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "x Sup eigh Sup Sub 2 Base")?;
+    return Ok(());
+
+}
+
+#[test]
+fn deeply_nested_at_end() -> Result<()> {
+    let expr = "<math> <msup><mi>x</mi><msup><mi>y</mi><msub><mi>a</mi><mn>2</mn></msub></msup></msup> </math>";
+    test("en", "MathSpeak", expr, "x Superscript y Super Super eigh Super Super Sub 2 Baseline")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subscripts() -> Result<()> {
+    let expr = "<math> <msub><mi>x</mi><mi>i</mi></msub> <mo>+</mo> <msub><mi>x</mi><mn>1</mn></msub> </math>";
+    test("en", "MathSpeak", expr, "x Subscript i Baseline, plus x 1")?;
+    return Ok(());
+
+}
+
+#[test]
+fn msubsup() -> Result<()> {
+    let expr = "<math> <msubsup><mi>x</mi><mi>i</mi><mi>n</mi></msubsup> <mo>+</mo> <mn>1</mn> </math>";
+    test("en", "MathSpeak", expr, "x Subscript i Superscript n Baseline, plus 1")?;
+    return Ok(());
+
+}
+
+#[test]
+fn script_in_fraction() -> Result<()> {
+    let expr = "<math> <mfrac><mn>1</mn><msup><mi>x</mi><mi>n</mi></msup></mfrac> </math>";
+    test("en", "MathSpeak", expr, "StartFraction 1 Over x Superscript n Baseline EndFraction")?;
+    return Ok(());
+
+}
+
+#[test]
+fn modified_above() -> Result<()> {
+    let expr = "<math> <mover><mi>x</mi><mo>¯</mo></mover> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "ModifyingAbove x With bar")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "ModAbove x With bar")?;
+    return Ok(());
+
+}
+
+#[test]
+fn sum_under_over() -> Result<()> {
+    let expr = "<math>
+        <munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>
+        <msub><mi>a</mi><mi>i</mi></msub>
+    </math>";
+    test("en", "MathSpeak", expr, "sum Underscript i is equal to 1 Overscript n Endscripts; eigh Subscript i")?;
+    return Ok(());
+
+}
+
+#[test]
+fn sum_scripts() -> Result<()> {
+    let expr = "<math> <msubsup><mo>∑</mo><mi>i</mi><mi>n</mi></msubsup> <msub><mi>a</mi><mi>i</mi></msub> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+            "sum Subscript i Superscript n Baseline, eigh Subscript i")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "sum Sub i Sup n Base, eigh Sub i")?;
+    return Ok(());
+
+}
+
+#[test]
+fn integral_scripts() -> Result<()> {
+    let expr = "<math> <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup> <mi>x</mi><mi>d</mi><mi>x</mi> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "integral Subscript 0 Superscript 1 Baseline x d x")?;
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "integral Sub 0 Sup 1 Base x d x")?;
+    return Ok(());
+
+}
+
+#[test]
+fn integral_under_over() -> Result<()> {
+    let expr = "<math> <munderover><mo>∫</mo><mn>0</mn><mn>1</mn></munderover> <mi>x</mi><mi>d</mi><mi>x</mi> </math>";
+    test("en", "MathSpeak", expr, "integral Underscript 0 Overscript 1 Endscripts x d x")?;
+    return Ok(());
+
+}
@@ -0,0 +1,81 @@
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn derivative() -> Result<()> {
+  let expr = "<math> <mfrac><mrow><mi>d</mi><mi>y</mi></mrow><mrow><mi>d</mi><mi>x</mi></mrow></mfrac> </math>";
+  test("en", "SimpleSpeak", expr, "the derivative of y, with respect to x")?;
+  return Ok(());
+
+}
+
+#[test]
+fn second_derivative() -> Result<()> {
+  let expr = "<math>
+      <mfrac>
+        <mrow><msup><mi>d</mi><mn>2</mn></msup><mi>y</mi></mrow>
+        <mrow><mi>d</mi><msup><mi>x</mi><mn>2</mn></msup></mrow>
+      </mfrac>
+    </math>";
+  test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Terse")],
+      expr, "second derivative of y, with respect to x")?;
+  test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Medium")],
+      expr, "the second derivative of y, with respect to x")?;
+  return Ok(());
+
+}
+
+#[test]
+fn partial_derivative() -> Result<()> {
+  let expr = "<math>
+      <mfrac>
+        <mrow><msup><mo>∂</mo><mn>2</mn></msup><mi>f</mi></mrow>
+        <mrow><mo>∂</mo><mi>x</mi><mo>∂</mo><mi>y</mi></mrow>
+      </mfrac>
+    </math>";
+  test("en", "SimpleSpeak", expr, "the second partial derivative of f, with respect to x and y")?;
+  return Ok(());
+
+}
+
+#[test]
+fn derivative_operator_nth() -> Result<()> {
+  let expr = "<math>
+      <mfrac><msup><mi>d</mi><mi>n</mi></msup><mrow><mi>d</mi><msup><mi>x</mi><mi>n</mi></msup></mrow></mfrac>
+      <mi>y</mi>
+    </math>";
+  test("en", "SimpleSpeak", expr, "the n-th derivative of y, with respect to x")?;
+  return Ok(());
+
+}
+
+#[test]
+fn derivative_prime() -> Result<()> {
+  let expr = "<math> <msup><mi>f</mi><mo>″</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "f double prime, of x")?;
+  return Ok(());
+
+}
+
+#[test]
+fn derivative_dot() -> Result<()> {
+  let expr = "<math> <mover><mi>x</mi><mo>¨</mo></mover> </math>";
+  test("en", "SimpleSpeak", expr, "x double dot")?;
+  return Ok(());
+
+}
+
+#[test]
+fn integral_from_to() -> Result<()> {
+  let expr = "<math>
+      <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>
+      <mrow><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo></mrow>
+      <mi>d</mi><mi>x</mi>
+    </math>";
+  test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Medium")],
+      expr, "the integral from 0, to 1, of f of x, d x")?;
+  test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Verbose")],
+      expr, "the integral from 0, to 1, of f of x, with respect to x")?;
+  return Ok(());
+
+}
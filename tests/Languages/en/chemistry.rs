@@ -767,3 +767,76 @@ fn dropped_msubsup_bug_358() -> Result<()> {
 }
 
 
+
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr, "water")?;
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "water")?;
+  return Ok(());
+
+}
+
+#[test]
+fn salt_as_compound() -> Result<()> {
+  let expr = "<math><mi>Na</mi><mi>Cl</mi></math>";
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "sodium chloride")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr, "sulfate ion")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ammonium_as_compound() -> Result<()> {
+  let expr = "<math><mi>N</mi><msubsup><mi>H</mi><mn>4</mn><mo>+</mo></msubsup></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr, "ammonium ion")?;
+  return Ok(());
+
+}
+
+#[test]
+fn aluminum_sulfate_as_compound() -> Result<()> {
+  let expr = "<math><mrow><msub><mi>Al</mi><mn>2</mn></msub>
+          <msub><mrow><mo>(</mo><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub><mo>)</mo></mrow><mn>3</mn></msub></mrow></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr, "aluminum sulfate")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr,
+      "2 molecules of hydrogen, plus oxygen; reacts to form, 2 molecules of water")?;
+  return Ok(());
+
+}
+
+#[test]
+fn unknown_compound_is_spelled_out() -> Result<()> {
+  let expr = "<math><mi>Cl</mi><msub><mi>O</mi><mn>2</mn></msub></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound"), ("Verbosity", "Terse")], expr, "cap c l, cap o, 2")?;
+  return Ok(());
+
+}
+
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("en", "ClearSpeak", vec![("Chemistry", "AsCompound")], expr,
+      "2 sodium ions plus sulfate ion; reacts to form sodium sulfate")?;
+  return Ok(());
+
+}
+
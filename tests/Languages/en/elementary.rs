@@ -0,0 +1,86 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("en", "ClearSpeak", expr, "addition; column 3, carry 1; 424; plus 33; line; 457; end addition")?;
+  test("en", "SimpleSpeak", expr, "addition; column 3, carry 1; 424; plus 33; line; 457; end addition")?;
+  test_prefs("en", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "addition; column 1; 4, 3, line; 7; column 2; 2, 3, line; 5; column 3; carry 1, 4, plus, line; 4; end addition")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2,327</mn>
+        <msrow><mo>-</mo><mn>1,156</mn></msrow>
+        <msline/>
+        <mn>1,171</mn>
+      </mstack>
+    </math>";
+  test("en", "ClearSpeak", expr,
+      "subtraction; column 3, cross out 3 and write 2; column 2, cross out 2 and write 12; 2,327; minus 1,156; line; 1,171; end subtraction")?;
+  test_prefs("en", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "subtraction; column 1; 7, 6, line; 1; column 2; cross out 2 and write 12, 2, 5, line; 7; \
+       column 3; cross out 3 and write 2, 3, 1, line; 1; column 4; 2, 1, line; 1; column 5; minus, line; end subtraction")?;
+  return Ok(());
+}
+
+#[test]
+fn multiplication() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mn>123</mn>
+        <msrow><mo>×</mo><mn>45</mn></msrow>
+        <msline/>
+        <mn>615</mn>
+        <msrow><mn>492</mn><none/></msrow>
+        <msline/>
+        <mn>5535</mn>
+      </mstack>
+    </math>";
+  test("en", "SimpleSpeak", expr, "multiplication; 123; times 45; line; 615; 492; line; 5535; end multiplication")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  // example from the MathML spec
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>435.3</mn>
+        <mn>1306</mn>
+        <msgroup position='2' shift='-1'>
+          <msgroup><mn>12</mn><msline length='2'/></msgroup>
+          <msgroup><mn>10</mn><mn>9</mn><msline length='2'/></msgroup>
+          <msgroup><mn>16</mn><mn>15</mn><msline length='2'/><mn>1.0</mn></msgroup>
+          <msgroup position='-1'><mn>9</mn><msline length='3'/><mn>1</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("en", "ClearSpeak", expr,
+      "long division; 1306; divided by 3; result 435.3; steps; 12; line; 10; 9; line; 16; 15; line; 1.0; 9; line; 1; end long division")?;
+  return Ok(());
+}
+
+#[test]
+fn empty_stacks() -> Result<()> {
+  test("en", "ClearSpeak", "<math><mstack><mscarries/></mstack></math>", "")?;
+  test("en", "ClearSpeak", "<math><mstack/></math>", "")?;
+  test("en", "ClearSpeak", "<math><mstack><mscarries/><mn>12</mn></mstack></math>", "stacked rows; 12; end stacked rows")?;
+  return Ok(());
+}
@@ -16,4 +16,5 @@ mod shared;
 mod chemistry;
 mod alphabets;
 mod intent;
+mod elementary;
 
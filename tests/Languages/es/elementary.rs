@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("es", "ClearSpeak", expr, "suma; columna 3, llevar 1; 424; más 33; línea; 457; fin de suma")?;
+  test("es", "SimpleSpeak", expr, "suma; columna 3, llevar 1; 424; más 33; línea; 457; fin de suma")?;
+  test_prefs("es", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "suma; columna 1; 4, 3, línea; 7; columna 2; 2, 3, línea; 5; columna 3; llevar 1, 4, más, línea; 4; fin de suma")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("es", "ClearSpeak", expr, "resta; columna 3, tachar 3 y escribir 2; columna 2, tachar 2 y escribir 12; 2327; menos 1156; línea; 1171; fin de resta")?;
+  test("es", "SimpleSpeak", expr, "resta; columna 3, tachar 3 y escribir 2; columna 2, tachar 2 y escribir 12; 2327; menos 1156; línea; 1171; fin de resta")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("es", "ClearSpeak", expr, "división larga; 36; dividido entre 3; resultado 12; pasos; 3; línea; 6; 6; línea; 0; fin de división larga")?;
+  test("es", "SimpleSpeak", expr, "división larga; 36; dividido entre 3; resultado 12; pasos; 3; línea; 6; 6; línea; 0; fin de división larga")?;
+  return Ok(());
+}
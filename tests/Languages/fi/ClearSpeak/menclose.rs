@@ -6,7 +6,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("fi", "ClearSpeak", expr, "kirjanpitomerkki, rajaus 3 plus 2 i loppu rajaus")?;
+    test("fi", "ClearSpeak", expr, "aktuaari 3 plus 2 i loppu aktuaari")?;
     return Ok(());
 
 }
@@ -56,7 +56,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("fi", "ClearSpeak", expr, "ylös viistoon, yliviivaus, rajaus 3 kahdesosaa loppu rajaus")?;
+    test("fi", "ClearSpeak", expr, "yliviivattu 3 kahdesosaa")?;
     return Ok(());
 
 }
@@ -66,7 +66,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("fi", "ClearSpeak", expr, "alas viistoon, yliviivaus, rajaus 3 kahdesosaa loppu rajaus")?;
+    test("fi", "ClearSpeak", expr, "yliviivattu 3 kahdesosaa")?;
     return Ok(());
 
 }
@@ -76,7 +76,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("fi", "ClearSpeak", expr, "x, yliviivaus, rajaus 3 kahdesosaa loppu rajaus")?;
+    test("fi", "ClearSpeak", expr, "yliviivattu 3 kahdesosaa")?;
     return Ok(());
 
 }
@@ -166,7 +166,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("fi", "ClearSpeak", expr, "vaihekulma, rajaus 3 kahdesosaa loppu rajaus")?;
+    test("fi", "ClearSpeak", expr, "osoitin 3 kahdesosaa")?;
     return Ok(());
 
 }
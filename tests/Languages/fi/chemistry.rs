@@ -687,3 +687,37 @@ fn mhchem_roman_in_superscript() -> Result<()> {
 }
 
 
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("fi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "vesi")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("fi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "sulfaatti ioni")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("fi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 vety molekyyliä plus happi, reagoi 2 vesi molekyyliä")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("fi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 natrium ionia plus sulfaatti ioni; reagoi natriumsulfaatti")?;
+  return Ok(());
+
+}
@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("fi", "ClearSpeak", expr, "yhteenlasku; sarake 3, muistinumero 1; 424; plus 33; viiva; 457; yhteenlasku päättyy")?;
+  test("fi", "SimpleSpeak", expr, "yhteenlasku; sarake 3, muistinumero 1; 424; plus 33; viiva; 457; yhteenlasku päättyy")?;
+  test_prefs("fi", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "yhteenlasku; sarake 1; 4, 3, viiva; 7; sarake 2; 2, 3, viiva; 5; sarake 3; muistinumero 1, 4, plus, viiva; 4; yhteenlasku päättyy")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("fi", "ClearSpeak", expr, "vähennyslasku; sarake 3, yliviivaa 3 ja kirjoita 2; sarake 2, yliviivaa 2 ja kirjoita 12; 2327; miinus 1156; viiva; 1171; vähennyslasku päättyy")?;
+  test("fi", "SimpleSpeak", expr, "vähennyslasku; sarake 3, yliviivaa 3 ja kirjoita 2; sarake 2, yliviivaa 2 ja kirjoita 12; 2327; miinus 1156; viiva; 1171; vähennyslasku päättyy")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("fi", "ClearSpeak", expr, "jakokulma; 36; jaettuna 3; tulos 12; välivaiheet; 3; viiva; 6; 6; viiva; 0; jakokulma päättyy")?;
+  test("fi", "SimpleSpeak", expr, "jakokulma; 36; jaettuna 3; tulos 12; välivaiheet; 3; viiva; 6; 6; viiva; 0; jakokulma päättyy")?;
+  return Ok(());
+}
@@ -0,0 +1,38 @@
+/// Tests for the chemistry rules
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "air")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "ion sulfat")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 molekul hidrogen tambah oksigen; hasil 2 molekul air")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 ion natrium tambah ion sulfat, hasil natrium sulfat")?;
+  return Ok(());
+
+}
@@ -182,3 +182,113 @@ fn curl_vector() -> Result<()> {
     return Ok(());
 
 }
+
+#[test]
+fn leibniz_derivative() -> Result<()> {
+  let mathml = r#"<math>
+        <mfrac>
+            <mrow><mi>d</mi><mi>y</mi></mrow>
+            <mrow><mi>d</mi><mi>x</mi></mrow>
+        </mfrac>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+        <derivative data-from-mathml='mfrac'>
+            <mn>1</mn>
+            <mi data-from-mathml='mi'>y</mi>
+            <mi data-from-mathml='mi'>x</mi>
+        </derivative>
+    </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn leibniz_partial_derivative() -> Result<()> {
+  let mathml = r#"<math>
+        <mfrac>
+            <mrow><msup><mo>∂</mo><mn>2</mn></msup><mi>f</mi></mrow>
+            <mrow><mo>∂</mo><mi>x</mi><mo>∂</mo><mi>y</mi></mrow>
+        </mfrac>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+        <partial-derivative data-from-mathml='mfrac'>
+            <mn data-from-mathml='mn'>2</mn>
+            <mi data-from-mathml='mi'>f</mi>
+            <mi data-from-mathml='mi'>x</mi>
+            <mi data-from-mathml='mi'>y</mi>
+        </partial-derivative>
+    </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn lagrange_derivative() -> Result<()> {
+  let mathml = r#"<math>
+        <msup><mi>f</mi><mo>″</mo></msup>
+        <mo>(</mo><mi>x</mi><mo>)</mo>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+        <mrow data-from-mathml='mrow' data-changed='added'>
+            <derivative data-from-mathml='msup' data-intent-property=':prime:'>
+                <mn>2</mn>
+                <mi data-from-mathml='mi'>f</mi>
+            </derivative>
+            <mo data-from-mathml='mo' data-changed='added'>&#x2061;</mo>
+            <mrow data-from-mathml='mrow' data-changed='added'>
+                <mo data-from-mathml='mo'>(</mo>
+                <mi data-from-mathml='mi'>x</mi>
+                <mo data-from-mathml='mo'>)</mo>
+            </mrow>
+        </mrow>
+    </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn newton_derivative() -> Result<()> {
+  let mathml = r#"<math>
+        <mover><mi>x</mi><mo>˙</mo></mover>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+        <derivative data-from-mathml='mover' data-intent-property=':dot:'>
+            <mn>1</mn>
+            <mi data-from-mathml='mi'>x</mi>
+        </derivative>
+    </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn integral_with_limits() -> Result<()> {
+  let mathml = r#"<math>
+        <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>
+        <mrow><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo></mrow>
+        <mi>d</mi><mi>x</mi>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+        <integral data-from-mathml='mrow' data-changed='added'>
+            <mn data-from-mathml='mn'>0</mn>
+            <mn data-from-mathml='mn'>1</mn>
+            <mrow data-from-mathml='mrow'>
+                <mi data-from-mathml='mi'>f</mi>
+                <mo data-from-mathml='mo' data-changed='added'>&#x2061;</mo>
+                <mrow data-from-mathml='mrow' data-changed='added'>
+                    <mo data-from-mathml='mo'>(</mo>
+                    <mi data-from-mathml='mi'>x</mi>
+                    <mo data-from-mathml='mo'>)</mo>
+                </mrow>
+            </mrow>
+            <mi data-from-mathml='mi'>x</mi>
+        </integral>
+    </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
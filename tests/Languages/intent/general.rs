@@ -114,3 +114,97 @@ fn evaluated_at() -> Result<()> {
     return Ok(());
 
 }
+
+#[test]
+fn menclose_cancel() -> Result<()> {
+  let mathml = r#"<math>
+        <mfrac>
+          <mrow><mn>2</mn><menclose notation="updiagonalstrike"><mn>3</mn></menclose></mrow>
+          <menclose notation="downdiagonalstrike updiagonalstrike"><mn>3</mn></menclose>
+        </mfrac>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+      <fraction data-from-mathml='mfrac'>
+        <mrow data-from-mathml='mrow'>
+          <mn data-from-mathml='mn'>2</mn>
+          <mo data-from-mathml='mo' data-changed='added'>&#x2062;</mo>
+          <cancel data-from-mathml='menclose' notation='updiagonalstrike' data-intent-property=':prefix:'>
+            <mn data-from-mathml='mn'>3</mn>
+          </cancel>
+        </mrow>
+        <cancel data-from-mathml='menclose' notation='downdiagonalstrike updiagonalstrike' data-intent-property=':prefix:'>
+          <mn data-from-mathml='mn'>3</mn>
+        </cancel>
+      </fraction>
+   </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_boxed() -> Result<()> {
+  let mathml = r#"<math><mi>x</mi><mo>=</mo><menclose notation="box"><mn>5</mn></menclose></math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+      <mrow data-from-mathml='mrow' data-changed='added'>
+        <mi data-from-mathml='mi'>x</mi>
+        <mo data-from-mathml='mo'>=</mo>
+        <boxed data-from-mathml='menclose' notation='box' data-intent-property=':prefix:'>
+          <mn data-from-mathml='mn'>5</mn>
+        </boxed>
+      </mrow>
+   </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_empty_box() -> Result<()> {
+  // an empty box is a placeholder, not a boxed answer
+  let mathml = r#"<math><menclose notation="box"><mtext>&#xA0;</mtext></menclose></math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+      <menclose data-from-mathml='menclose' notation='box'>
+        <mtext data-from-mathml='mtext' data-width='0.700' data-empty-in-2D='true'>&#xA0;</mtext>
+      </menclose>
+   </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_circled_and_phasor() -> Result<()> {
+  let mathml = r#"<math>
+        <menclose notation="circle"><mn>1</mn></menclose>
+        <mo>,</mo>
+        <menclose notation="phasorangle"><mi>θ</mi></menclose>
+    </math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+      <mrow data-from-mathml='mrow' data-changed='added'>
+        <circled data-from-mathml='menclose' notation='circle' data-intent-property=':prefix:'>
+          <mn data-from-mathml='mn'>1</mn>
+        </circled>
+        <mo data-from-mathml='mo'>,</mo>
+        <phasor data-from-mathml='menclose' notation='phasorangle' data-intent-property=':prefix:'>
+          <mi data-from-mathml='mi'>θ</mi>
+        </phasor>
+      </mrow>
+   </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_madruwb() -> Result<()> {
+  let mathml = r#"<math><menclose notation="madruwb"><mi>n</mi></menclose></math>"#;
+  let intent = r#"<math data-from-mathml='math'>
+      <factorial data-from-mathml='menclose' notation='madruwb' data-intent-property=':postfix:'>
+        <mi data-from-mathml='mi'>n</mi>
+      </factorial>
+   </math>"#;
+    test_intent(mathml, intent, vec![])?;
+    return Ok(());
+
+}
@@ -6,7 +6,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("nb", "ClearSpeak", expr, "aktuarielt symbol, omslutter 3 pluss 2 i, slutt omslutning")?;
+    test("nb", "ClearSpeak", expr, "aktuariell 3 pluss 2 i slutt aktuariell")?;
     return Ok(());
 
 }
@@ -56,7 +56,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("nb", "ClearSpeak", expr, "diagonalt oppover, utstryking, omslutter 3 halve, slutt omslutning")?;
+    test("nb", "ClearSpeak", expr, "strøket 3 halve")?;
     return Ok(());
 
 }
@@ -66,7 +66,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("nb", "ClearSpeak", expr, "diagonalt nedover, utstryking, omslutter 3 halve, slutt omslutning")?;
+    test("nb", "ClearSpeak", expr, "strøket 3 halve")?;
     return Ok(());
 
 }
@@ -76,7 +76,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("nb", "ClearSpeak", expr, "kryss, utstryking, omslutter 3 halve, slutt omslutning")?;
+    test("nb", "ClearSpeak", expr, "strøket 3 halve")?;
     return Ok(());
 
 }
@@ -166,7 +166,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("nb", "ClearSpeak", expr, "fasevinkel, omslutter 3 halve, slutt omslutning")?;
+    test("nb", "ClearSpeak", expr, "viser 3 halve")?;
     return Ok(());
 
 }
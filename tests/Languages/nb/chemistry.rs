@@ -767,3 +767,37 @@ fn dropped_msubsup_bug_358() -> Result<()> {
 }
 
 
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("nb", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "vann")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("nb", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "sulfat ion")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("nb", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 molekyler hydrogen pluss oksygen; reaksjon danner 2 molekyler vann")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("nb", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 natrium ioner pluss sulfat ion; reaksjon danner natriumsulfat")?;
+  return Ok(());
+
+}
@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("nb", "ClearSpeak", expr, "addisjon; kolonne 3, mente 1; 424; pluss 33; strek; 457; slutt addisjon")?;
+  test("nb", "SimpleSpeak", expr, "addisjon; kolonne 3, mente 1; 424; pluss 33; strek; 457; slutt addisjon")?;
+  test_prefs("nb", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "addisjon; kolonne 1; 4, 3, strek; 7; kolonne 2; 2, 3, strek; 5; kolonne 3; mente 1, 4, pluss, strek; 4; slutt addisjon")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("nb", "ClearSpeak", expr, "subtraksjon; kolonne 3, stryk 3 og skriv 2; kolonne 2, stryk 2 og skriv 12; 2327; minus 1156; strek; 1171; slutt subtraksjon")?;
+  test("nb", "SimpleSpeak", expr, "subtraksjon; kolonne 3, stryk 3 og skriv 2; kolonne 2, stryk 2 og skriv 12; 2327; minus 1156; strek; 1171; slutt subtraksjon")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("nb", "ClearSpeak", expr, "oppstilt divisjon; 36; delt på 3; resultat 12; utregning; 3; strek; 6; 6; strek; 0; slutt oppstilt divisjon")?;
+  test("nb", "SimpleSpeak", expr, "oppstilt divisjon; 36; delt på 3; resultat 12; utregning; 3; strek; 6; 6; strek; 0; slutt oppstilt divisjon")?;
+  return Ok(());
+}
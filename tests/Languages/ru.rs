@@ -31,4 +31,5 @@ mod units;
 mod chemistry;
 mod alphabets;
 mod intent;
-mod mtable;
\ No newline at end of file
+mod mtable;
+mod elementary;
\ No newline at end of file
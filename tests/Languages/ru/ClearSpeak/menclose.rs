@@ -7,7 +7,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("ru", "ClearSpeak", expr, "актуарный символ, содержащее 3 плюс 2 и конец")?;
+    test("ru", "ClearSpeak", expr, "актуарный 3 плюс 2 и конец актуарного")?;
     return Ok(());
 }
 
@@ -52,7 +52,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("ru", "ClearSpeak", expr, "диагональ вверх, перечёркивание, содержащее три вторых конец")?;
+    test("ru", "ClearSpeak", expr, "зачёркнуто три вторых")?;
     return Ok(());
 }
 
@@ -61,7 +61,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("ru", "ClearSpeak", expr, "диагональ вниз, перечёркивание, содержащее три вторых конец")?;
+    test("ru", "ClearSpeak", expr, "зачёркнуто три вторых")?;
     return Ok(());
 }
 
@@ -70,7 +70,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("ru", "ClearSpeak", expr, "икс, перечёркивание, содержащее три вторых конец")?;
+    test("ru", "ClearSpeak", expr, "зачёркнуто три вторых")?;
     return Ok(());
 }
 
@@ -151,7 +151,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("ru", "ClearSpeak", expr, "угол фазы, содержащее три вторых конец")?;
+    test("ru", "ClearSpeak", expr, "фазор три вторых")?;
     return Ok(());
 }
 
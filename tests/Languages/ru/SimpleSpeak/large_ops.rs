@@ -204,7 +204,7 @@ fn integral_under() -> Result<()> {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("ru", "SimpleSpeak", expr, "интеграл по действительным числам от; эф от икс д икс")?;
+    test("ru", "SimpleSpeak", expr, "интеграл по действительным числам, от эф от икс, д икс")?;
     return Ok(());
 }
 
@@ -215,6 +215,6 @@ fn integral() -> Result<()> {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("ru", "SimpleSpeak", expr, "интеграл от эф от икс д икс")?;
+    test("ru", "SimpleSpeak", expr, "интеграл от эф от икс, д икс")?;
     return Ok(());
 }
@@ -739,3 +739,37 @@ fn dropped_msubsup_bug_358() -> Result<()> {
 }
 
 
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("ru", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "вода")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("ru", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "сульфат ион")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("ru", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "водород, 2 молекулы плюс кислород реагирует с образованием вода, 2 молекулы")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("ru", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 натрий иона плюс сульфат ион реагирует с образованием сульфат натрия")?;
+  return Ok(());
+
+}
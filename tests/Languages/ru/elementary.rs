@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("ru", "ClearSpeak", expr, "сложение в столбик; разряд 3, перенос 1; 424; плюс 33; черта; 457; конец сложения")?;
+  test("ru", "SimpleSpeak", expr, "сложение в столбик; разряд 3, перенос 1; 424; плюс 33; черта; 457; конец сложения")?;
+  test_prefs("ru", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "сложение в столбик; разряд 1; 4, 3, черта; 7; разряд 2; 2, 3, черта; 5; разряд 3; перенос 1, 4, плюс, черта; 4; конец сложения")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("ru", "ClearSpeak", expr, "вычитание в столбик; разряд 3, зачеркнуть 3 и написать 2; разряд 2, зачеркнуть 2 и написать 12; 2327; минус 1156; черта; 1171; конец вычитания")?;
+  test("ru", "SimpleSpeak", expr, "вычитание в столбик; разряд 3, зачеркнуть 3 и написать 2; разряд 2, зачеркнуть 2 и написать 12; 2327; минус 1156; черта; 1171; конец вычитания")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("ru", "ClearSpeak", expr, "деление уголком; 36; разделить на 3; частное 12; шаги; 3; черта; 6; 6; черта; 0; конец деления")?;
+  test("ru", "SimpleSpeak", expr, "деление уголком; 36; разделить на 3; частное 12; шаги; 3; черта; 6; 6; черта; 0; конец деления")?;
+  return Ok(());
+}
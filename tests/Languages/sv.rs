@@ -30,4 +30,5 @@ mod alphabets;
 mod intent;
 mod mtable;
 mod units;
+mod elementary;
 
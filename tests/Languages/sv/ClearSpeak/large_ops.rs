@@ -217,7 +217,7 @@ fn integral_under() -> Result<()> {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("sv", "ClearSpeak", expr, "integralen över de reella talen, av; f av x d x")?;
+    test("sv", "ClearSpeak", expr, "integralen över de reella talen, av f av x, d x")?;
     return Ok(());
 
 }
@@ -229,7 +229,7 @@ fn integral() -> Result<()> {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("sv", "ClearSpeak", expr, "integralen av f av x d x")?;
+    test("sv", "ClearSpeak", expr, "integralen av f av x, d x")?;
     return Ok(());
 
 }
\ No newline at end of file
@@ -6,7 +6,7 @@ fn menclose_actuarial() -> Result<()> {
     let expr = "<math>
                     <menclose notation='actuarial'>  <mn>3</mn><mo>+</mo><mn>2</mn><mi>i</mi> </menclose>
                 </math>";
-    test("sv", "ClearSpeak", expr, "aktuariell symbol, omsluter 3 plus 2 i, slut omslutning")?;
+    test("sv", "ClearSpeak", expr, "aktuariell 3 plus 2 i slut aktuariell")?;
     return Ok(());
 
 }
@@ -56,7 +56,7 @@ fn menclose_updiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("sv", "ClearSpeak", expr, "diagonal uppåt, överstrykning, omsluter 3 halva, slut omslutning")?;
+    test("sv", "ClearSpeak", expr, "överstruket 3 halva")?;
     return Ok(());
 
 }
@@ -66,7 +66,7 @@ fn menclose_downdiagonalstrike() -> Result<()> {
     let expr = "<math>
                     <menclose notation='downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("sv", "ClearSpeak", expr, "diagonal nedåt, överstrykning, omsluter 3 halva, slut omslutning")?;
+    test("sv", "ClearSpeak", expr, "överstruket 3 halva")?;
     return Ok(());
 
 }
@@ -76,7 +76,7 @@ fn menclose_cross_out() -> Result<()> {
     let expr = "<math>
                     <menclose notation='updiagonalstrike downdiagonalstrike'>  <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("sv", "ClearSpeak", expr, "kryss, överstrykning, omsluter 3 halva, slut omslutning")?;
+    test("sv", "ClearSpeak", expr, "överstruket 3 halva")?;
     return Ok(());
 
 }
@@ -166,7 +166,7 @@ fn menclose_phasorangle() -> Result<()> {
     let expr = "<math>
                     <menclose notation='phasorangle'> <mfrac><mn>3</mn><mn>2</mn></mfrac> </menclose>
                 </math>";
-    test("sv", "ClearSpeak", expr, "fasorvinkel, omsluter 3 halva, slut omslutning")?;
+    test("sv", "ClearSpeak", expr, "visare 3 halva")?;
     return Ok(());
 
 }
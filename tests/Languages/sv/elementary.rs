@@ -0,0 +1,55 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("sv", "ClearSpeak", expr, "addition; kolumn 3, minnessiffra 1; 424; plus 33; streck; 457; slut addition")?;
+  test("sv", "SimpleSpeak", expr, "addition; kolumn 3, minnessiffra 1; 424; plus 33; streck; 457; slut addition")?;
+  test_prefs("sv", "ClearSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "addition; kolumn 1; 4, 3, streck; 7; kolumn 2; 2, 3, streck; 5; kolumn 3; minnessiffra 1, 4, plus, streck; 4; slut addition")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("sv", "ClearSpeak", expr, "subtraktion; kolumn 3, stryk 3 och skriv 2; kolumn 2, stryk 2 och skriv 12; 2327; minus 1156; streck; 1171; slut subtraktion")?;
+  test("sv", "SimpleSpeak", expr, "subtraktion; kolumn 3, stryk 3 och skriv 2; kolumn 2, stryk 2 och skriv 12; 2327; minus 1156; streck; 1171; slut subtraktion")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("sv", "ClearSpeak", expr, "liggande stolen; 36; delat med 3; resultat 12; uträkning; 3; streck; 6; 6; streck; 0; slut liggande stolen")?;
+  test("sv", "SimpleSpeak", expr, "liggande stolen; 36; delat med 3; resultat 12; uträkning; 3; streck; 6; 6; streck; 0; slut liggande stolen")?;
+  return Ok(());
+}
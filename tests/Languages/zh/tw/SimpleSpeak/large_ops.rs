@@ -217,7 +217,7 @@ fn integral_under() -> Result<()> {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("zh-tw", "SimpleSpeak", expr, "積分 下層 實數集 項目; f x d x")?;
+    test("zh-tw", "SimpleSpeak", expr, "積分 下層 實數集 項目, f x, d x")?;
     return Ok(());
 
 }
@@ -229,7 +229,7 @@ fn integral() -> Result<()> {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("zh-tw", "SimpleSpeak", expr, "積分 項目 f x d x")?;
+    test("zh-tw", "SimpleSpeak", expr, "積分 項目, f x, d x")?;
     return Ok(());
 
 }
\ No newline at end of file
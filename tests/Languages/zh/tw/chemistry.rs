@@ -690,3 +690,37 @@ fn mhchem_roman_in_superscript() -> Result<()> {
 }
 
 
+#[test]
+fn water_as_compound() -> Result<()> {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("zh-tw", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "水")?;
+  return Ok(());
+
+}
+
+#[test]
+fn sulfate_as_compound() -> Result<()> {
+  let expr = "<math><msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup></math>";
+  test_prefs("zh-tw", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "硫酸根 離子")?;
+  return Ok(());
+
+}
+
+#[test]
+fn equation_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mo>+</mo><msub><mi>O</mi><mn>2</mn></msub>
+          <mo>&#x2192;</mo><mn>2</mn><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("zh-tw", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 個 氫氣 分子 加 氧氣; 反應形成 2 個 水 分子")?;
+  return Ok(());
+
+}
+
+#[test]
+fn ions_with_coefficient_as_compound() -> Result<()> {
+  let expr = "<math><mn>2</mn><msup><mi>Na</mi><mo>+</mo></msup><mo>+</mo>
+          <msup><mrow><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></mrow><mrow><mn>2</mn><mo>&#x2212;</mo></mrow></msup>
+          <mo>&#x2192;</mo><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+  test_prefs("zh-tw", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "2 個 鈉 離子 加 硫酸根 離子; 反應形成 硫酸鈉")?;
+  return Ok(());
+
+}
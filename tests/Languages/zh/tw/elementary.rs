@@ -0,0 +1,52 @@
+/// Tests for elementary math layouts (mstack and mlongdiv)
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn addition_with_carry() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries><mn>1</mn><none/><none/></mscarries>
+        <mn>424</mn>
+        <msrow><mo>+</mo><mn>33</mn></msrow>
+        <msline/>
+        <mn>457</mn>
+      </mstack>
+    </math>";
+  test("zh-tw", "SimpleSpeak", expr, "直式加法; 位 3, 進位 1; 424; 加 33; 橫線; 457; 加法結束")?;
+  test_prefs("zh-tw", "SimpleSpeak", vec![("ElementaryMath", "Columns")], expr,
+      "直式加法; 位 1; 4, 3, 橫線; 7; 位 2; 2, 3, 橫線; 5; 位 3; 進位 1, 4, 加, 橫線; 4; 加法結束")?;
+  return Ok(());
+}
+
+#[test]
+fn subtraction_with_crossout() -> Result<()> {
+  let expr = "<math>
+      <mstack>
+        <mscarries crossout='updiagonalstrike'><mn>2</mn><mn>12</mn><mscarry crossout='none'><none/></mscarry></mscarries>
+        <mn>2327</mn>
+        <msrow><mo>-</mo><mn>1156</mn></msrow>
+        <msline/>
+        <mn>1171</mn>
+      </mstack>
+    </math>";
+  test("zh-tw", "SimpleSpeak", expr, "直式減法; 位 3, 劃掉 3 改寫 2; 位 2, 劃掉 2 改寫 12; 2327; 減 1156; 橫線; 1171; 減法結束")?;
+  return Ok(());
+}
+
+#[test]
+fn long_division() -> Result<()> {
+  let expr = "<math>
+      <mlongdiv longdivstyle='lefttop'>
+        <mn>3</mn>
+        <mn>12</mn>
+        <mn>36</mn>
+        <msgroup>
+          <msgroup><mn>3</mn><msline length='1'/></msgroup>
+          <msgroup position='-1'><mn>6</mn><mn>6</mn><msline length='1'/><mn>0</mn></msgroup>
+        </msgroup>
+      </mlongdiv>
+    </math>";
+  test("zh-tw", "SimpleSpeak", expr, "長除法; 36; 除以 3; 商 12; 步驟; 3; 橫線; 6; 6; 橫線; 0; 長除法結束")?;
+  return Ok(());
+}
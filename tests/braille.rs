@@ -1,4 +1,5 @@
 #![allow(non_snake_case)]
+#![allow(clippy::needless_return)]
 
 mod common;
 
@@ -24,6 +25,14 @@ mod braille {
         mod vi;
     }
 
+    mod Finnish {
+        mod finnish;
+    }
+
+    mod Marburg {
+        mod marburg;
+    }
+
     mod LaTeX {
         mod augenbit;
         mod other;
@@ -0,0 +1,477 @@
+// Finnish braille tests
+// The expressions are the Finnish examples from the table of math braille codes created by Georgios Kouroupetroglou
+//   (PythonScripts/BrailleMathCodes Repository.csv); the expected braille is built from the Finnish symbols listed there.
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn regular_plus() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>+</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠖⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn regular_minus() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>-</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠤⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn equals() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>=</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn parentheses() -> Result<()> {
+    let expr = r#"<math><mrow><mo>(</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>)</mo></mrow></math>"#;
+    test_braille("Finnish", expr, "⠦⠭⠖⠽⠴")?;
+    return Ok(());
+
+}
+
+#[test]
+fn square_brackets() -> Result<()> {
+    let expr = r#"<math><mrow><mo>[</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>]</mo></mrow></math>"#;
+    test_braille("Finnish", expr, "⠷⠭⠖⠽⠾")?;
+    return Ok(());
+
+}
+
+#[test]
+fn curly_braces() -> Result<()> {
+    let expr = r#"<math><mrow><mo>{</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>}</mo></mrow></math>"#;
+    test_braille("Finnish", expr, "⠫⠭⠖⠽⠻")?;
+    return Ok(());
+
+}
+
+#[test]
+fn vertical_bars() -> Result<()> {
+    let expr = r#"<math><mrow><mo>|</mo><mi>x</mi><mo>|</mo></mrow></math>"#;
+    test_braille("Finnish", expr, "⠸⠭⠸")?;
+    return Ok(());
+
+}
+
+#[test]
+fn decimal_comma() -> Result<()> {
+    let expr = r#"<math><mn>5.37</mn></math>"#;
+    test_braille("Finnish", expr, "⠼⠑⠂⠉⠛")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_equal() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>≠</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠐⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greater_than() -> Result<()> {
+    let expr = r#"<math><mi>π</mi><mo>&gt;</mo><mn>3</mn></math>"#;
+    test_braille("Finnish", expr, "⠨⠏⠀⠱⠀⠼⠉")?;
+    return Ok(());
+
+}
+
+#[test]
+fn less_than_or_equal() -> Result<()> {
+    let expr = r#"<math><mn>0</mn><mo>&lt;</mo><mi>b</mi><mo>≤</mo><mn>5</mn></math>"#;
+    test_braille("Finnish", expr, "⠼⠚⠀⠣⠀⠃⠀⠣⠶⠀⠼⠑")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greater_than_or_equal() -> Result<()> {
+    let expr = r#"<math><mi>a</mi><mo>≥</mo><mi>b</mi></math>"#;
+    test_braille("Finnish", expr, "⠁⠀⠱⠶⠀⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn much_less_than() -> Result<()> {
+    let expr = r#"<math><mi>T</mi><mo>≪</mo><msub><mi>T</mi><mi>c</mi></msub></math>"#;
+    test_braille("Finnish", expr, "⠠⠞⠀⠣⠣⠀⠠⠞⠡⠉")?;
+    return Ok(());
+
+}
+
+#[test]
+fn approximately_equal() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>≈</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠸⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn similar() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>∼</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠨⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn plus_or_minus() -> Result<()> {
+    let expr = r#"<math><mi>α</mi><mo>±</mo><mn>2</mn><mi>π</mi></math>"#;
+    test_braille("Finnish", expr, "⠨⠁⠖⠤⠼⠃⠀⠨⠏")?;
+    return Ok(());
+
+}
+
+#[test]
+fn dot_times() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>⋅</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠄⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn element_of() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>∈</mo><mi>A</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠳⠔⠀⠠⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_element_of() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>∉</mo><mi>A</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠳⠐⠔⠀⠠⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subset() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>⊂</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠀⠳⠪⠀⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subset_or_equal() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>⊆</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠀⠳⠶⠪⠀⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_subset() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>⊄</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠀⠳⠐⠪⠀⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn empty_set() -> Result<()> {
+    let expr = r#"<math><mo>∅</mo><mo>⊂</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠳⠴⠀⠳⠪⠀⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn intersection() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>∩</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠳⠦⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn union() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>∪</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠳⠖⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn set_difference() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>∖</mo><mi>B</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠳⠡⠠⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn exists() -> Result<()> {
+    let expr = r#"<math><mo>∃</mo><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠳⠢⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn negation() -> Result<()> {
+    let expr = r#"<math><mo>¬</mo><mi>P</mi></math>"#;
+    test_braille("Finnish", expr, "⠳⠲⠠⠏")?;
+    return Ok(());
+
+}
+
+#[test]
+fn perpendicular() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mi>B</mi><mo>⊥</mo><mi>C</mi><mi>D</mi></math>"#;
+    test_braille("Finnish", expr, "⠠⠁⠠⠃⠀⠼⠄⠀⠠⠉⠠⠙")?;
+    return Ok(());
+
+}
+
+#[test]
+fn implication() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>⇒</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠤⠤⠱⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn equivalence() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>⇔</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠣⠤⠤⠱⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn left_arrow() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>←</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠣⠤⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn two_way_arrow() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>↔</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠣⠤⠱⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn north_east_arrow() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>↗</mo><mi>y</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠔⠱⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn summation() -> Result<()> {
+    let expr = r#"<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msub><mi>x</mi><mi>i</mi></msub></math>"#;
+    test_braille("Finnish", expr, "⠸⠎⠡⠦⠊⠶⠼⠁⠀⠴⠬⠝⠐⠭⠡⠊")?;
+    return Ok(());
+
+}
+
+#[test]
+fn product() -> Result<()> {
+    let expr = r#"<math><munderover><mo>∏</mo><mrow><mi>k</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msub><mi>x</mi><mi>k</mi></msub></math>"#;
+    test_braille("Finnish", expr, "⠸⠏⠡⠦⠅⠶⠼⠁⠀⠴⠬⠝⠐⠭⠡⠅")?;
+    return Ok(());
+
+}
+
+#[test]
+fn integral() -> Result<()> {
+    let expr = r#"<math><msubsup><mo>∫</mo><mi>a</mi><mi>b</mi></msubsup><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mi>d</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠮⠡⠁⠬⠃⠐⠋⠦⠭⠴⠙⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn sine() -> Result<()> {
+    let expr = r#"<math><mi>sin</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠎⠊⠝⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn cosine() -> Result<()> {
+    let expr = r#"<math><mi>cos</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠉⠕⠎⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn tangent() -> Result<()> {
+    let expr = r#"<math><mi>tan</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠞⠁⠝⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn natural_logarithm() -> Result<()> {
+    let expr = r#"<math><mi>ln</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠇⠝⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn arcsine() -> Result<()> {
+    let expr = r#"<math><mi>arcsin</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠁⠗⠉⠎⠊⠝⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn cube_root() -> Result<()> {
+    let expr = r#"<math><mroot><mn>27</mn><mn>3</mn></mroot></math>"#;
+    test_braille("Finnish", expr, "⠩⠼⠉⠐⠼⠃⠛")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subscript() -> Result<()> {
+    let expr = r#"<math><msub><mi>x</mi><mn>1</mn></msub></math>"#;
+    test_braille("Finnish", expr, "⠭⠡⠼⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn superscript() -> Result<()> {
+    let expr = r#"<math><msup><mi>z</mi><mi>n</mi></msup></math>"#;
+    test_braille("Finnish", expr, "⠵⠬⠝")?;
+    return Ok(());
+
+}
+
+#[test]
+fn factorial() -> Result<()> {
+    let expr = r#"<math><mi>n</mi><mo>!</mo></math>"#;
+    test_braille("Finnish", expr, "⠝⠠⠲")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nabla() -> Result<()> {
+    let expr = r#"<math><mo>∇</mo><mo>(</mo><mi>f</mi><mi>g</mi><mo>)</mo></math>"#;
+    test_braille("Finnish", expr, "⠸⠲⠦⠋⠛⠴")?;
+    return Ok(());
+
+}
+
+#[test]
+fn prime() -> Result<()> {
+    let expr = r#"<math><msup><mi>r</mi><mo>′</mo></msup></math>"#;
+    test_braille("Finnish", expr, "⠗⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn double_prime() -> Result<()> {
+    let expr = r#"<math><msup><mi>r</mi><mo>″</mo></msup></math>"#;
+    test_braille("Finnish", expr, "⠗⠔⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn numeric_fraction() -> Result<()> {
+    let expr = r#"<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>"#;
+    test_braille("Finnish", expr, "⠼⠁⠆")?;
+    return Ok(());
+
+}
+
+#[test]
+fn vulgar_fraction() -> Result<()> {
+    let expr = r#"<math><mn>¾</mn></math>"#;
+    test_braille("Finnish", expr, "⠼⠉⠲")?;
+    return Ok(());
+
+}
+
+#[test]
+fn angle_brackets() -> Result<()> {
+    let expr = r#"<math><mo>⟨</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>⟩</mo></math>"#;
+    test_braille("Finnish", expr, "⠣⠭⠄⠀⠽⠜")?;
+    return Ok(());
+
+}
+
+#[test]
+fn triangle() -> Result<()> {
+    let expr = r#"<math><mo>△</mo><mi>A</mi><mi>B</mi><mi>C</mi></math>"#;
+    test_braille("Finnish", expr, "⠫⠙⠠⠁⠠⠃⠠⠉")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greek_letters() -> Result<()> {
+    let expr = r#"<math><mi>η</mi><mo>+</mo><mi>θ</mi><mo>+</mo><mi>ω</mi></math>"#;
+    test_braille("Finnish", expr, "⠨⠜⠖⠨⠹⠖⠨⠚")?;
+    return Ok(());
+
+}
+
+#[test]
+fn capital_greek_letter() -> Result<()> {
+    let expr = r#"<math><mi>Δ</mi><mi>x</mi></math>"#;
+    test_braille("Finnish", expr, "⠸⠙⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn overline() -> Result<()> {
+    let expr = r#"<math><mi>a</mi><mover><mi>b</mi><mo>¯</mo></mover></math>"#;
+    test_braille("Finnish", expr, "⠁⠃⠱")?;
+    return Ok(());
+
+}
+
+#[test]
+fn infinity() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>→</mo><mi>∞</mi></math>"#;
+    test_braille("Finnish", expr, "⠭⠀⠤⠱⠀⠿")?;
+    return Ok(());
+
+}
+
+#[test]
+fn parallel() -> Result<()> {
+    let expr = r#"<math><mi>a</mi><mo>∥</mo><mi>b</mi></math>"#;
+    test_braille("Finnish", expr, "⠁⠀⠸⠸⠀⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nordic_letters() -> Result<()> {
+    let expr = r#"<math><mi>ä</mi><mo>+</mo><mi>Ö</mi></math>"#;
+    test_braille("Finnish", expr, "⠜⠖⠠⠪")?;
+    return Ok(());
+
+}
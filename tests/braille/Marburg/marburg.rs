@@ -0,0 +1,606 @@
+// German (Marburg) braille tests
+// Most expressions are the Marburg examples from the table of math braille codes created by Georgios Kouroupetroglou
+//   (PythonScripts/BrailleMathCodes Repository.csv); the expected braille is built from the Marburg symbols listed there.
+// The tests at the end cover the Marburg number rules (dropped digits, the lowercase indicator after a number).
+use crate::common::*;
+use anyhow::Result;
+
+#[test]
+fn regular_plus() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>+</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠖⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn regular_minus() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>-</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠤⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn plus_or_minus() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#xB1;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠖⠤⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn times_dot() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x22C5;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠄⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn multiplication_sign() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#xD7;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠦⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn hollow_dot() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2218;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠴⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn less_than() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&lt;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠪⠄⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greater_than() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&gt;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠕⠂⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn less_or_equal() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2264;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠪⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greater_or_equal() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2265;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠕⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn much_less() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x226A;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠪⠪⠄⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_equal() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2260;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠔⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn identical() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2261;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠶⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_identical() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2262;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠔⠶⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn simple_tilde() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x223C;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠢⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn double_tilde() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2248;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠢⠢⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn congruent() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2245;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠢⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn corresponds_to() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo mathvariant="normal">&#x2259;</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠬⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn defined_as() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>&#x2254;</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠒⠶⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn ratio() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>&#x2236;</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠒⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn element_of() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2208;</mo><mi mathvariant="italic">A</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠯⠑⠀⠨⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_element_of() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="normal">x</mi><mo mathvariant="normal">&#x2209;</mo><mi mathvariant="normal">A</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠔⠯⠑⠀⠨⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn contains() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x220B;</mo><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠀⠯⠔⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subset() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x2282;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠀⠣⠄⠀⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn subset_or_equal() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x2286;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠀⠣⠶⠀⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn not_superset() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x2285;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠀⠔⠜⠂⠀⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn intersection() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x2229;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠬⠄⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn union() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x222A;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠩⠄⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn set_difference() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">A</mi><mo mathvariant="normal">&#x2216;</mo><mi mathvariant="italic">B</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠡⠄⠨⠃")?;
+    return Ok(());
+
+}
+
+#[test]
+fn logical_and() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2227;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠬⠂⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn logical_or() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="normal">x</mi><mo mathvariant="normal">&#x2228;</mo><mi mathvariant="normal">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠩⠂⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn logical_not() -> Result<()> {
+    let expr = r#"<math><mo>&#xAC;</mo><mi>x</mi></math>"#;
+    test_braille("Marburg", expr, "⠒⠔⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn for_all() -> Result<()> {
+    let expr = r#"<math><mo mathvariant="normal">&#x2200;</mo><mi mathvariant="normal">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠯⠂⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn implication() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mi mathvariant="normal"> </mi><mo mathvariant="normal">&#x21D2;</mo><mi mathvariant="normal"> </mi><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠶⠶⠕⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn equivalence() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mi mathvariant="normal"> </mi><mo mathvariant="normal">&#x21D4;</mo><mi mathvariant="normal"> </mi><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠪⠶⠶⠕⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn two_way_arrow() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>&#x2194;</mo><mi>y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠐⠒⠂⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn left_arrow() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2190;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠐⠒⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn arrow_up() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x2191;</mo><mi mathvariant="italic">y</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠹⠐⠆⠀⠽")?;
+    return Ok(());
+
+}
+
+#[test]
+fn maps_to() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">x</mi><mo mathvariant="normal">&#x21A6;</mo><mi class="unknown_entity" mathvariant="normal">arctan</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠘⠒⠂⠀⠫⠂⠞⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn parentheses() -> Result<()> {
+    let expr = r#"<math><mo>(</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>)</mo></math>"#;
+    test_braille("Marburg", expr, "⠣⠭⠖⠽⠜")?;
+    return Ok(());
+
+}
+
+#[test]
+fn square_brackets() -> Result<()> {
+    let expr = r#"<math><mo>[</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>]</mo></math>"#;
+    test_braille("Marburg", expr, "⠷⠭⠖⠽⠾")?;
+    return Ok(());
+
+}
+
+#[test]
+fn curly_braces() -> Result<()> {
+    let expr = r#"<math><mo>{</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>}</mo></math>"#;
+    test_braille("Marburg", expr, "⠐⠷⠭⠖⠽⠐⠾")?;
+    return Ok(());
+
+}
+
+#[test]
+fn vertical_bars() -> Result<()> {
+    let expr = r#"<math><mo>|</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>|</mo></math>"#;
+    test_braille("Marburg", expr, "⠈⠇⠭⠖⠽⠈⠇")?;
+    return Ok(());
+
+}
+
+#[test]
+fn angle_brackets() -> Result<()> {
+    let expr = r#"<math><mo mathvariant="normal">&#x27E8;</mo><mi mathvariant="italic">x</mi><mo mathvariant="normal">,</mo><mi mathvariant="italic">y</mi><mo mathvariant="normal">&#x27E9;</mo></math>"#;
+    test_braille("Marburg", expr, "⠠⠷⠭⠂⠀⠽⠠⠾")?;
+    return Ok(());
+
+}
+
+#[test]
+fn sine() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">sin</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠎⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn cosine() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">cos</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠉⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn cosecant() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="normal">csc</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠣⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn arcsine() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">arcsin</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠂⠎⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn hyperbolic_tangent() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="normal">tanh</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠦⠞⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn arccosine() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">arccos</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠂⠉⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn logarithm() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">log</mi><mi mathvariant="italic">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠇⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn natural_logarithm() -> Result<()> {
+    let expr = r#"<math><mi class="unknown_entity" mathvariant="normal">ln</mi><mi mathvariant="normal">x</mi></math>"#;
+    test_braille("Marburg", expr, "⠫⠦⠇⠀⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn prime() -> Result<()> {
+    let expr = r#"<math><mi>f</mi><mo>&#x2032;</mo></math>"#;
+    test_braille("Marburg", expr, "⠋⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn factorial() -> Result<()> {
+    let expr = r#"<math><mi>n</mi><mo>!</mo></math>"#;
+    test_braille("Marburg", expr, "⠝⠫")?;
+    return Ok(());
+
+}
+
+#[test]
+fn greek_letters() -> Result<()> {
+    let expr = r#"<math><mi>&#x3B1;</mi><mo>+</mo><mi>&#x3C7;</mi><mo>+</mo><mi>&#x3C9;</mi></math>"#;
+    test_braille("Marburg", expr, "⠰⠁⠖⠰⠉⠖⠰⠺")?;
+    return Ok(());
+
+}
+
+#[test]
+fn capital_greek() -> Result<()> {
+    let expr = r#"<math><mi>&#x3A9;</mi><mo>+</mo><mi>&#x394;</mi></math>"#;
+    test_braille("Marburg", expr, "⠰⠘⠺⠖⠰⠘⠙")?;
+    return Ok(());
+
+}
+
+#[test]
+fn summation() -> Result<()> {
+    let expr = r#"<math><munderover><mstyle displaystyle="true" mathvariant="normal"><mo>&#x2211;</mo></mstyle><mrow><mi mathvariant="italic">&#x3BD;</mi><mi mathvariant="normal">=</mi><mn mathvariant="normal">1</mn></mrow><mrow><mo mathvariant="normal">&#x221E;</mo></mrow></munderover><msub><mi mathvariant="italic">a</mi><mrow><mi mathvariant="italic">&#x3BD;</mi></mrow></msub></math>"#;
+    test_braille("Marburg", expr, "⠯⠎⠡⠢⠰⠝⠶⠼⠁⠀⠔⠌⠼⠿⠀⠁⠡⠰⠝")?;
+    return Ok(());
+
+}
+
+#[test]
+fn integral() -> Result<()> {
+    let expr = r#"<math><msubsup><mstyle displaystyle="true" mathvariant="normal"><mo>&#x222B;</mo></mstyle><mrow><msub><mi mathvariant="italic">x</mi><mrow><mi mathvariant="italic">k</mi><mo mathvariant="normal">-</mo><mn mathvariant="normal">1</mn></mrow></msub></mrow><mrow><msub><mi mathvariant="italic">x</mi><mrow><mi mathvariant="italic">k</mi><mi mathvariant="normal">+</mi><mn mathvariant="normal">1</mn></mrow></msub></mrow></msubsup></math>"#;
+    test_braille("Marburg", expr, "⠮⠡⠭⠡⠢⠅⠤⠼⠁⠀⠔⠌⠭⠡⠢⠅⠖⠼⠁⠀⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn nested_roots() -> Result<()> {
+    let expr = r#"<math><mroot><mrow><mi>b</mi><mo>&#xB7;</mo><mroot><mrow><msup><mi>b</mi><mn>2</mn></msup><mo>&#xB7;</mo><msqrt><mi>b</mi></msqrt></mrow><mn>3</mn></mroot></mrow><mn>4</mn></mroot></math>"#;
+    test_braille("Marburg", expr, "⠌⠲⠩⠢⠃⠄⠌⠒⠩⠢⠃⠌⠆⠄⠩⠃⠔⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn punctuation_indicator() -> Result<()> {
+    let expr = r#"<math><mi mathvariant="italic">a</mi><mo mathvariant="normal">&#x2265;</mo><mn mathvariant="normal">0</mn><mo mathvariant="normal">,</mo><mi mathvariant="italic">n</mi><mo mathvariant="normal">,</mo><mi mathvariant="italic">z</mi><mo mathvariant="normal">&#x2208;</mo><mi mathvariant="double-struck">N</mi></math>"#;
+    test_braille("Marburg", expr, "⠁⠀⠕⠶⠀⠼⠚⠠⠂⠀⠝⠂⠀⠵⠀⠯⠑⠀⠨⠨⠝")?;
+    return Ok(());
+
+}
+
+#[test]
+fn vulgar_fraction() -> Result<()> {
+    let expr = r#"<math><mn>&#xBD;</mn></math>"#;
+    test_braille("Marburg", expr, "⠼⠁⠆")?;
+    return Ok(());
+
+}
+
+#[test]
+fn numeric_fraction() -> Result<()> {
+    let expr = r#"<math><mfrac><mn>3</mn><mn>4</mn></mfrac></math>"#;
+    test_braille("Marburg", expr, "⠼⠉⠲")?;
+    return Ok(());
+
+}
+
+#[test]
+fn infinity() -> Result<()> {
+    let expr = r#"<math><mi>n</mi><mo>&#x2192;</mo><mi>&#x221E;</mi></math>"#;
+    test_braille("Marburg", expr, "⠝⠀⠒⠂⠀⠼⠿")?;
+    return Ok(());
+
+}
+
+#[test]
+fn degree() -> Result<()> {
+    let expr = r#"<math><mn>90</mn><mo>&#xB0;</mo></math>"#;
+    test_braille("Marburg", expr, "⠼⠊⠚⠸⠈⠴")?;
+    return Ok(());
+
+}
+
+#[test]
+fn double_struck_capital() -> Result<()> {
+    let expr = r#"<math><mi>x</mi><mo>&#x2208;</mo><mi>&#x211D;</mi></math>"#;
+    test_braille("Marburg", expr, "⠭⠀⠯⠑⠀⠨⠨⠗")?;
+    return Ok(());
+
+}
+
+#[test]
+fn empty_set() -> Result<()> {
+    let expr = r#"<math><mi>A</mi><mo>=</mo><mi>&#x2205;</mi></math>"#;
+    test_braille("Marburg", expr, "⠨⠁⠀⠶⠀⠯⠕")?;
+    return Ok(());
+
+}
+
+#[test]
+fn perpendicular() -> Result<()> {
+    let expr = r#"<math><mi>g</mi><mo>&#x22A5;</mo><mi>h</mi></math>"#;
+    test_braille("Marburg", expr, "⠛⠀⠼⠄⠀⠓")?;
+    return Ok(());
+
+}
+
+#[test]
+fn boxed() -> Result<()> {
+    let expr = r#"<math><menclose notation="box"><mi>x</mi></menclose></math>"#;
+    test_braille("Marburg", expr, "⠻⠶⠢⠭⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn number_then_letter() -> Result<()> {
+    let expr = r#"<math><mn>2</mn><mi>a</mi></math>"#;
+    test_braille("Marburg", expr, "⠼⠃⠠⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn numeric_fraction_two_digit_denominator() -> Result<()> {
+    let expr = r#"<math><mfrac><mn>3</mn><mn>10</mn></mfrac><mo>+</mo><mi>x</mi></math>"#;
+    test_braille("Marburg", expr, "⠼⠉⠂⠴⠀⠖⠭")?;
+    return Ok(());
+
+}
+
+#[test]
+fn mixed_number() -> Result<()> {
+    let expr = r#"<math><mn>2</mn><mfrac><mn>3</mn><mn>4</mn></mfrac></math>"#;
+    test_braille("Marburg", expr, "⠼⠃⠼⠉⠲")?;
+    return Ok(());
+
+}
+
+#[test]
+fn general_fraction() -> Result<()> {
+    let expr = r#"<math><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mrow><mi>c</mi><mo>-</mo><mi>d</mi></mrow></mfrac></math>"#;
+    test_braille("Marburg", expr, "⠢⠁⠖⠃⠔⠳⠢⠉⠤⠙⠔")?;
+    return Ok(());
+
+}
+
+#[test]
+fn numeric_superscript() -> Result<()> {
+    let expr = r#"<math><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>1</mn></math>"#;
+    test_braille("Marburg", expr, "⠭⠌⠆⠀⠖⠼⠁")?;
+    return Ok(());
+
+}
+
+#[test]
+fn limit() -> Result<()> {
+    let expr = r#"<math><munder><mi>lim</mi><mrow><mi>x</mi><mo>→</mo><mn>0</mn></mrow></munder><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo></math>"#;
+    test_braille("Marburg", expr, "⠼⠇⠡⠢⠭⠒⠂⠼⠚⠀⠔⠀⠋⠣⠭⠜")?;
+    return Ok(());
+
+}
+
+#[test]
+fn decimal_and_list() -> Result<()> {
+    let expr = r#"<math><mn>3.14</mn><mo>,</mo><mn>2</mn></math>"#;
+    test_braille("Marburg", expr, "⠼⠉⠂⠁⠙⠠⠂⠀⠼⠃")?;
+    return Ok(());
+
+}
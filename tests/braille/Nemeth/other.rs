@@ -175,3 +175,117 @@ fn no_omission_for_spaces_in_middle() -> Result<()> {
 
 }
 
+
+#[test]
+fn mstack_addition() -> Result<()> {
+    let expr = "<math><mstack>
+            <mscarries><mn>1</mn><none/><none/></mscarries>
+            <mn>424</mn>
+            <msrow><mo>+</mo><mn>33</mn></msrow>
+            <msline/>
+            <mn>457</mn>
+        </mstack></math>";
+    // the operation sign goes in the column to the left of the longest number and the line extends under it
+    test_braille("Nemeth", expr, "⠀⠂\n⠀⠲⠆⠲\n⠬⠀⠒⠒\n⠒⠒⠒⠒\n⠀⠲⠢⠶")?;
+    return Ok(());
+}
+
+#[test]
+fn mstack_multiplication() -> Result<()> {
+    let expr = "<math><mstack>
+            <mn>123</mn>
+            <msrow><mo>×</mo><mn>45</mn></msrow>
+            <msline/>
+            <mn>615</mn>
+            <msrow><mn>492</mn><none/></msrow>
+            <msline/>
+            <mn>5535</mn>
+        </mstack></math>";
+    test_braille("Nemeth", expr, "⠀⠀⠂⠆⠒\n⠈⠡⠀⠲⠢\n⠒⠒⠒⠒⠒\n⠀⠀⠖⠂⠢\n⠀⠲⠔⠆\n⠒⠒⠒⠒⠒\n⠀⠢⠢⠒⠢")?;
+    return Ok(());
+}
+
+#[test]
+fn mlongdiv() -> Result<()> {
+    let expr = "<math><mlongdiv longdivstyle='lefttop'>
+            <mn>3</mn>
+            <mn>15</mn>
+            <mn>47</mn>
+            <msgroup position='1'><mn>3</mn><msline length='1'/></msgroup>
+            <msgroup>
+              <mn>17</mn>
+              <mn>15</mn><msline length='2'/>
+              <mn>2</mn>
+            </msgroup>
+        </mlongdiv></math>";
+    test_braille("Nemeth", expr, "⠀⠀⠀⠂⠢\n⠀⠀⠀⠒⠒\n⠒⠈⠣⠲⠶\n⠀⠀⠀⠒\n⠀⠀⠀⠒\n⠀⠀⠀⠂⠶\n⠀⠀⠀⠂⠢\n⠀⠀⠀⠒⠒\n⠀⠀⠀⠀⠆")?;
+    return Ok(());
+}
+
+#[test]
+fn mstack_empty() -> Result<()> {
+    // nothing to lay out -- these are brailled the same as "<math><mrow/></math>"
+    test_braille("Nemeth", "<math><mstack/></math>", "⠿")?;
+    test_braille("Nemeth", "<math><mstack><mscarries/></mstack></math>", "⠿")?;
+    test_braille("Nemeth", "<math><mstack><mscarries/><mn>12</mn></mstack></math>", "⠂⠆")?;
+    return Ok(());
+}
+
+#[test]
+fn line_length_runover() -> Result<()> {
+    let expr = "<math><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>=</mo>
+            <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>12345</mn><mi>y</mi><mo>-</mo><mn>7</mn><mo>+</mo><mi>z</mi></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleLineLength", "12")], expr,
+        "⠁⠬⠃⠬⠹⠂⠌⠆⠼⠐\n⠀⠀⠨⠅⠀⠭⠘⠆⠐⠐\n⠀⠀⠬⠂⠆⠒⠲⠢⠽⠤⠶⠐\n⠀⠀⠬⠵")?;
+    return Ok(());
+}
+
+#[test]
+fn line_length_matrix() -> Result<()> {
+    let expr = "<math><mrow><mo>(</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+            <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+        </mtable><mo>)</mo></mrow></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleLineLength", "12")], expr, "⠠⠷⠼⠂⠀⠼⠆\n⠼⠒⠀⠼⠲⠠⠾")?;
+    return Ok(());
+}
+
+#[test]
+fn menclose_actuarial() -> Result<()> {
+    let expr = "<math><msub><mi>a</mi><menclose notation='actuarial'><mi>n</mi></menclose></msub></math>";
+    test_braille("Nemeth", expr, "⠁⠰⠝⠱⠳")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_madruwb() -> Result<()> {
+    let expr = "<math><menclose notation='madruwb'><mi>n</mi></menclose></math>";
+    test_braille("Nemeth", expr, "⠝⠯")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_radical() -> Result<()> {
+    let expr = "<math><menclose notation='radical'><mi>x</mi></menclose></math>";
+    test_braille("Nemeth", expr, "⠜⠭⠻")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_longdiv() -> Result<()> {
+    let expr = "<math><menclose notation='longdiv'><mn>125</mn></menclose></math>";
+    test_braille("Nemeth", expr, "⠳⠐⠂⠆⠢⠣⠱⠻")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_leftrightarrow() -> Result<()> {
+    let expr = "<math><menclose notation='leftrightarrow'><mi>x</mi></menclose></math>";
+    test_braille("Nemeth", expr, "⠫⠪⠒⠒⠕⠸⠫⠭⠻")?;
+    return Ok(());
+
+}
@@ -199,3 +199,96 @@ fn caps_bug_295() -> Result<()> {
     return Ok(());
 
 }
+
+#[test]
+fn mstack_addition() -> Result<()> {
+    let expr = "<math><mstack>
+            <mscarries><mn>1</mn><none/><none/></mscarries>
+            <mn>424</mn>
+            <msrow><mo>+</mo><mn>33</mn></msrow>
+            <msline/>
+            <mn>457</mn>
+        </mstack></math>";
+    test_braille("UEB", expr, "⠀⠼⠁\n⠀⠼⠙⠃⠙\n⠐⠖⠼⠉⠉\n⠒⠒⠒⠒⠒\n⠀⠼⠙⠑⠛")?;
+    return Ok(());
+}
+
+#[test]
+fn mstack_multiplication() -> Result<()> {
+    let expr = "<math><mstack>
+            <mn>123</mn>
+            <msrow><mo>×</mo><mn>45</mn></msrow>
+            <msline/>
+            <mn>615</mn>
+            <msrow><mn>492</mn><none/></msrow>
+            <msline/>
+            <mn>5535</mn>
+        </mstack></math>";
+    test_braille("UEB", expr, "⠀⠼⠁⠃⠉\n⠐⠦⠼⠙⠑\n⠒⠒⠒⠒⠒\n⠀⠼⠋⠁⠑\n⠼⠙⠊⠃\n⠒⠒⠒⠒⠒\n⠼⠑⠑⠉⠑")?;
+    return Ok(());
+}
+
+#[test]
+fn mstack_empty() -> Result<()> {
+    // nothing to lay out -- these are brailled the same as "<math><mrow/></math>"
+    test_braille("UEB", "<math><mstack/></math>", "⠬")?;
+    test_braille("UEB", "<math><mstack><mscarries/></mstack></math>", "⠬")?;
+    test_braille("UEB", "<math><mstack><mscarries/><mn>12</mn></mstack></math>", "⠼⠁⠃")?;
+    return Ok(());
+}
+
+#[test]
+fn line_length_runover() -> Result<()> {
+    let expr = "<math><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>=</mo>
+            <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>12345</mn><mi>y</mi><mo>-</mo><mn>7</mn><mo>+</mo><mi>z</mi></math>";
+    test_braille_prefs("UEB", vec![("BrailleLineLength", "12")], expr,
+        "⠁⠐⠖⠃⠐⠖⠼⠁⠌⠃\n⠀⠀⠐⠶⠀⠭⠰⠔⠼⠃\n⠀⠀⠐⠖⠼⠁⠃⠉⠙⠑⠽\n⠀⠀⠐⠤⠼⠛⠐⠖⠵")?;
+    return Ok(());
+}
+
+#[test]
+fn line_length_long_number() -> Result<()> {
+    let expr = "<math><mn>12345678901234567890123456</mn></math>";
+    test_braille_prefs("UEB", vec![("BrailleLineLength", "12")], expr, "⠼⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚⠐\n⠀⠀⠁⠃⠉⠙⠑⠋⠛⠓⠊⠐\n⠀⠀⠚⠁⠃⠉⠙⠑⠋")?;
+    return Ok(());
+}
+
+#[test]
+fn menclose_actuarial() -> Result<()> {
+    let expr = "<math><msub><mi>a</mi><menclose notation='actuarial'><mi>n</mi></menclose></msub></math>";
+    test_braille("UEB", expr, "⠰⠰⠁⠢⠣⠝⠱⠸⠜")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_madruwb() -> Result<()> {
+    let expr = "<math><menclose notation='madruwb'><mi>n</mi></menclose></math>";
+    test_braille("UEB", expr, "⠰⠝⠖")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_radical() -> Result<()> {
+    let expr = "<math><menclose notation='radical'><mi>x</mi></menclose></math>";
+    test_braille("UEB", expr, "⠰⠰⠩⠭⠬")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_longdiv() -> Result<()> {
+    let expr = "<math><menclose notation='longdiv'><mn>125</mn></menclose></math>";
+    test_braille("UEB", expr, "⠸⠼⠁⠃⠑⠱")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_leftrightarrow() -> Result<()> {
+    let expr = "<math><menclose notation='leftrightarrow'><mi>x</mi></menclose></math>";
+    test_braille("UEB", expr, "⠭⠰⠳⠺⠗⠕⠻")?;
+    return Ok(());
+
+}
@@ -1177,3 +1177,42 @@ fn vi_text42() -> Result<()> {
 
 }
 
+#[test]
+fn menclose_actuarial() -> Result<()> {
+    let expr = "<math><msub><mi>a</mi><menclose notation='actuarial'><mi>n</mi></menclose></msub></math>";
+    test_braille("Vietnam", expr, "⠁⠢⠣⠝⠨⠱⠸⠱")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_madruwb() -> Result<()> {
+    let expr = "<math><menclose notation='madruwb'><mi>n</mi></menclose></math>";
+    test_braille("Vietnam", expr, "⠝⠖")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_radical() -> Result<()> {
+    let expr = "<math><menclose notation='radical'><mi>x</mi></menclose></math>";
+    test_braille("Vietnam", expr, "⠩⠭⠱")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_longdiv() -> Result<()> {
+    let expr = "<math><menclose notation='longdiv'><mn>125</mn></menclose></math>";
+    test_braille("Vietnam", expr, "⠸⠼⠁⠃⠑⠨⠱")?;
+    return Ok(());
+
+}
+
+#[test]
+fn menclose_leftrightarrow() -> Result<()> {
+    let expr = "<math><menclose notation='leftrightarrow'><mi>x</mi></menclose></math>";
+    test_braille("Vietnam", expr, "⠭⠳⠺⠗⠕⠻")?;
+    return Ok(());
+
+}
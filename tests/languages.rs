@@ -1,4 +1,5 @@
 #![allow(non_snake_case)]
+#![allow(clippy::needless_return, clippy::doc_lazy_continuation, clippy::module_inception)]
 
 mod common;
 
@@ -15,6 +16,7 @@ mod Languages {
         mod vi;
     }
     mod id {
+        mod chemistry;
         mod units;
     }
     // mod es;